@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use json_keyquotes_convert::{json_key_quote_utils, load_write_utils, Quotes};
+
+fn load_test_resource(name: &str) -> String {
+    load_write_utils::load_json(&Path::new("./test_resources").join(name)).unwrap()
+}
+
+fn bench_conversions(c: &mut Criterion) {
+    let json_without_keyquotes = load_test_resource("Test_without_keyquotes.json");
+    let json_with_keyquotes = load_test_resource("Test_with_keyquotes.json");
+
+    c.bench_function("json_add_key_quotes", |b| {
+        b.iter(|| {
+            json_key_quote_utils::json_add_key_quotes(
+                black_box(&json_without_keyquotes),
+                Quotes::DoubleQuote,
+            )
+        })
+    });
+    c.bench_function("json_add_key_quotes_custom", |b| {
+        b.iter(|| {
+            json_key_quote_utils::json_add_key_quotes(
+                black_box(&json_without_keyquotes),
+                Quotes::Custom('§'),
+            )
+        })
+    });
+    c.bench_function("json_remove_key_quotes", |b| {
+        b.iter(|| json_key_quote_utils::json_remove_key_quotes(black_box(&json_with_keyquotes)))
+    });
+    c.bench_function("json_escape_ctrlchars", |b| {
+        b.iter(|| json_key_quote_utils::json_escape_ctrlchars(black_box(&json_with_keyquotes)))
+    });
+    c.bench_function("json_unescape_ctrlchars", |b| {
+        b.iter(|| json_key_quote_utils::json_unescape_ctrlchars(black_box(&json_without_keyquotes)))
+    });
+}
+
+criterion_group!(benches, bench_conversions);
+criterion_main!(benches);
@@ -0,0 +1,117 @@
+//! The `json-keyquotes` command line tool, only available with the `cli` feature.
+//!
+//! Converts a JSON file from and to JSON without key-quotes, using the [JsonKeyQuoteConverter].
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand};
+use json_keyquotes_convert::{load_write_utils, JsonKeyQuoteConverter, Quotes};
+
+/// The path which stands for stdin or stdout.
+const STDIO_PATH: &str = "-";
+
+#[derive(Parser)]
+#[command(name = "json-keyquotes", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Adds key-quotes and escapes the ctrl-characters in the string values.
+    Add {
+        /// The quotes to add around the keys: `double`, `single`, `backtick` or a custom character.
+        #[arg(long, default_value_t = Quotes::DoubleQuote)]
+        quotes: Quotes,
+        #[command(flatten)]
+        io: IoArgs,
+    },
+    /// Removes key-quotes and unescapes the ctrl-characters in the string values.
+    Remove {
+        #[command(flatten)]
+        io: IoArgs,
+    },
+}
+
+#[derive(Args)]
+struct IoArgs {
+    /// The input file, or `-` for stdin.
+    input: String,
+    /// Writes the output to the input file.
+    #[arg(long, conflicts_with = "output")]
+    in_place: bool,
+    /// The output file, or `-` for stdout (default).
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Only reports whether the input would change, exits with 1 if it would.
+    #[arg(long, conflicts_with_all = ["in_place", "output"])]
+    check: bool,
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(exit_code) => exit_code,
+        Err(err) => {
+            eprintln!("json-keyquotes: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<ExitCode, String> {
+    let (io, quote_type) = match &cli.command {
+        Command::Add { quotes, io } => (io, *quotes),
+        Command::Remove { io } => (io, Quotes::default()),
+    };
+
+    let json = read_input(&io.input)?;
+    let converter = JsonKeyQuoteConverter::new(&json, quote_type)
+        .validate()
+        .map_err(|err| format!("{}: {}", io.input, err))?;
+    let converted = match cli.command {
+        Command::Add { .. } => converter.add_key_quotes().escape_ctrlchars(),
+        Command::Remove { .. } => converter.remove_key_quotes().unescape_ctrlchars(),
+    }
+    .json();
+
+    if io.check {
+        if converted == json {
+            return Ok(ExitCode::SUCCESS);
+        }
+        println!("{} would change", io.input);
+        return Ok(ExitCode::FAILURE);
+    }
+
+    let output = match (&io.output, io.in_place) {
+        (_, true) if io.input == STDIO_PATH => {
+            return Err("--in-place can not be used with stdin".to_string())
+        }
+        (_, true) => &io.input,
+        (Some(output), false) => output,
+        (None, false) => STDIO_PATH,
+    };
+    write_output(output, &converted)?;
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Reads the JSON string from the file, or from stdin.
+fn read_input(input: &str) -> Result<String, String> {
+    if input == STDIO_PATH {
+        return load_write_utils::load_json_stdin().map_err(|err| format!("stdin: {}", err));
+    }
+
+    load_write_utils::load_json(Path::new(input)).map_err(|err| format!("{}: {}", input, err))
+}
+
+/// Writes the JSON string to the file, or to stdout.
+fn write_output(output: &str, json: &str) -> Result<(), String> {
+    if output == STDIO_PATH {
+        return load_write_utils::write_json_stdout(json).map_err(|err| format!("stdout: {}", err));
+    }
+
+    load_write_utils::write_json(Path::new(output), json)
+        .map_err(|err| format!("{}: {}", output, err))
+}
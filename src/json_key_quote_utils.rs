@@ -2,19 +2,286 @@
 //!
 //! Contains the core functionality of this crate.
 
-use std::path::Path;
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use once_cell::sync::Lazy;
-use regex::Regex;
+#[cfg(not(feature = "lite"))]
+use regex::{escape as regex_escape, Captures, Regex};
+#[cfg(feature = "lite")]
+use regex_lite::{escape as regex_escape, Captures, Regex};
 
-use crate::{load_write_utils, Quotes};
+use crate::load_write_utils::{self, WriteOptions};
+use crate::scanner::{self, Member, Node, Token};
+use crate::stream;
+use crate::{ConvertOptions, Dialect, Indent, JsonKeyQuoteConverter, KeyCase, Quotes};
 
-const SUPPORTED_KEY_CHARS_REGEX_STR: &str = r#"A-Za-z0-9`~!@#$%€^&*()\-_=+\\|;"'.<>/?\s"#;
+const SUPPORTED_KEY_CHARS_REGEX_STR: &str = r#"A-Za-z0-9`~!@#$%€^&*()\-_=+\\|;"'.<>/?\s\x08\x00"#;
+
+/// The control characters with a short escaped variant,
+/// as handled by [json_escape_ctrlchars] and [json_unescape_ctrlchars].
+///
+/// The other control characters in the range U+0000–U+001F are escaped as `\uXXXX`, see [escape_ctrlchar].
+pub(crate) const CTRLCHAR_ESCAPES: [(&str, &str); 6] = [
+    ("\r", "\\r"),
+    ("\n", "\\n"),
+    ("\t", "\\t"),
+    ("\u{8}", "\\b"),
+    ("\u{c}", "\\f"),
+    ("\0", "\\u0000"),
+];
+
+/// The characters and their escaped variants,
+/// as handled by [json_escape_html] and [json_unescape_html].
+const HTML_ESCAPES: [(char, &str); 5] = [
+    ('<', "\\u003C"),
+    ('>', "\\u003E"),
+    ('&', "\\u0026"),
+    ('\'', "\\u0027"),
+    ('"', "\\u0022"),
+];
+
+static SINGLEQUOTED_STRING_VALUE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#":[\s]*?'((?:[^'\\]|\\.)*)'"#).unwrap());
+static DOUBLEQUOTED_STRING_VALUE_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#":[\s]*?"((?:[^"\\]|\\.)*)""#).unwrap());
+
+/// The maximum length in bytes of the keys quoted by [json_add_key_quotes].
+pub const DEFAULT_MAX_KEY_LEN: usize = 4096;
+
+// `/` == `\/` in Regex101
+static REMOVE_SINGLE_QUOTES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        &(r#"(?P<before>[{\[,][\s]*)'(?P<key>["#.to_string()
+            + SUPPORTED_KEY_CHARS_REGEX_STR
+            + r#"]*?)'(?P<after>\s*?:)"#),
+    )
+    .unwrap()
+});
+// `/` == `\/` in Regex101
+static REMOVE_DOUBLE_QUOTES_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        &(r#"(?P<before>[{\[,][\s]*)"(?P<key>["#.to_string()
+            + SUPPORTED_KEY_CHARS_REGEX_STR
+            + r#"]*?)"(?P<after>\s*?:)"#),
+    )
+    .unwrap()
+});
+
+static SUPPORTED_KEY_CHAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&(r#"^["#.to_string() + SUPPORTED_KEY_CHARS_REGEX_STR + r#"]$"#)).unwrap()
+});
+
+/// The regexes matching the quoted keys in [json_escape_ctrlchars].
+static ESCAPE_CTRLCHARS_KEY_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
+    // The keys may contain any control character, which is removed:
+    let key = |quote: &str| {
+        quote.to_string()
+            + r#"(?P<key>["#
+            + SUPPORTED_KEY_CHARS_REGEX_STR
+            + r#"\x01-\x1f]*?[^"'])"#
+            + quote
+    };
+    let string_key_prefix = r#"(?P<prevchar_key>[^"'][\s]*)"#;
+
+    [
+        // For all single-quoted string keys with single-quoted values:
+        string_key_prefix.to_string() + &key("'") + r#"(?P<val>\s*?:\s*?'[\s\S]*?')"#,
+        // For all double-quoted string keys with single-quoted values:
+        string_key_prefix.to_string() + &key("\"") + r#"(?P<val>\s*?:\s*?'[\s\S]*?')"#,
+        // For all single-quoted string keys with double-quoted values:
+        string_key_prefix.to_string() + &key("'") + r#"(?P<val>\s*?:\s*?"[\s\S]*?")"#,
+        // For all double-quoted string keys with double-quoted values:
+        string_key_prefix.to_string() + &key("\"") + r#"(?P<val>\s*?:\s*?"[\s\S]*?")"#,
+        // For all single-quoted object keys:
+        key("'") + r#"(?P<val>\s*?:\s*?[{\[])"#,
+        // For all double-quoted object keys:
+        key("\"") + r#"(?P<val>\s*?:\s*?[{\[])"#,
+        // For all single-quoted number keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string() + &key("'") + r#"(?P<after>\s*?:\s*?[\d\-\.])"#,
+        // For all double-quoted number keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string() + &key("\"") + r#"(?P<after>\s*?:\s*?[\d\-\.])"#,
+        // For all single-quoted null and boolean keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string()
+            + &key("'")
+            + r#"(?P<after>\s*?:\s*?(?:null|true|false))"#,
+        // For all double-quoted null and boolean keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string()
+            + &key("\"")
+            + r#"(?P<after>\s*?:\s*?(?:null|true|false))"#,
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// The regexes matching the unquoted keys in [json_unescape_ctrlchars].
+static UNESCAPE_CTRLCHARS_KEY_REGEXES: Lazy<Vec<Regex>> = Lazy::new(|| {
+    let key = r#"(?P<key>["#.to_string() + SUPPORTED_KEY_CHARS_REGEX_STR + r#"]*?[^"'])"#;
+
+    [
+        // For all single-quoted string keys:
+        r#"(?P<prevchar_key>[^"'][\s]*)"#.to_string() + &key + r#"(?P<val>\s*?:\s*?'[\s\S]*?')"#,
+        // For all double-quoted string keys:
+        r#"(?P<prevchar_key>[^"'][\s]*)"#.to_string() + &key + r#"(?P<val>\s*?:\s*?"[\s\S]*?")"#,
+        // For all object keys:
+        key.clone() + r#"(?P<val>\s*?:\s*?[{\[])"#,
+        // For all number keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string() + &key + r#"(?P<after>\s*?:\s*?[\d\-\.])"#,
+        // For all null and boolean keys:
+        r#"(?P<before>[\[,{]\s*?)"#.to_string()
+            + &key
+            + r#"(?P<after>\s*?:\s*?(?:null|true|false))"#,
+    ]
+    .iter()
+    .map(|pattern| Regex::new(pattern).unwrap())
+    .collect()
+});
+
+/// How [json_merge] resolves a key which exists in both JSON objects.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeStrategy {
+    /// Keep the value of the base JSON object.
+    TakeLeft,
+    /// Take the value of the overlay JSON object.
+    TakeRight,
+    /// Return an error with the conflicting key.
+    Error,
+}
+
+/// Which occurrence of a duplicate key [json_deduplicate_keys] keeps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepDuplicate {
+    /// Keep the first occurrence of the key.
+    First,
+    /// Keep the last occurrence of the key.
+    Last,
+}
+
+/// How [json_replace_nonfinite] replaces the `NaN` and `Infinity` literals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NonFinitePolicy {
+    /// Replace them by `null`.
+    Null,
+    /// Replace them by a double-quoted string value, like `"NaN"` or `"-Infinity"`.
+    StringLiteral,
+    /// Return an error with the first one.
+    Error,
+}
+
+/// A key which occurs more than once in the same JSON object, as found by [json_find_duplicate_keys].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DuplicateKey {
+    /// The key, without its quotes.
+    pub key: String,
+    /// The byte offsets of every occurrence of the key, including its opening quote.
+    pub offsets: Vec<usize>,
+}
+
+/// The difference between the keys of two JSON strings, as computed by [json_diff_keys].
+///
+/// All keys are without their quotes, sorted and deduplicated.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JsonKeyDiff {
+    /// The keys only found in the `after` JSON string.
+    pub added: Vec<String>,
+    /// The keys only found in the `before` JSON string.
+    pub removed: Vec<String>,
+    /// The keys found in both JSON strings.
+    pub unchanged: Vec<String>,
+}
+
+impl JsonKeyDiff {
+    /// Whether no keys were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The amount of keys and values changed by a conversion,
+/// as returned by [json_add_key_quotes_with_stats] for example.
+///
+/// Stats of multiple conversions can be summed with `+=`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConversionStats {
+    /// The amount of keys which got key-quotes.
+    pub keys_quoted: usize,
+    /// The amount of keys which lost their key-quotes.
+    pub keys_unquoted: usize,
+    /// The amount of string values with escaped ctrl-characters.
+    pub values_escaped: usize,
+    /// The amount of string values with unescaped ctrl-characters.
+    pub values_unescaped: usize,
+}
+
+impl std::ops::AddAssign for ConversionStats {
+    fn add_assign(&mut self, other: ConversionStats) {
+        self.keys_quoted += other.keys_quoted;
+        self.keys_unquoted += other.keys_unquoted;
+        self.values_escaped += other.values_escaped;
+        self.values_unescaped += other.values_unescaped;
+    }
+}
+
+/// A key of a JSON string, as returned by [keys].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeySpan {
+    /// The key text, without its quotes.
+    pub key: String,
+    /// The quotes found around the key: [Quotes::NoQuotes], [Quotes::SingleQuote],
+    /// [Quotes::DoubleQuote] or [Quotes::Backtick].
+    pub quotes: Quotes,
+    /// The byte range of the key in the JSON string, including its quotes.
+    pub span: Range<usize>,
+}
+
+/// A key encountered by [json_add_key_quotes_with].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    /// The key text, without its quotes.
+    pub key: String,
+    /// The byte range of the key in the original JSON string, including its quotes.
+    pub span: Range<usize>,
+    /// Whether the key was already quoted.
+    pub quoted: bool,
+}
+
+/// The outcome of a file conversion, like [json_convert_without_to_with_keyquotes].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileConversion {
+    /// The converted JSON string was written to the file.
+    Converted,
+    /// The file was already converted, so it was not written to.
+    Unchanged,
+}
+
+/// The direction of the conversion done by [convert_dir].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Converts JSON without key-quotes to JSON with key-quotes, see [JsonKeyQuoteConverter::convert].
+    AddKeyQuotes,
+    /// Converts JSON with key-quotes to JSON without key-quotes.
+    RemoveKeyQuotes,
+}
+
+/// The outcome of converting a single file in [convert_dir].
+#[derive(Debug)]
+pub struct FileResult {
+    /// The file path, or the directory path if the directory could not be read.
+    pub path: PathBuf,
+    /// Whether the file was converted, or the error which prevented it.
+    pub outcome: Result<FileConversion, io::Error>,
+}
 
 /// Convenience method for chained [load_write_utils::load_json],
 /// [json_remove_key_quotes], [json_unescape_ctrlchars]
 ///  and [load_write_utils::write_json] function calls.
 ///
+/// The file is only written to if the conversion changed its contents,
+/// see [load_write_utils::write_json_atomic].
+///
 /// # Arguments
 ///
 /// * `path` - The file path.
@@ -26,31 +293,54 @@ const SUPPORTED_KEY_CHARS_REGEX_STR: &str = r#"A-Za-z0-9`~!@#$%€^&*()\-_=+\\|;
 /// use json_keyquotes_convert::{json_key_quote_utils};
 ///
 /// let path = Path::new("./test_resources/Test_with_keyquotes.json");
-/// json_key_quote_utils::json_convert_with_to_without_keyquotes(path);
+/// json_key_quote_utils::json_convert_with_to_without_keyquotes(path)?;
 /// ```
-pub fn json_convert_with_to_without_keyquotes(path: &Path) {
-    let json = match load_write_utils::load_json(path) {
-        Ok(val) => val,
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
-        }
-    };
+pub fn json_convert_with_to_without_keyquotes(path: &Path) -> Result<FileConversion, io::Error> {
+    json_convert_with_to_without_keyquotes_with_options(path, &WriteOptions::default())
+}
+
+/// Like [json_convert_with_to_without_keyquotes], honoring the [WriteOptions].
+///
+/// If the backup can not be created, the file is not written to.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `options` - The options, like the suffix of the backup file.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::json_key_quote_utils;
+/// use json_keyquotes_convert::load_write_utils::WriteOptions;
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// let options = WriteOptions { backup: Some(".bak".to_string()), ..WriteOptions::default() };
+/// json_key_quote_utils::json_convert_with_to_without_keyquotes_with_options(path, &options)?;
+/// ```
+pub fn json_convert_with_to_without_keyquotes_with_options(
+    path: &Path,
+    options: &WriteOptions,
+) -> Result<FileConversion, io::Error> {
+    let json = load_write_utils::load_json(path)?;
 
     let unquoted_json = json_remove_key_quotes(&json);
 
-    match load_write_utils::write_json(path, &json_unescape_ctrlchars(&unquoted_json)) {
-        Ok(()) => (),
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
-        }
-    }
+    write_if_changed(
+        path,
+        &json,
+        &json_unescape_ctrlchars(&unquoted_json),
+        options,
+    )
 }
 
 /// Convenience method for chained [load_write_utils::load_json], [json_add_key_quotes]
 /// ,[json_escape_ctrlchars] and [load_write_utils::write_json] calls.
 ///
+/// The file is only written to if the conversion changed its contents,
+/// see [load_write_utils::write_json_atomic].
+///
 /// # Arguments
 ///
 /// * `path` - The file path.
@@ -60,826 +350,6887 @@ pub fn json_convert_with_to_without_keyquotes(path: &Path) {
 ///
 /// ```rust,ignore
 /// use std::path::Path;
-/// use json_keyquotes_convert::{json_keyquote_utils, Quotes};
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
 ///
-/// let path = Path::new("./test_resources/Test_without_keyquotes.json")
-/// json_keyquote_utils::json_convert_without_to_with_keyquotes(path, Quotes::default());
+/// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+/// json_key_quote_utils::json_convert_without_to_with_keyquotes(path, Quotes::default())?;
 /// ```
-pub fn json_convert_without_to_with_keyquotes(path: &Path, quote_type: Quotes) {
-    let json = match load_write_utils::load_json(path) {
-        Ok(val) => val,
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
-        }
-    };
-
-    let keyquoted_json = json_add_key_quotes(&json, quote_type);
-
-    match load_write_utils::write_json(path, &json_escape_ctrlchars(&keyquoted_json)) {
-        Ok(()) => (),
-        Err(err) => {
-            eprintln!("{}", err);
-            return;
-        }
-    }
+pub fn json_convert_without_to_with_keyquotes(
+    path: &Path,
+    quote_type: Quotes,
+) -> Result<FileConversion, io::Error> {
+    json_convert_without_to_with_keyquotes_with_options(path, quote_type, &WriteOptions::default())
 }
 
-/// Adds key-quotes to the JSON string.
+/// Like [json_convert_without_to_with_keyquotes], honoring the [WriteOptions].
+///
+/// If the backup can not be created, the file is not written to.
 ///
 /// # Arguments
 ///
-/// * `json` - The JSON string.
+/// * `path` - The file path.
 /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+/// * `options` - The options, like the suffix of the backup file.
 ///
 /// # Examples
 ///
-/// ```
+/// ```rust,ignore
+/// use std::path::Path;
 /// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+/// use json_keyquotes_convert::load_write_utils::WriteOptions;
 ///
-/// let json_added = json_key_quote_utils::json_add_key_quotes("{key: \"val\"}", Quotes::default());
-/// assert_eq!(json_added, "{\"key\": \"val\"}");
+/// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+/// let options = WriteOptions { backup: Some(".bak".to_string()), ..WriteOptions::default() };
+/// json_key_quote_utils::json_convert_without_to_with_keyquotes_with_options(path, Quotes::default(), &options)?;
+/// ```
+pub fn json_convert_without_to_with_keyquotes_with_options(
+    path: &Path,
+    quote_type: Quotes,
+    options: &WriteOptions,
+) -> Result<FileConversion, io::Error> {
+    let json = load_write_utils::load_json(path)?;
+
+    let keyquoted_json = json_add_key_quotes(&json, quote_type);
+
+    write_if_changed(
+        path,
+        &json,
+        &json_escape_ctrlchars(&keyquoted_json),
+        options,
+    )
+}
+
+/// Like [json_convert_with_to_without_keyquotes], without blocking the async executor.
 ///
-/// let json_already_existing = json_key_quote_utils::json_add_key_quotes("{\"key\": \"val\"}", Quotes::default());
-/// assert_eq!(json_already_existing, "{\"key\": \"val\"}");
+/// Only available with the `tokio` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// json_key_quote_utils::async_json_convert_with_to_without_keyquotes(path).await?;
 /// ```
-pub fn json_add_key_quotes(json: &str, quote_type: Quotes) -> String {
-    // Add quotes around all string keys (single-quoted):
-    // `/` == `\/` in Regex101
-    let single_quoted_string_val_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<prevchar_key>[^"'][\s]*)(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?[^"'])(?P<val>:\s*?'[\s\S]*?')"#),
-        )
-        .unwrap()
-    });
-    let json_single_quoted_string_passed = single_quoted_string_val_regex.replace_all(
-        json,
-        "$prevchar_key".to_string() + quote_type.as_str() + "$key" + quote_type.as_str() + "$val",
-    );
+#[cfg(feature = "tokio")]
+pub async fn async_json_convert_with_to_without_keyquotes(
+    path: &Path,
+) -> Result<FileConversion, io::Error> {
+    let json = load_write_utils::async_load_json(path).await?;
 
-    // Add quotes around all string keys (double-quoted):
-    // `/` == `\/` in Regex101
-    let double_quoted_string_val_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<prevchar_key>[^"'][\s]*)(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?[^"'])(?P<val>:\s*?"[\s\S]*?")"#),
-        )
-        .unwrap()
-    });
-    let json_double_quoted_string_passed = double_quoted_string_val_regex.replace_all(
-        &json_single_quoted_string_passed,
-        "$prevchar_key".to_string() + quote_type.as_str() + "$key" + quote_type.as_str() + "$val",
-    );
+    let unquoted_json = json_remove_key_quotes(&json);
 
-    // Add quotes around all object keys:
-    // `/` == `\/` in Regex101
-    let object_val_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?[^"'])(?P<val>:\s*?[{\[])"#),
-        )
-        .unwrap()
-    });
-    let json_object_passed = object_val_regex.replace_all(
-        &json_double_quoted_string_passed,
-        quote_type.as_str().to_string() + "$key" + quote_type.as_str() + "$val",
-    );
+    async_write_if_changed(path, &json, &json_unescape_ctrlchars(&unquoted_json)).await
+}
 
-    // Add quotes around all number keys:
-    // `/` == `\/` in Regex101
-    let number_val_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<before>[\[,{]\s*?)(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?[^"'])(?P<after>:\s*?[\d\-\.])"#),
-        )
-        .unwrap()
-    });
-    let json_number_passed = number_val_regex.replace_all(
-        &json_object_passed,
-        "$before".to_string() + quote_type.as_str() + "$key" + quote_type.as_str() + "$after",
-    );
+/// Like [json_convert_without_to_with_keyquotes], without blocking the async executor.
+///
+/// Only available with the `tokio` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+/// json_key_quote_utils::async_json_convert_without_to_with_keyquotes(path, Quotes::default()).await?;
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn async_json_convert_without_to_with_keyquotes(
+    path: &Path,
+    quote_type: Quotes,
+) -> Result<FileConversion, io::Error> {
+    let json = load_write_utils::async_load_json(path).await?;
 
-    // Add quotes around all `null`, and `boolean` keys:
-    // `/` == `\/` in Regex101
-    let null_bools_val_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<before>[\[,{]\s*?)(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?[^"'])(?P<after>:\s*?(?:null|true|false))"#),
-        )
-        .unwrap()
-    });
-    let json_null_bools_passed = null_bools_val_regex.replace_all(
-        &json_number_passed,
-        "$before".to_string() + quote_type.as_str() + "$key" + quote_type.as_str() + "$after",
-    );
+    let keyquoted_json = json_add_key_quotes(&json, quote_type);
 
-    return json_null_bools_passed.to_string();
+    async_write_if_changed(path, &json, &json_escape_ctrlchars(&keyquoted_json)).await
 }
 
-/// Removes key-quotes from the JSON string.
+/// Like [json_convert_with_to_without_keyquotes], writing the converted JSON string to another file.
+///
+/// The input file is never written to. The output file is always written to, atomically.
 ///
 /// # Arguments
 ///
-/// * `json` - The JSON string.
+/// * `input` - The file path to load the JSON string from.
+/// * `output` - The file path to write the converted JSON string to.
 ///
 /// # Examples
 ///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::json_key_quote_utils;
+///
+/// let input = Path::new("./test_resources/Test_with_keyquotes.json");
+/// let output = Path::new("./out/Test_without_keyquotes.json");
+/// json_key_quote_utils::json_convert_with_to_without_keyquotes_to(input, output)?;
 /// ```
-/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+pub fn json_convert_with_to_without_keyquotes_to(
+    input: &Path,
+    output: &Path,
+) -> Result<(), io::Error> {
+    json_convert_with_to_without_keyquotes_to_with_options(input, output, &WriteOptions::default())
+}
+
+/// Like [json_convert_with_to_without_keyquotes_to], honoring the [WriteOptions].
 ///
-/// let json_removed = json_key_quote_utils::json_remove_key_quotes("{\"key\": \"val\"}");
-/// assert_eq!(json_removed, "{key: \"val\"}");
+/// The line endings of the input file are preserved by default,
+/// and the backup is made of an existing output file.
 ///
-/// let json_already_removed = json_key_quote_utils::json_remove_key_quotes("{key: \"val\"}");
-/// assert_eq!(json_already_removed, "{key: \"val\"}");
+/// # Arguments
+///
+/// * `input` - The file path to load the JSON string from.
+/// * `output` - The file path to write the converted JSON string to.
+/// * `options` - The options, like whether the parent directories of the output should be created.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::json_key_quote_utils;
+/// use json_keyquotes_convert::load_write_utils::WriteOptions;
+///
+/// let input = Path::new("./test_resources/Test_with_keyquotes.json");
+/// let output = Path::new("./out/Test_without_keyquotes.json");
+/// let options = WriteOptions { create_dirs: true, ..WriteOptions::default() };
+/// json_key_quote_utils::json_convert_with_to_without_keyquotes_to_with_options(input, output, &options)?;
 /// ```
-pub fn json_remove_key_quotes(json: &str) -> String {
-    // Remove the quotes from the keys (single-quoted):
-    // `/` == `\/` in Regex101
-    let single_quotes_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<before>[{\[,][\s]*)'(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?)'(?P<after>\s*?:)"#),
-        )
-        .unwrap()
-    });
-    let json_single_quotes_passed = single_quotes_regex.replace_all(json, "$before$key$after");
+pub fn json_convert_with_to_without_keyquotes_to_with_options(
+    input: &Path,
+    output: &Path,
+    options: &WriteOptions,
+) -> Result<(), io::Error> {
+    let json = load_write_utils::load_json(input)?;
 
-    // Remove the quotes from the keys (double-quoted):
-    // `/` == `\/` in Regex101
-    let double_quotes_regex = Lazy::new(|| {
-        Regex::new(
-            &(r#"(?P<before>[{\[,][\s]*)"(?P<key>["#.to_string()
-                + SUPPORTED_KEY_CHARS_REGEX_STR
-                + r#"]*?)"(?P<after>\s*?:)"#),
-        )
-        .unwrap()
-    });
-    let json_double_quotes_passed =
-        double_quotes_regex.replace_all(&json_single_quotes_passed, "$before$key$after");
+    let unquoted_json = json_remove_key_quotes(&json);
 
-    return json_double_quotes_passed.to_string();
+    write_to(
+        input,
+        output,
+        &json,
+        &json_unescape_ctrlchars(&unquoted_json),
+        options,
+    )
 }
 
-/// Escape ctrl-characters from the JSON string values
-/// and remove ctrl-characters from the JSON keys with keyquotes.
+/// Like [json_convert_without_to_with_keyquotes], writing the converted JSON string to another file.
 ///
-/// This method will escape `newlines`, `tabs` and `carriage returns` in the JSON string values
-/// and remove `newlines`, `tabs` and `carriage returns` in the JSON keys with keyquotes.
+/// The input file is never written to. The output file is always written to, atomically.
 ///
 /// # Arguments
 ///
-/// * `json` - The JSON string.
+/// * `input` - The file path to load the JSON string from.
+/// * `output` - The file path to write the converted JSON string to.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
 ///
 /// # Examples
 ///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let input = Path::new("./test_resources/Test_without_keyquotes.json");
+/// let output = Path::new("./out/Test_with_keyquotes.json");
+/// json_key_quote_utils::json_convert_without_to_with_keyquotes_to(input, output, Quotes::default())?;
 /// ```
-/// use json_keyquotes_convert::{json_key_quote_utils};
+pub fn json_convert_without_to_with_keyquotes_to(
+    input: &Path,
+    output: &Path,
+    quote_type: Quotes,
+) -> Result<(), io::Error> {
+    json_convert_without_to_with_keyquotes_to_with_options(
+        input,
+        output,
+        quote_type,
+        &WriteOptions::default(),
+    )
+}
+
+/// Like [json_convert_without_to_with_keyquotes_to], honoring the [WriteOptions].
 ///
-/// let json_escaped = json_key_quote_utils::json_escape_ctrlchars(r#"{"key": "va
-/// l"}"#);
-/// assert_eq!(json_escaped, r#"{"key": "va\nl"}"#);
+/// The line endings of the input file are preserved by default,
+/// and the backup is made of an existing output file.
 ///
-/// let json_already_escaped = json_key_quote_utils::json_escape_ctrlchars(r#"{"key": "va\nl"}"#);
-/// assert_eq!(json_already_escaped, r#"{"key": "va\nl"}"#);
+/// # Arguments
+///
+/// * `input` - The file path to load the JSON string from.
+/// * `output` - The file path to write the converted JSON string to.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+/// * `options` - The options, like whether the parent directories of the output should be created.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+/// use json_keyquotes_convert::load_write_utils::WriteOptions;
+///
+/// let input = Path::new("./test_resources/Test_without_keyquotes.json");
+/// let output = Path::new("./out/Test_with_keyquotes.json");
+/// let options = WriteOptions { create_dirs: true, ..WriteOptions::default() };
+/// json_key_quote_utils::json_convert_without_to_with_keyquotes_to_with_options(input, output, Quotes::default(), &options)?;
 /// ```
-pub fn json_escape_ctrlchars(json: &str) -> String {
-    // Replace all control characters with their escaped variants:
+pub fn json_convert_without_to_with_keyquotes_to_with_options(
+    input: &Path,
+    output: &Path,
+    quote_type: Quotes,
+    options: &WriteOptions,
+) -> Result<(), io::Error> {
+    let json = load_write_utils::load_json(input)?;
 
-    let mut new_json = json.to_owned();
+    let keyquoted_json = json_add_key_quotes(&json, quote_type);
 
-    // Two iterations are needed for the tab escaping:
+    write_to(
+        input,
+        output,
+        &json,
+        &json_escape_ctrlchars(&keyquoted_json),
+        options,
+    )
+}
 
-    for _n in 0..2 {
-        // For all single-quoted string keys with single-quoted values:
-        let singlequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)'(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])'(?P<val>\s*?:\s*?'[\s\S]*?')"#),
-            )
-            .unwrap()
-        });
-        for cap in singlequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+/// Converts all files in the directory which match the predicate, in place.
+///
+/// A failing file does not abort the conversion, its error is returned in its [FileResult] instead.
+/// The files are converted in sorted path order. Like [json_convert_without_to_with_keyquotes],
+/// a file is only written to if the conversion changed its contents.
+///
+/// With [Direction::AddKeyQuotes], the conversions enabled in the [ConvertOptions] are applied.
+/// With [Direction::RemoveKeyQuotes], the comments are stripped if enabled, the key-quotes are removed
+/// and the ctrl-characters are unescaped if `escape_ctrlchars` is enabled.
+///
+/// # Arguments
+///
+/// * `dir` - The directory path.
+/// * `direction` - Whether key-quotes should be added or removed.
+/// * `options` - The conversions to apply.
+/// * `recursive` - Whether the files in the subdirectories should be converted too.
+/// * `filter` - Whether the file at the path should be converted, like an extension filter.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::json_key_quote_utils::{self, Direction};
+/// use json_keyquotes_convert::ConvertOptions;
+///
+/// let results = json_key_quote_utils::convert_dir(
+///     Path::new("./config"),
+///     Direction::AddKeyQuotes,
+///     &ConvertOptions::default(),
+///     true,
+///     |path| path.extension().is_some_and(|extension| extension == "json"),
+/// );
+/// for result in results.iter().filter(|result| result.outcome.is_err()) {
+///     eprintln!("{}: {:?}", result.path.display(), result.outcome);
+/// }
+/// ```
+pub fn convert_dir(
+    dir: &Path,
+    direction: Direction,
+    options: &ConvertOptions,
+    recursive: bool,
+    filter: impl Fn(&Path) -> bool,
+) -> Vec<FileResult> {
+    let mut results = Vec::new();
+    convert_dir_into(dir, direction, options, recursive, &filter, &mut results);
 
-        // For all double-quoted string keys with single-quoted values:
-        let singlequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)"(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])"(?P<val>\s*?:\s*?'[\s\S]*?')"#),
-            )
-            .unwrap()
-        });
-        for cap in singlequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
+    results
+}
+
+fn convert_dir_into(
+    dir: &Path,
+    direction: Direction,
+    options: &ConvertOptions,
+    recursive: bool,
+    filter: &dyn Fn(&Path) -> bool,
+    results: &mut Vec<FileResult>,
+) {
+    let paths = std::fs::read_dir(dir).and_then(|entries| {
+        entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<PathBuf>, io::Error>>()
+    });
+    let mut paths = match paths {
+        Ok(paths) => paths,
+        Err(err) => {
+            results.push(FileResult {
+                path: dir.to_path_buf(),
+                outcome: Err(err),
+            });
+            return;
         }
+    };
+    paths.sort();
 
-        // For all single-quoted string keys with double-quoted values:
-        let doublequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)'(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])'(?P<val>\s*?:\s*?"[\s\S]*?")"#),
-            )
-            .unwrap()
-        });
-        for cap in doublequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
+    for path in paths {
+        if path.is_dir() {
+            if recursive {
+                convert_dir_into(&path, direction, options, recursive, filter, results);
+            }
+        } else if filter(&path) {
+            let outcome = convert_file(&path, direction, options);
+            results.push(FileResult { path, outcome });
         }
+    }
+}
 
-        // For all double-quoted string keys with double-quoted values:
-        let doublequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)"(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])"(?P<val>\s*?:\s*?"[\s\S]*?")"#),
-            )
-            .unwrap()
-        });
-        for cap in doublequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+/// Converts the file in place for [convert_dir].
+fn convert_file(
+    path: &Path,
+    direction: Direction,
+    options: &ConvertOptions,
+) -> Result<FileConversion, io::Error> {
+    let json = load_write_utils::load_json(path)?;
 
-        // For all single-quoted object keys:
-        let object_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"'(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])'(?P<val>\s*?:\s*?[{\[])"#),
-            )
-            .unwrap()
-        });
-        for cap in object_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
+    let converted_json = match direction {
+        Direction::AddKeyQuotes => JsonKeyQuoteConverter::with_options(&json, options)
+            .convert()
+            .json(),
+        Direction::RemoveKeyQuotes => {
+            let uncommented_json = if options.strip_comments {
+                json_strip_comments(&json)
+            } else {
+                json.clone()
+            };
+            let mut converter =
+                JsonKeyQuoteConverter::with_options(&uncommented_json, options).remove_key_quotes();
+            if options.escape_ctrlchars {
+                converter = converter.unescape_ctrlchars();
+            }
+            converter.json()
         }
+    };
 
-        // For all double-quoted object keys:
-        let object_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#""(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])"(?P<val>\s*?:\s*?[{\[])"#),
-            )
-            .unwrap()
-        });
-        for cap in object_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+    write_if_changed(path, &json, &converted_json, &WriteOptions::default())
+}
 
-        // For all single-quoted number keys:
-        let number_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)'(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])'(?P<after>\s*?:\s*?[\d\-\.])"#),
-            )
-            .unwrap()
-        });
-        for cap in number_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+/// Converts the JSON read from the reader and writes it to the writer, in bounded memory.
+///
+/// Unlike loading the JSON string and converting it, the input is converted while it is read,
+/// so inputs larger than the available memory can be converted.
+/// Only the state of the conversion is carried across the chunks of the reader.
+///
+/// With [Direction::AddKeyQuotes], the key-quotes are added like [json_add_key_quotes]
+/// and the ctrl-characters in the string values are escaped like [json_escape_ctrlchars].
+/// With [Direction::RemoveKeyQuotes], the key-quotes are removed like [json_remove_key_quotes]
+/// and the ctrl-characters in the string values are unescaped like [json_unescape_ctrlchars].
+/// The ctrl-characters in the keys are left as they are.
+/// Unquoted keys longer than [DEFAULT_MAX_KEY_LEN] bytes are not quoted.
+///
+/// The input is converted byte by byte, invalid UTF-8 is written as-is.
+///
+/// # Arguments
+///
+/// * `reader` - The reader, like a file or stdin.
+/// * `writer` - The writer, like a file or stdout.
+/// * `direction` - Whether key-quotes should be added or removed.
+/// * `quote_type` - The quotes to add around the keys, ignored by [Direction::RemoveKeyQuotes].
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, Direction};
+/// use json_keyquotes_convert::Quotes;
+///
+/// let mut output = Vec::new();
+/// let stats = json_key_quote_utils::convert_stream(
+///     "{key: \"va\nl\"}".as_bytes(),
+///     &mut output,
+///     Direction::AddKeyQuotes,
+///     Quotes::default(),
+/// )
+/// .unwrap();
+/// assert_eq!(output, br#"{"key": "va\nl"}"#);
+/// assert_eq!(stats.keys_quoted, 1);
+/// ```
+pub fn convert_stream(
+    mut reader: impl BufRead,
+    writer: impl Write,
+    direction: Direction,
+    quote_type: Quotes,
+) -> Result<ConversionStats, io::Error> {
+    let mut converter =
+        stream::StreamConverter::new(io::BufWriter::new(writer), direction, quote_type);
 
-        // For all double-quoted number keys:
-        let number_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)"(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])"(?P<after>\s*?:\s*?[\d\-\.])"#),
-            )
-            .unwrap()
-        });
-        for cap in number_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            break;
         }
+        converter.write_chunk(chunk)?;
+        let len = chunk.len();
+        reader.consume(len);
+    }
 
-        // For all single-quoted null and boolean keys:
-        let null_boolean_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)'(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])'(?P<after>\s*?:\s*?(?:null|true|false))"#),
-            )
-            .unwrap()
-        });
-        for cap in null_boolean_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+    converter.finish()
+}
 
-        // For all double-quoted null and boolean keys:
-        let null_boolean_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)"(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])"(?P<after>\s*?:\s*?(?:null|true|false))"#),
-            )
-            .unwrap()
-        });
-        for cap in null_boolean_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\r", ""), 1);
-            new_json =
-                new_json.replacen(cap_match, &cap_match.replace("\t", "").replace("\t", ""), 1);
-        }
+/// Previews the changes [json_convert_without_to_with_keyquotes] would make to the file, without writing to it.
+///
+/// Returns the lines which would be modified by [json_add_key_quotes] and [json_escape_ctrlchars]
+/// as a unified diff without context lines, see [diff_lines].
+/// The line numbers refer to the original file. The diff is empty if nothing would change.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+/// print!("{}", json_key_quote_utils::diff_preview(path, Quotes::default())?);
+/// ```
+pub fn diff_preview(path: &Path, quote_type: Quotes) -> Result<String, io::Error> {
+    let json = load_write_utils::load_json(path)?;
 
-        // For all single-quoted string values:
-        let singlequoted_string_value_regex =
-            Lazy::new(|| Regex::new(r#":[\s]*?'((?:[^'\\]|\\.)*)'"#).unwrap());
-        for cap in singlequoted_string_value_regex.captures_iter(&new_json.clone()) {
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\r", "\\r"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\n", "\\n"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\t", "\\t"), 1);
-        }
+    let converted_json = json_escape_ctrlchars(&json_add_key_quotes(&json, quote_type));
 
-        // For all double-quoted string values:
-        let doublequoted_string_value_regex =
-            Lazy::new(|| Regex::new(r#":[\s]*?"((?:[^"\\]|\\.)*)""#).unwrap());
-        for cap in doublequoted_string_value_regex.captures_iter(&new_json.clone()) {
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\r", "\\r"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\n", "\\n"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\t", "\\t"), 1);
-        }
+    Ok(diff_lines(&json, &converted_json))
+}
+
+/// Writes the converted JSON string to the file atomically, unless it equals the original JSON string.
+///
+/// The line endings of the [WriteOptions] are applied first, preserving the original ones by default.
+/// The file is backed up first if the [WriteOptions] have a backup suffix.
+fn write_if_changed(
+    path: &Path,
+    json: &str,
+    converted_json: &str,
+    options: &WriteOptions,
+) -> Result<FileConversion, io::Error> {
+    let converted_json = load_write_utils::apply_newlines(converted_json, Some(json), options);
+    if converted_json == json {
+        return Ok(FileConversion::Unchanged);
     }
 
-    new_json
+    load_write_utils::backup_json(path, options)?;
+    load_write_utils::write_json_atomic(path, &converted_json)?;
+
+    Ok(FileConversion::Converted)
 }
 
-/// Unescape ctrl-characters from the JSON string values
-/// and remove ctrl-characters from the JSON keys without keyquotes.
+/// Like [write_if_changed] with the default [WriteOptions], without blocking the async executor.
+#[cfg(feature = "tokio")]
+async fn async_write_if_changed(
+    path: &Path,
+    json: &str,
+    converted_json: &str,
+) -> Result<FileConversion, io::Error> {
+    let converted_json =
+        load_write_utils::apply_newlines(converted_json, Some(json), &WriteOptions::default());
+    if converted_json == json {
+        return Ok(FileConversion::Unchanged);
+    }
+
+    load_write_utils::async_write_json_atomic(path, &converted_json).await?;
+
+    Ok(FileConversion::Converted)
+}
+
+/// Writes the converted JSON string to the output file atomically, leaving the input file untouched.
+///
+/// Returns an error if the output file is the input file.
+fn write_to(
+    input: &Path,
+    output: &Path,
+    json: &str,
+    converted_json: &str,
+    options: &WriteOptions,
+) -> Result<(), io::Error> {
+    if output.exists() && std::fs::canonicalize(input)? == std::fs::canonicalize(output)? {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "the output {} is the input file, which is never written to",
+                output.display()
+            ),
+        ));
+    }
+
+    let converted_json = load_write_utils::apply_newlines(converted_json, Some(json), options);
+
+    load_write_utils::backup_json(output, options)?;
+    load_write_utils::create_parent_dirs(output, options)?;
+    load_write_utils::write_json_atomic(output, &converted_json)
+}
+
+/// Adds key-quotes to the JSON string.
 ///
-/// This method will unescape `newlines`, `tabs` and `carriage returns` in the JSON string values
-/// and remove `newlines`, `tabs` and `carriage returns` in the JSON keys without keyquotes.
+/// Unquoted keys longer than [DEFAULT_MAX_KEY_LEN] bytes are left as they are,
+/// use [json_add_key_quotes_with_options] to get an error for them instead.
 ///
 /// # Arguments
 ///
 /// * `json` - The JSON string.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+///   [Quotes::NoQuotes] is equivalent to calling [json_remove_key_quotes].
 ///
 /// # Examples
 ///
 /// ```
-/// use json_keyquotes_convert::{json_key_quote_utils};
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
 ///
-/// let json_unescaped = json_key_quote_utils::json_unescape_ctrlchars(r#"{key: "va\nl"}"#);
-/// assert_eq!(json_unescaped, r#"{key: "va
-/// l"}"#);
+/// let json_added = json_key_quote_utils::json_add_key_quotes("{key: \"val\"}", Quotes::default());
+/// assert_eq!(json_added, "{\"key\": \"val\"}");
 ///
-/// let json_already_unescaped = json_key_quote_utils::json_unescape_ctrlchars(&json_unescaped);
-/// assert_eq!(json_already_unescaped, r#"{key: "va
-/// l"}"#);
+/// let json_already_existing = json_key_quote_utils::json_add_key_quotes("{\"key\": \"val\"}", Quotes::default());
+/// assert_eq!(json_already_existing, "{\"key\": \"val\"}");
 /// ```
-pub fn json_unescape_ctrlchars(json: &str) -> String {
-    // Replace all escaped control characters with their unescaped variants:
+pub fn json_add_key_quotes(json: &str, quote_type: Quotes) -> String {
+    json_add_key_quotes_with_dialect(json, quote_type, Dialect::Json)
+}
 
-    let mut new_json = json.to_owned();
+/// Adds key-quotes to the JSON string like [json_add_key_quotes],
+/// recognizing the values of the [Dialect].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+/// * `dialect` - The dialect of the JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Dialect, Quotes};
+///
+/// let json_added = json_key_quote_utils::json_add_key_quotes_with_dialect("{a: +5, b: -Infinity, c: 0xFF}", Quotes::default(), Dialect::Json5);
+/// assert_eq!(json_added, r#"{"a": +5, "b": -Infinity, "c": 0xFF}"#);
+/// ```
+pub fn json_add_key_quotes_with_dialect(
+    json: &str,
+    quote_type: Quotes,
+    dialect: Dialect,
+) -> String {
+    add_key_quotes_reporting(json, quote_type, dialect, |_| {})
+}
 
-    // Two iterations are needed for the tab unescaping:
+/// Adds key-quotes to the JSON string like [json_add_key_quotes],
+/// returning an error if any key is longer than `max_key_len` bytes.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+/// * `max_key_len` - The maximum length of a key in bytes, without its quotes.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_added = json_key_quote_utils::json_add_key_quotes_with_options("{key: \"val\"}", Quotes::default(), 3);
+/// assert_eq!(json_added, Ok("{\"key\": \"val\"}".to_string()));
+///
+/// let json_too_long = json_key_quote_utils::json_add_key_quotes_with_options("{key: \"val\"}", Quotes::default(), 2);
+/// assert!(json_too_long.is_err());
+/// ```
+pub fn json_add_key_quotes_with_options(
+    json: &str,
+    quote_type: Quotes,
+    max_key_len: usize,
+) -> Result<String, String> {
+    if let Some(key) = keys(json).find(|key| key.key.len() > max_key_len) {
+        return Err(format!(
+            "the key at byte {} is {} bytes long, longer than the maximum of {} bytes",
+            key.span.start,
+            key.key.len(),
+            max_key_len
+        ));
+    }
 
-    for _n in 0..2 {
-        // For all single-quoted string keys:
-        let singlequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])(?P<val>\s*?:\s*?'[\s\S]*?')"#),
-            )
-            .unwrap()
-        });
-        for cap in singlequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\r", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\t", ""), 1);
-        }
+    Ok(add_key_quotes_tokens(
+        json,
+        quote_type,
+        Dialect::Json,
+        usize::MAX,
+        |_| {},
+    ))
+}
 
-        // For all double-quoted string keys:
-        let doublequoted_string_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<prevchar_key>[^"'][\s]*)(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])(?P<val>\s*?:\s*?"[\s\S]*?")"#),
-            )
-            .unwrap()
-        });
-        for cap in doublequoted_string_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\r", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\t", ""), 1);
-        }
+/// Adds key-quotes to the JSON string like [json_add_key_quotes_with_dialect],
+/// calling `on_key` for every key of the original JSON string.
+pub(crate) fn add_key_quotes_reporting(
+    json: &str,
+    quote_type: Quotes,
+    dialect: Dialect,
+    on_key: impl FnMut(KeyEvent),
+) -> String {
+    let json_quoted = add_key_quotes_tokens(json, quote_type, dialect, DEFAULT_MAX_KEY_LEN, on_key);
 
-        // For all object keys:
-        let object_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])(?P<val>\s*?:\s*?[{\[])"#),
-            )
-            .unwrap()
-        });
-        for cap in object_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\r", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\t", ""), 1);
-        }
+    // The mongo shell helpers are converted afterwards, so the keys are reported at their original spans:
+    if dialect == Dialect::MongoShell && quote_type != Quotes::NoQuotes {
+        return json_convert_mongo_shell_values(&json_quoted, quote_type);
+    }
 
-        // For all number keys:
-        let number_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])(?P<after>\s*?:\s*?[\d\-\.])"#),
-            )
-            .unwrap()
-        });
-        for cap in number_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\r", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\t", ""), 1);
-        }
+    json_quoted
+}
 
-        // For all null and boolean keys:
-        let null_boolean_key_regex = Lazy::new(|| {
-            Regex::new(
-                &(r#"(?P<before>[\[,{]\s*?)(?P<key>["#.to_string()
-                    + SUPPORTED_KEY_CHARS_REGEX_STR
-                    + r#"]*?[^"'])(?P<after>\s*?:\s*?(?:null|true|false))"#),
-            )
-            .unwrap()
-        });
-        for cap in null_boolean_key_regex.captures_iter(&new_json.clone()) {
-            let cap_match = cap.name("key").unwrap().as_str();
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\r", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\n", ""), 1);
-            new_json = new_json.replacen(cap_match, &cap_match.replace("\\t", ""), 1);
+/// Adds key-quotes to the unquoted keys with a supported value, in a single pass over the tokens.
+///
+/// Every key is reported to `on_key` as it is encountered, including the empty keys.
+/// Keys longer than `max_key_len` bytes are left as they are.
+fn add_key_quotes_tokens(
+    json: &str,
+    quote_type: Quotes,
+    dialect: Dialect,
+    max_key_len: usize,
+    mut on_key: impl FnMut(KeyEvent),
+) -> String {
+    let quote = quote_type.as_char();
+    let tokens = scanner::tokenize(json);
+    let mut quoted = String::with_capacity(json.len());
+    let mut pos = 0;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let mut report = |key: &str, quoted: bool| {
+            on_key(KeyEvent {
+                key: key.to_string(),
+                span: token.start..token.end,
+                quoted,
+            })
+        };
+
+        let replacement = match token.kind {
+            // An empty key is only its colon, directly after the `{` or `,`:
+            scanner::TokenKind::Colon
+                if i > 0
+                    && matches!(
+                        tokens[i - 1].kind,
+                        scanner::TokenKind::ObjectStart | scanner::TokenKind::Comma
+                    ) =>
+            {
+                on_key(KeyEvent {
+                    key: String::new(),
+                    span: token.start..token.start,
+                    quoted: false,
+                });
+                quote.map(|quote| format!("{}{}:", quote, quote))
+            }
+            scanner::TokenKind::Key(Some(_)) => {
+                report(token.inner_text(json), true);
+                None
+            }
+            scanner::TokenKind::Key(None) => {
+                match custom_quoted_key(token.text(json), quote_type) {
+                    Some(key) => {
+                        report(key, true);
+                        None
+                    }
+                    None => {
+                        let key = token.text(json);
+                        report(key, false);
+                        quote
+                            .filter(|_| key.len() <= max_key_len)
+                            .filter(|_| {
+                                tokens.get(i + 2).is_some_and(|value| {
+                                    is_quoted_value(&json[value.start..], dialect)
+                                })
+                            })
+                            .map(|quote| quote_key(&escape_quote(key, quote), Some(quote)))
+                    }
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(replacement) = replacement {
+            quoted.push_str(&json[pos..token.start]);
+            quoted.push_str(&replacement);
+            pos = token.end;
         }
+    }
+    quoted.push_str(&json[pos..]);
 
-        // For all single-quoted string values:
-        let singlequoted_string_value_regex =
-            Lazy::new(|| Regex::new(r#":[\s]*?'((?:[^'\\]|\\.)*)'"#).unwrap());
-        for cap in singlequoted_string_value_regex.captures_iter(&new_json.clone()) {
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\r", "\r"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\n", "\n"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\t", "\t"), 1);
+    match quote {
+        Some(_) => quoted,
+        None => json_remove_key_quotes(json),
+    }
+}
+
+/// Returns the key without its custom key-quotes, if the bare key is within the [Quotes::Custom] quotes.
+fn custom_quoted_key(key: &str, quote_type: Quotes) -> Option<&str> {
+    match quote_type {
+        Quotes::Custom(quote) => key.strip_prefix(quote)?.strip_suffix(quote),
+        _ => None,
+    }
+}
+
+/// Whether the keys of the value at the start of the text get key-quotes: strings, objects, arrays,
+/// numbers, `null`, booleans and calls like `ObjectId("x")`, plus `+5`, `Infinity` and `NaN` in [Dialect::Json5].
+pub(crate) fn is_quoted_value(value: &str, dialect: Dialect) -> bool {
+    let name_len = value
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '$')))
+        .unwrap_or(value.len());
+    let is_call = name_len > 0
+        && value[name_len..]
+            .trim_start_matches(scanner::is_whitespace)
+            .starts_with('(');
+
+    value.starts_with(['"', '\'', '{', '['])
+        || value.starts_with(|c: char| c.is_ascii_digit() || matches!(c, '-' | '.'))
+        || ["null", "true", "false"]
+            .iter()
+            .any(|literal| value.starts_with(literal))
+        || is_call
+        || dialect == Dialect::Json5
+            && ["+", "Infinity", "NaN"]
+                .iter()
+                .any(|literal| value.starts_with(literal))
+}
+
+/// The mongo shell helpers converted by [json_convert_mongo_shell_values], with their extended JSON keys.
+const MONGO_SHELL_TYPES: [(&str, &str); 5] = [
+    ("ObjectId", "$oid"),
+    ("ISODate", "$date"),
+    ("NumberLong", "$numberLong"),
+    ("NumberInt", "$numberInt"),
+    ("NumberDecimal", "$numberDecimal"),
+];
+
+/// Converts the mongo shell helpers to MongoDB extended JSON, like `ObjectId("x")` to `{"$oid": "x"}`.
+///
+/// Supported are `ObjectId`, `ISODate`, `NumberLong`, `NumberInt` and `NumberDecimal`,
+/// with a single- or double-quoted string or a bare number as their argument.
+/// The argument becomes a double-quoted string value. Other calls and the text inside strings are left untouched.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to put around the extended JSON keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_converted = json_key_quote_utils::json_convert_mongo_shell_values(r#"{_id: ObjectId("5f1d"), n: NumberLong(5)}"#, Quotes::DoubleQuote);
+/// assert_eq!(json_converted, r#"{_id: {"$oid": "5f1d"}, n: {"$numberLong": "5"}}"#);
+/// ```
+pub fn json_convert_mongo_shell_values(json: &str, quote_type: Quotes) -> String {
+    let mut converted = String::with_capacity(json.len());
+    let mut pos = 0;
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+    let mut previous: Option<char> = None;
+
+    while let Some(c) = json[pos..].chars().next() {
+        let after_identifier = previous.is_some_and(|previous| {
+            previous.is_ascii_alphanumeric() || matches!(previous, '_' | '$')
+        });
+        if quote.is_none() && !after_identifier {
+            if let Some((end, value)) = mongo_shell_value(json, pos, quote_type) {
+                converted.push_str(&value);
+                previous = Some(')');
+                pos = end;
+                continue;
+            }
         }
 
-        // For all double-quoted string values:
-        let doublequoted_string_value_regex =
-            Lazy::new(|| Regex::new(r#":[\s]*?"((?:[^"\\]|\\.)*)""#).unwrap());
-        for cap in doublequoted_string_value_regex.captures_iter(&new_json.clone()) {
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\r", "\r"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\n", "\n"), 1);
-            new_json = new_json.replacen(&cap[1], &cap[1].replace("\\t", "\t"), 1);
+        match quote {
+            Some(_) if escaped => escaped = false,
+            Some(_) if c == '\\' => escaped = true,
+            Some(current_quote) if c == current_quote => quote = None,
+            Some(_) => (),
+            None if matches!(c, '"' | '\'' | '`') => quote = Some(c),
+            None => (),
         }
+        converted.push(c);
+        previous = Some(c);
+        pos += c.len_utf8();
     }
 
-    new_json
+    converted
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{json_key_quote_utils, load_write_utils, Quotes};
-    use std::path::Path;
+/// Converts the mongo shell helper call at `start`, returning the byte offset after the call
+/// and the extended JSON object. Returns `None` if there is no supported call at `start`.
+fn mongo_shell_value(json: &str, start: usize, quote_type: Quotes) -> Option<(usize, String)> {
+    let skip_whitespace =
+        |pos: usize| json.len() - json[pos..].trim_start_matches(scanner::is_whitespace).len();
 
-    const SUPPORTED_KEY_CHARS: &str = r#"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789`~!@#$%€^&*()-_=+\|;"'.<>/?"#;
-    const SUPPORTED_VALUE_CHARS: &str = r#"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789`~!@#$%€^&*()-_=+\|:;"'.<>/?"#;
+    let (name, type_key) = MONGO_SHELL_TYPES
+        .iter()
+        .find(|(name, _)| json[start..].starts_with(name))?;
+    let open = skip_whitespace(start + name.len());
+    if !json[open..].starts_with('(') {
+        return None;
+    }
 
-    #[test]
-    fn test_json_convert_without_to_with_keyquotes() {
-        let path = Path::new("./tmp_without_keyquotes");
-        std::fs::copy(
-            "./test_resources/Test_without_keyquotes.json",
-            "./tmp_without_keyquotes",
-        )
-        .unwrap();
-        json_key_quote_utils::json_convert_without_to_with_keyquotes(
-            path,
-            crate::Quotes::DoubleQuote,
-        );
-        let converted_file_contents = load_write_utils::load_json(path).unwrap();
-        let expected_file_contents =
-            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
-                .unwrap();
-        assert!(converted_file_contents == expected_file_contents);
-        std::fs::remove_file("./tmp_without_keyquotes").unwrap();
+    let arg_start = skip_whitespace(open + 1);
+    let (arg, arg_end) = match json[arg_start..].chars().next()? {
+        arg_quote @ ('"' | '\'') => {
+            let end = scanner::string_end(json, arg_start, arg_quote);
+            let inner = json[arg_start + 1..end].strip_suffix(arg_quote)?;
+            (convert_quotes(inner, arg_quote, '"'), end)
+        }
+        _ => {
+            let len = json[arg_start..].find(')')?;
+            let bare = json[arg_start..arg_start + len].trim_end_matches(scanner::is_whitespace);
+            let is_number = !bare.is_empty()
+                && bare
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+            if !is_number {
+                return None;
+            }
+            ("\"".to_string() + bare + "\"", arg_start + bare.len())
+        }
+    };
+
+    let close = skip_whitespace(arg_end);
+    if !json[close..].starts_with(')') {
+        return None;
     }
 
-    #[test]
-    fn test_json_convert_with_to_without_keyquotes() {
-        let path = Path::new("./tmp_with_keyquotes");
-        std::fs::copy(
-            "./test_resources/Test_with_keyquotes.json",
-            "./tmp_with_keyquotes",
-        )
-        .unwrap();
-        json_key_quote_utils::json_convert_with_to_without_keyquotes(path);
-        let converted_file_contents = load_write_utils::load_json(path).unwrap();
-        let expected_file_contents =
-            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
-                .unwrap();
-        assert!(converted_file_contents == expected_file_contents);
-        std::fs::remove_file("./tmp_with_keyquotes").unwrap();
+    let key = quote_key(type_key, quote_type.as_char());
+    Some((close + 1, format!("{{{}: {}}}", key, arg)))
+}
+
+/// Adds key-quotes to the JSON string like [json_add_key_quotes],
+/// calling `on_key` for every key in the original JSON string.
+///
+/// The keys are reported in document order by the same pass which quotes them,
+/// including the keys nested in arrays and objects and the empty keys.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to add around the JSON keys.
+/// * `on_key` - The closure which receives a [KeyEvent] for every key.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let mut unquoted_keys = Vec::new();
+/// let json_added = json_key_quote_utils::json_add_key_quotes_with(
+///     r#"{key: {"quoted": 1}}"#,
+///     Quotes::default(),
+///     |event| {
+///         if !event.quoted {
+///             unquoted_keys.push(event.key);
+///         }
+///     },
+/// );
+/// assert_eq!(json_added, r#"{"key": {"quoted": 1}}"#);
+/// assert_eq!(unquoted_keys, vec!["key"]);
+/// ```
+pub fn json_add_key_quotes_with(
+    json: &str,
+    quote_type: Quotes,
+    on_key: impl FnMut(KeyEvent),
+) -> String {
+    add_key_quotes_reporting(json, quote_type, Dialect::Json, on_key)
+}
+
+/// Returns the keys of the JSON string in document order, without converting anything.
+///
+/// The keys nested in objects and arrays are included,
+/// colons and quotes inside string values are not mistaken for keys.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json = r#"{key: "a: b", 'other': [{"nested": 1}]}"#;
+/// let keys: Vec<_> = json_key_quote_utils::keys(json)
+///     .map(|key| (key.key, key.quotes, &json[key.span]))
+///     .collect();
+/// assert_eq!(keys, vec![
+///     ("key".to_string(), Quotes::NoQuotes, "key"),
+///     ("other".to_string(), Quotes::SingleQuote, "'other'"),
+///     ("nested".to_string(), Quotes::DoubleQuote, r#""nested""#),
+/// ]);
+/// ```
+pub fn keys(json: &str) -> impl Iterator<Item = KeySpan> + '_ {
+    scanner::tokenize(json)
+        .into_iter()
+        .filter_map(move |token| match token.kind {
+            scanner::TokenKind::Key(quote) => Some(KeySpan {
+                key: token.inner_text(json).to_string(),
+                quotes: quotes_from_char(quote),
+                span: token.start..token.end,
+            }),
+            _ => None,
+        })
+}
+
+/// Detects the quote style used by most of the quoted keys in the JSON string.
+///
+/// Returns `None` if no key is quoted. On a tie, [Quotes::DoubleQuote] is preferred
+/// over [Quotes::SingleQuote], which is preferred over [Quotes::Backtick].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let quote_type = json_key_quote_utils::json_infer_quote_type(r#"{'a': 1, 'b': {"c": 2}, d: 3}"#);
+/// assert_eq!(quote_type, Some(Quotes::SingleQuote));
+///
+/// assert_eq!(json_key_quote_utils::json_infer_quote_type("{a: 1}"), None);
+/// ```
+pub fn json_infer_quote_type(json: &str) -> Option<Quotes> {
+    let mut counts = [
+        (Quotes::DoubleQuote, 0),
+        (Quotes::SingleQuote, 0),
+        (Quotes::Backtick, 0),
+    ];
+    for key in keys(json) {
+        if let Some((_, count)) = counts.iter_mut().find(|(quotes, _)| *quotes == key.quotes) {
+            *count += 1;
+        }
+    }
+
+    counts
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map(|(quotes, _)| quotes)
+}
+
+/// Removes key-quotes from the JSON string.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_removed = json_key_quote_utils::json_remove_key_quotes("{\"key\": \"val\"}");
+/// assert_eq!(json_removed, "{key: \"val\"}");
+///
+/// let json_already_removed = json_key_quote_utils::json_remove_key_quotes("{key: \"val\"}");
+/// assert_eq!(json_already_removed, "{key: \"val\"}");
+/// ```
+pub fn json_remove_key_quotes(json: &str) -> String {
+    // Remove the quotes from the keys (single-quoted):
+    let json_single_quotes_passed = REMOVE_SINGLE_QUOTES_REGEX.replace_all(json, unquote_key);
+
+    // Remove the quotes from the keys (double-quoted):
+    let json_double_quotes_passed =
+        REMOVE_DOUBLE_QUOTES_REGEX.replace_all(&json_single_quotes_passed, unquote_key);
+
+    // Remove the backticks from the keys, backticks in string values are not key-quotes.
+    // Like the other key-quotes, they are kept around keys with structural characters, like `a,b`:
+    rewrite_tokens(&json_double_quotes_passed, |token| match token.kind {
+        scanner::TokenKind::Key(Some('`')) => {
+            let key = token.inner_text(&json_double_quotes_passed);
+            (!key.trim().is_empty() && key.chars().all(is_supported_key_char))
+                .then(|| key.to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Removes the key-quotes from the matched key, unless it is empty or only whitespace, like `""` or `" "`.
+fn unquote_key(caps: &Captures) -> String {
+    if caps["key"].trim().is_empty() {
+        return caps[0].to_string();
+    }
+
+    format!("{}{}{}", &caps["before"], &caps["key"], &caps["after"])
+}
+
+/// Removes key-quotes from the JSON string, including the custom key-quotes of the `quote_type`.
+///
+/// This is equivalent to [json_remove_key_quotes] for any other `quote_type` than [Quotes::Custom].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The custom key-quotes to remove.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_removed = json_key_quote_utils::json_remove_custom_key_quotes("{§key§: \"val\", \"other\": 1}", Quotes::Custom('§'));
+/// assert_eq!(json_removed, "{key: \"val\", other: 1}");
+/// ```
+pub fn json_remove_custom_key_quotes(json: &str, quote_type: Quotes) -> String {
+    let json_removed = json_remove_key_quotes(json);
+    let quote = match quote_type {
+        Quotes::Custom(_) => custom_quote_regex_str(quote_type),
+        _ => return json_removed,
+    };
+
+    // Remove the custom quotes from the keys:
+    // `/` == `\/` in Regex101
+    let custom_quotes_regex = Regex::new(
+        &(r#"(?P<before>[{\[,][\s]*)"#.to_string()
+            + &quote
+            + r#"(?P<key>["#
+            + SUPPORTED_KEY_CHARS_REGEX_STR
+            + r#"]*?)"#
+            + &quote
+            + r#"(?P<after>\s*?:)"#),
+    )
+    .unwrap();
+
+    custom_quotes_regex
+        .replace_all(&json_removed, unquote_key)
+        .to_string()
+}
+
+/// Removes key-quotes from the JSON string like [json_remove_key_quotes],
+/// except from the keys named `true`, `false` or `null`.
+///
+/// Without their quotes, these keys are ambiguous for some parsers.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_removed = json_key_quote_utils::json_remove_key_quotes_keep_reserved(r#"{"null": 1, 'true': "yes", "nullable": false}"#);
+/// assert_eq!(json_removed, r#"{"null": 1, 'true': "yes", nullable: false}"#);
+/// ```
+pub fn json_remove_key_quotes_keep_reserved(json: &str) -> String {
+    restore_key_quotes(json, &json_remove_key_quotes(json), is_reserved_key)
+}
+
+/// Whether the key is named like a literal, see [json_remove_key_quotes_keep_reserved].
+pub(crate) fn is_reserved_key(key: &str) -> bool {
+    matches!(key, "true" | "false" | "null")
+}
+
+/// Removes key-quotes from the JSON string like [json_remove_key_quotes],
+/// except from the numeric keys, like `123`, `007`, `-1` or `1.5`.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_removed = json_key_quote_utils::json_remove_key_quotes_keep_numeric(r#"{"123": "x", "007": "y", "key1": 1}"#);
+/// assert_eq!(json_removed, r#"{"123": "x", "007": "y", key1: 1}"#);
+/// ```
+pub fn json_remove_key_quotes_keep_numeric(json: &str) -> String {
+    restore_key_quotes(json, &json_remove_key_quotes(json), is_numeric_key)
+}
+
+/// Whether the key looks like a number, see [json_remove_key_quotes_keep_numeric].
+pub(crate) fn is_numeric_key(key: &str) -> bool {
+    let unsigned = key.strip_prefix('-').unwrap_or(key);
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, "0"));
+
+    !integer.is_empty()
+        && !fraction.is_empty()
+        && integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+}
+
+/// Removes key-quotes from the JSON string like [json_remove_key_quotes],
+/// but only from the keys which stay unambiguous without them.
+///
+/// These keys consist of ASCII letters, digits, `_`, `$`, `-` and `.` only, and are not named `true`, `false` or `null`.
+/// Other keys keep their key-quotes, like `"my key"`, which relaxed parsers would read up to the space,
+/// or `"null"`, which they could read as a literal, so the JSON string can be converted back with [json_add_key_quotes].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_removed = json_key_quote_utils::json_remove_key_quotes_safe(r#"{"my key": 1, "my_key": 2, "a.b-c": 3, "null": 4}"#);
+/// assert_eq!(json_removed, r#"{"my key": 1, my_key: 2, a.b-c: 3, "null": 4}"#);
+/// ```
+pub fn json_remove_key_quotes_safe(json: &str) -> String {
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::Key(Some(_)) => {
+            let key = token.inner_text(json);
+            is_safe_key(key).then(|| key.to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Whether the key stays unambiguous without key-quotes, see [json_remove_key_quotes_safe].
+fn is_safe_key(key: &str) -> bool {
+    !key.is_empty()
+        && !is_reserved_key(key)
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '$' | '-' | '.'))
+}
+
+/// Puts the key-quotes back around the keys for which `keep` returns `true`,
+/// after they were removed from the `original` JSON string.
+///
+/// Removing key-quotes does not change the tokens, so the keys are matched in document order.
+pub(crate) fn restore_key_quotes(
+    original: &str,
+    removed: &str,
+    keep: impl Fn(&str) -> bool,
+) -> String {
+    let mut original_keys = scanner::tokenize(original)
+        .into_iter()
+        .filter(|token| matches!(token.kind, scanner::TokenKind::Key(_)));
+
+    rewrite_tokens(removed, |token| match token.kind {
+        scanner::TokenKind::Key(_) => {
+            let original_key = original_keys.next()?;
+            let name = token.text(removed);
+            (original_key.text(original) != name && keep(name))
+                .then(|| original_key.text(original).to_string())
+        }
+        _ => None,
+    })
+}
+
+/// Returns the regex-escaped character of a [Quotes::Custom], or an empty string for any other quote type.
+fn custom_quote_regex_str(quote_type: Quotes) -> String {
+    match quote_type {
+        Quotes::Custom(c) => regex_escape(&c.to_string()),
+        _ => String::new(),
+    }
+}
+
+/// Whether the character is supported in JSON keys, see [crate::Quotes::custom].
+pub(crate) fn is_supported_key_char(c: char) -> bool {
+    SUPPORTED_KEY_CHAR_REGEX.is_match(&c.to_string())
+}
+
+/// Add quotes to the unquoted JSON keys for which the predicate returns `true`.
+///
+/// The predicate receives the unquoted key. Already quoted keys and the keys
+/// for which the predicate returns `false` are left untouched.
+/// Nothing is quoted if the `quote_type` is [Quotes::NoQuotes].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `predicate` - Whether the key should be quoted.
+/// * `quote_type` - The quotes to add around the matching keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_quoted = json_key_quote_utils::json_quote_only_keys_matching(
+///     "{first name: 1, age: 2}",
+///     |key| key.contains(' '),
+///     Quotes::default(),
+/// );
+/// assert_eq!(json_quoted, r#"{"first name": 1, age: 2}"#);
+/// ```
+pub fn json_quote_only_keys_matching(
+    json: &str,
+    predicate: impl Fn(&str) -> bool,
+    quote_type: Quotes,
+) -> String {
+    let Some(quote) = quote_type.as_char() else {
+        return json.to_string();
+    };
+
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::Key(None) if predicate(token.text(json)) => Some(quote_key(
+            &escape_quote(token.text(json), quote),
+            Some(quote),
+        )),
+        _ => None,
+    })
+}
+
+/// Adds key-quotes only to the keys of nested objects, at nesting depth `2` and deeper.
+///
+/// The keys of the top-level object stay as they are, see [json_add_key_quotes_from_depth].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to add around the nested keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_quoted = json_key_quote_utils::json_add_key_quotes_recursive_only("{a: {b: 1}}", Quotes::default());
+/// assert_eq!(json_quoted, r#"{a: {"b": 1}}"#);
+/// ```
+pub fn json_add_key_quotes_recursive_only(json: &str, quote_type: Quotes) -> String {
+    json_add_key_quotes_from_depth(json, quote_type, 2)
+}
+
+/// Adds key-quotes only to the keys at nesting depth `min_depth` and deeper.
+///
+/// The keys of the top-level object are at depth `1`,
+/// every enclosing object or array adds one to the depth, like in [json_count_keys_at_depth].
+/// Nothing is quoted if the `quote_type` is [Quotes::NoQuotes].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to add around the keys.
+/// * `min_depth` - The nesting depth of the outermost keys to quote.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_quoted = json_key_quote_utils::json_add_key_quotes_from_depth("{a: {b: {c: 1}}}", Quotes::default(), 3);
+/// assert_eq!(json_quoted, r#"{a: {b: {"c": 1}}}"#);
+/// ```
+pub fn json_add_key_quotes_from_depth(json: &str, quote_type: Quotes, min_depth: usize) -> String {
+    let Some(quote) = quote_type.as_char() else {
+        return json.to_string();
+    };
+
+    rewrite_tokens(json, |token| {
+        let key = token.text(json);
+        // Keys with custom key-quotes are scanned as unquoted keys:
+        let is_quoted =
+            key.len() > quote.len_utf8() && key.starts_with(quote) && key.ends_with(quote);
+        (token.kind == scanner::TokenKind::Key(None) && token.depth >= min_depth && !is_quoted)
+            .then(|| quote_key(&escape_quote(key, quote), Some(quote)))
+    })
+}
+
+/// Add quotes to the unquoted JSON keys which consist of ASCII digits only, like `0` or `42`.
+///
+/// All other keys are left untouched, see [json_quote_only_keys_matching].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to add around the numeric keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_quoted = json_key_quote_utils::json_quote_numeric_keys_only("{0: \"a\", 1: \"b\", key: \"c\"}", Quotes::default());
+/// assert_eq!(json_quoted, r#"{"0": "a", "1": "b", key: "c"}"#);
+/// ```
+pub fn json_quote_numeric_keys_only(json: &str, quote_type: Quotes) -> String {
+    json_quote_only_keys_matching(
+        json,
+        |key| !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit()),
+        quote_type,
+    )
+}
+
+/// Add quotes to the unquoted JSON keys which contain characters outside of `[A-Za-z0-9_]`,
+/// like spaces or dashes.
+///
+/// Bare alphanumeric keys and already quoted keys are left untouched, see [json_quote_only_keys_matching].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to add around the keys which need them.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_quoted = json_key_quote_utils::json_conditional_key_quotes("{simple_key: 1, key with spaces: 2}", Quotes::default());
+/// assert_eq!(json_quoted, r#"{simple_key: 1, "key with spaces": 2}"#);
+/// ```
+pub fn json_conditional_key_quotes(json: &str, quote_type: Quotes) -> String {
+    json_quote_only_keys_matching(
+        json,
+        |key| !key.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'),
+        quote_type,
+    )
+}
+
+/// Escape ctrl-characters from the JSON string values
+/// and remove ctrl-characters from the JSON keys with keyquotes.
+///
+/// This method will escape `newlines`, `tabs`, `carriage returns`, `backspaces`, `form feeds`
+/// and all other control characters in the range U+0000–U+001F as `\uXXXX`
+/// in the JSON string values and remove them from the JSON keys with keyquotes.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_escaped = json_key_quote_utils::json_escape_ctrlchars(r#"{"key": "va
+/// l"}"#);
+/// assert_eq!(json_escaped, r#"{"key": "va\nl"}"#);
+///
+/// let json_already_escaped = json_key_quote_utils::json_escape_ctrlchars(r#"{"key": "va\nl"}"#);
+/// assert_eq!(json_already_escaped, r#"{"key": "va\nl"}"#);
+/// ```
+pub fn json_escape_ctrlchars(json: &str) -> String {
+    if let Some(json_escaped) = convert_scalar_string(json, escape_ctrlchars) {
+        return json_escaped;
+    }
+
+    // Replace all control characters with their escaped variants:
+
+    let mut new_json = json.to_owned();
+
+    // Two iterations are needed for the tab escaping:
+
+    for _n in 0..2 {
+        for key_regex in ESCAPE_CTRLCHARS_KEY_REGEXES.iter() {
+            for cap in key_regex.captures_iter(&new_json.clone()) {
+                let cap_match = cap.name("key").unwrap().as_str();
+                new_json = new_json.replacen(cap_match, &remove_ctrlchars(cap_match), 1);
+            }
+        }
+
+        // For all single-quoted string values:
+        for cap in SINGLEQUOTED_STRING_VALUE_REGEX.captures_iter(&new_json.clone()) {
+            new_json = new_json.replacen(&cap[1], &escape_ctrlchars(&cap[1]), 1);
+        }
+
+        // For all double-quoted string values:
+        for cap in DOUBLEQUOTED_STRING_VALUE_REGEX.captures_iter(&new_json.clone()) {
+            new_json = new_json.replacen(&cap[1], &escape_ctrlchars(&cap[1]), 1);
+        }
+    }
+
+    new_json
+}
+
+/// Converts the contents of a top-level string, like `"va\nl"`, which the string value regexes miss
+/// as there is no colon in front of it.
+///
+/// Returns `None` if the JSON string is not a single string value.
+fn convert_scalar_string(json: &str, convert: fn(&str) -> String) -> Option<String> {
+    match scanner::parse(json)? {
+        Node::Scalar(token) if matches!(token.kind, scanner::TokenKind::String(_)) => {
+            let value = token.inner_text(json);
+            let value_start = token.start + 1;
+            let value_end = value_start + value.len();
+
+            Some(json[..value_start].to_string() + &convert(value) + &json[value_end..])
+        }
+        _ => None,
+    }
+}
+
+/// Unescape ctrl-characters from the JSON string values
+/// and remove ctrl-characters from the JSON keys without keyquotes.
+///
+/// This method will unescape `newlines`, `tabs`, `carriage returns`, `backspaces`, `form feeds`
+/// and all other control characters in the range U+0000–U+001F escaped as `\uXXXX`
+/// in the JSON string values and remove their escaped variants from the JSON keys without keyquotes.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_unescaped = json_key_quote_utils::json_unescape_ctrlchars(r#"{key: "va\nl"}"#);
+/// assert_eq!(json_unescaped, r#"{key: "va
+/// l"}"#);
+///
+/// let json_already_unescaped = json_key_quote_utils::json_unescape_ctrlchars(&json_unescaped);
+/// assert_eq!(json_already_unescaped, r#"{key: "va
+/// l"}"#);
+/// ```
+pub fn json_unescape_ctrlchars(json: &str) -> String {
+    if let Some(json_unescaped) = convert_scalar_string(json, unescape_ctrlchars) {
+        return json_unescaped;
+    }
+
+    // Replace all escaped control characters with their unescaped variants:
+
+    let mut new_json = json.to_owned();
+
+    // Two iterations are needed for the tab unescaping:
+
+    for _n in 0..2 {
+        for key_regex in UNESCAPE_CTRLCHARS_KEY_REGEXES.iter() {
+            for cap in key_regex.captures_iter(&new_json.clone()) {
+                let cap_match = cap.name("key").unwrap().as_str();
+                new_json = new_json.replacen(cap_match, &remove_escaped_ctrlchars(cap_match), 1);
+            }
+        }
+
+        // For all single-quoted string values:
+        for cap in SINGLEQUOTED_STRING_VALUE_REGEX.captures_iter(&new_json.clone()) {
+            new_json = new_json.replacen(&cap[1], &unescape_ctrlchars(&cap[1]), 1);
+        }
+
+        // For all double-quoted string values:
+        for cap in DOUBLEQUOTED_STRING_VALUE_REGEX.captures_iter(&new_json.clone()) {
+            new_json = new_json.replacen(&cap[1], &unescape_ctrlchars(&cap[1]), 1);
+        }
+    }
+
+    new_json
+}
+
+/// Adds key-quotes to the JSON string, see [json_add_key_quotes],
+/// and counts the keys which got key-quotes.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let (json_added, stats) = json_key_quote_utils::json_add_key_quotes_with_stats("{key: \"val\", \"other\": 1}", Quotes::DoubleQuote);
+/// assert_eq!(json_added, "{\"key\": \"val\", \"other\": 1}");
+/// assert_eq!(stats.keys_quoted, 1);
+/// ```
+pub fn json_add_key_quotes_with_stats(json: &str, quote_type: Quotes) -> (String, ConversionStats) {
+    let json_added = json_add_key_quotes(json, quote_type);
+    let stats = count_key_quote_changes(json, &json_added);
+
+    (json_added, stats)
+}
+
+/// Removes key-quotes from the JSON string, see [json_remove_custom_key_quotes],
+/// and counts the keys which lost their key-quotes.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The custom key-quotes to remove.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let (json_removed, stats) = json_key_quote_utils::json_remove_key_quotes_with_stats("{\"key\": \"val\", other: 1}", Quotes::DoubleQuote);
+/// assert_eq!(json_removed, "{key: \"val\", other: 1}");
+/// assert_eq!(stats.keys_unquoted, 1);
+/// ```
+pub fn json_remove_key_quotes_with_stats(
+    json: &str,
+    quote_type: Quotes,
+) -> (String, ConversionStats) {
+    let json_removed = json_remove_custom_key_quotes(json, quote_type);
+    let stats = count_key_quote_changes(json, &json_removed);
+
+    (json_removed, stats)
+}
+
+/// Escapes ctrl-characters in the JSON string, see [json_escape_ctrlchars],
+/// and counts the string values with escaped ctrl-characters.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let (json_escaped, stats) = json_key_quote_utils::json_escape_ctrlchars_with_stats("{key: \"va\nl\", other: \"val\"}");
+/// assert_eq!(json_escaped, r#"{key: "va\nl", other: "val"}"#);
+/// assert_eq!(stats.values_escaped, 1);
+/// ```
+pub fn json_escape_ctrlchars_with_stats(json: &str) -> (String, ConversionStats) {
+    let json_escaped = json_escape_ctrlchars(json);
+    let stats = ConversionStats {
+        values_escaped: count_changed_values(json, &json_escaped),
+        ..ConversionStats::default()
+    };
+
+    (json_escaped, stats)
+}
+
+/// Unescapes ctrl-characters in the JSON string, see [json_unescape_ctrlchars],
+/// and counts the string values with unescaped ctrl-characters.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let (json_unescaped, stats) = json_key_quote_utils::json_unescape_ctrlchars_with_stats(r#"{key: "va\nl", other: "val"}"#);
+/// assert_eq!(json_unescaped, "{key: \"va\nl\", other: \"val\"}");
+/// assert_eq!(stats.values_unescaped, 1);
+/// ```
+pub fn json_unescape_ctrlchars_with_stats(json: &str) -> (String, ConversionStats) {
+    let json_unescaped = json_unescape_ctrlchars(json);
+    let stats = ConversionStats {
+        values_unescaped: count_changed_values(json, &json_unescaped),
+        ..ConversionStats::default()
+    };
+
+    (json_unescaped, stats)
+}
+
+/// The colons of the empty keys, which directly follow the `{` or `,`.
+fn empty_key_colons(tokens: &[Token]) -> impl Iterator<Item = &Token> {
+    tokens
+        .windows(2)
+        .filter(|pair| {
+            matches!(
+                pair[0].kind,
+                scanner::TokenKind::ObjectStart | scanner::TokenKind::Comma
+            ) && pair[1].kind == scanner::TokenKind::Colon
+        })
+        .map(|pair| &pair[1])
+}
+
+/// Counts the keys which got or lost their key-quotes between the two JSON strings.
+pub(crate) fn count_key_quote_changes(before: &str, after: &str) -> ConversionStats {
+    let count_bare_keys = |json: &str| {
+        let tokens = scanner::tokenize(json);
+        let bare_keys = tokens
+            .iter()
+            .filter(|token| token.kind == scanner::TokenKind::Key(None))
+            .count();
+        bare_keys + empty_key_colons(&tokens).count()
+    };
+    let bare_before = count_bare_keys(before);
+    let bare_after = count_bare_keys(after);
+
+    ConversionStats {
+        keys_quoted: bare_before.saturating_sub(bare_after),
+        keys_unquoted: bare_after.saturating_sub(bare_before),
+        ..ConversionStats::default()
+    }
+}
+
+/// Counts the string values which differ between the two JSON strings.
+fn count_changed_values(before: &str, after: &str) -> usize {
+    let string_values = |json: &str| -> Vec<String> {
+        scanner::tokenize(json)
+            .iter()
+            .filter(|token| matches!(token.kind, scanner::TokenKind::String(_)))
+            .map(|token| token.text(json).to_string())
+            .collect()
+    };
+
+    string_values(before)
+        .iter()
+        .zip(string_values(after).iter())
+        .filter(|(value_before, value_after)| value_before != value_after)
+        .count()
+}
+
+/// Pretty-prints the JSON string with one member per line.
+///
+/// Nested objects and arrays are indented with `indent` per level, and a single space
+/// is put after every colon. Empty objects and arrays are kept on a single line.
+/// Unquoted keys and single-quoted strings are supported, as string boundaries are tracked
+/// instead of requiring valid JSON.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `indent` - The indentation to use for every nesting level.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Indent};
+///
+/// let json_pretty = json_key_quote_utils::json_pretty(r#"{key:"val",other:{}}"#, Indent::Tab);
+/// assert_eq!(json_pretty, "{\n\tkey: \"val\",\n\tother: {}\n}");
+/// ```
+pub fn json_pretty(json: &str, indent: Indent) -> String {
+    // Empty and whitespace-only input has nothing to format:
+    if json.trim().is_empty() {
+        return json.to_string();
+    }
+    let indent = indent.as_string();
+    let tokens = scanner::tokenize(json);
+
+    let mut pretty = String::with_capacity(json.len() * 2);
+    let mut level: usize = 0;
+    let mut line_break = false;
+    let mut previous: Option<&Token> = None;
+    let mut tokens_iter = tokens.iter().peekable();
+
+    while let Some(token) = tokens_iter.next() {
+        match token.kind {
+            scanner::TokenKind::ObjectEnd | scanner::TokenKind::ArrayEnd => {
+                level = level.saturating_sub(1);
+                line_break = false;
+                pretty.push('\n');
+                pretty.push_str(&indent.repeat(level));
+                pretty.push_str(token.text(json));
+            }
+            _ => {
+                if line_break {
+                    line_break = false;
+                    pretty.push('\n');
+                    pretty.push_str(&indent.repeat(level));
+                } else if previous.is_some_and(is_bare) && is_bare(token) {
+                    pretty.push(' ');
+                }
+                pretty.push_str(token.text(json));
+
+                match token.kind {
+                    scanner::TokenKind::ObjectStart | scanner::TokenKind::ArrayStart => {
+                        let is_empty = tokens_iter.peek().is_some_and(|next| {
+                            matches!(
+                                next.kind,
+                                scanner::TokenKind::ObjectEnd | scanner::TokenKind::ArrayEnd
+                            )
+                        });
+                        if is_empty {
+                            pretty.push_str(tokens_iter.next().unwrap().text(json));
+                        } else {
+                            level += 1;
+                            line_break = true;
+                        }
+                    }
+                    scanner::TokenKind::Comma => line_break = true,
+                    scanner::TokenKind::Colon => pretty.push(' '),
+                    _ => (),
+                }
+            }
+        }
+        previous = Some(token);
+    }
+
+    pretty
+}
+
+/// Converts the quotes around all JSON string values to the `target` quote style.
+///
+/// Keys are not affected. Values which already use the `target` quote style are left untouched.
+///
+/// The contents of the converted values are adjusted, so that they keep the same meaning:
+/// * Escaped quotes of the original style are unescaped, e.g. `'it\'s'` becomes `"it's"`.
+/// * Unescaped quotes of the `target` style are escaped, e.g. `'say "hi"'` becomes `"say \"hi\""`.
+/// * All other characters and escape sequences are kept as-is, so values containing both
+///   quote styles only get the quotes of the `target` style escaped.
+///
+/// String values can only be single- or double-quoted. A `target` of [Quotes::Backtick],
+/// [Quotes::NoQuotes] or [Quotes::Custom] is not valid around string values,
+/// so the JSON string is returned unchanged for these targets.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `target` - Whether the JSON string values should be single- or double-quoted.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_converted = json_key_quote_utils::json_convert_value_quotes(r#"{'key': 'it\'s'}"#, Quotes::DoubleQuote);
+/// assert_eq!(json_converted, r#"{'key': "it's"}"#);
+///
+/// let json_already_converted = json_key_quote_utils::json_convert_value_quotes(&json_converted, Quotes::DoubleQuote);
+/// assert_eq!(json_already_converted, r#"{'key': "it's"}"#);
+///
+/// let json_unchanged = json_key_quote_utils::json_convert_value_quotes(r#"{'key': 'val'}"#, Quotes::Backtick);
+/// assert_eq!(json_unchanged, r#"{'key': 'val'}"#);
+/// ```
+pub fn json_convert_value_quotes(json: &str, target: Quotes) -> String {
+    let target_quote = match target {
+        Quotes::DoubleQuote | Quotes::SingleQuote => target.as_char().unwrap(),
+        _ => return json.to_string(),
+    };
+
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::String(quote) if quote != target_quote => {
+            Some(convert_quotes(token.inner_text(json), quote, target_quote))
+        }
+        _ => None,
+    })
+}
+
+/// Surrounds the text of a string with the `target_quote`, see [json_convert_value_quotes].
+fn convert_quotes(text: &str, quote: char, target_quote: char) -> String {
+    let mut converted = String::from(target_quote);
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped) if escaped == quote && quote != target_quote => {
+                    converted.push(escaped)
+                }
+                Some(escaped) => {
+                    converted.push('\\');
+                    converted.push(escaped);
+                }
+                None => converted.push('\\'),
+            },
+            _ if c == target_quote => {
+                converted.push('\\');
+                converted.push(c);
+            }
+            _ => converted.push(c),
+        }
+    }
+    converted.push(target_quote);
+
+    converted
+}
+
+/// Parses relaxed JSON into a [serde_json::Value].
+///
+/// The JSON string is made valid JSON first, by adding double quotes around the unquoted keys,
+/// converting single-quoted keys and string values to double-quoted ones
+/// and escaping the ctrl-characters.
+///
+/// Only available with the `serde` feature.
+///
+/// # Arguments
+///
+/// * `json` - The relaxed JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let value = json_key_quote_utils::json_parse_relaxed("{key: 'va\nl', 'other': [1, 2]}").unwrap();
+/// assert_eq!(value["key"], "va\nl");
+/// assert_eq!(value["other"][1], 2);
+/// ```
+#[cfg(feature = "serde")]
+pub fn json_parse_relaxed(json: &str) -> Result<serde_json::Value, crate::ValidationError> {
+    Ok(serde_json::from_str(&json_normalize(json))?)
+}
+
+/// Converts relaxed JSON to valid JSON, see [json_parse_relaxed].
+#[cfg(feature = "serde")]
+pub(crate) fn json_normalize(json: &str) -> String {
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::Key(quote) => {
+            let quote = quote.unwrap_or('"');
+            let key_converted = convert_quotes(token.inner_text(json), quote, '"');
+            Some(escape_ctrlchars(&key_converted))
+        }
+        scanner::TokenKind::String(quote) => {
+            let value_converted = convert_quotes(token.inner_text(json), quote, '"');
+            Some(escape_ctrlchars(&value_converted))
+        }
+        _ => None,
+    })
+}
+
+/// Converts valid JSON to relaxed JSON, see [crate::to_relaxed_string].
+///
+/// Unlike [json_remove_key_quotes] and [json_unescape_ctrlchars], this only removes the key-quotes
+/// if the key can be read back without them, and leaves escaped backslashes untouched.
+#[cfg(feature = "serde")]
+pub(crate) fn json_relax(json: &str, value_quote_type: Quotes) -> String {
+    let json_removed = rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::Key(Some(_)) => {
+            let name = token.inner_text(json);
+            let needs_quotes = name.is_empty()
+                || name.trim() != name
+                || name.contains([':', ',', '{', '}', '[', ']', '"', '\'', '\\']);
+            (!needs_quotes).then(|| name.to_string())
+        }
+        _ => None,
+    });
+    let json_values_converted = json_convert_value_quotes(&json_removed, value_quote_type);
+
+    rewrite_string_values(&json_values_converted, |c, escaped| {
+        CTRLCHAR_ESCAPES
+            .iter()
+            .find(|(_, escape)| escaped && escape.ends_with(c))
+            .map(|(ctrlchar, _)| ctrlchar.to_string())
+    })
+}
+
+/// Escapes the forward slashes in the JSON string values as `\/`.
+///
+/// Forward slashes outside of string values, like in keys, are not affected.
+/// Already escaped forward slashes are left untouched.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_escaped = json_key_quote_utils::json_escape_forward_slash(r#"{a/b: "http://example.com"}"#);
+/// assert_eq!(json_escaped, r#"{a/b: "http:\/\/example.com"}"#);
+///
+/// let json_already_escaped = json_key_quote_utils::json_escape_forward_slash(&json_escaped);
+/// assert_eq!(json_already_escaped, r#"{a/b: "http:\/\/example.com"}"#);
+/// ```
+pub fn json_escape_forward_slash(json: &str) -> String {
+    rewrite_string_values(json, |c, escaped| match (c, escaped) {
+        ('/', false) => Some("\\/".to_string()),
+        _ => None,
+    })
+}
+
+/// Unescapes the escaped forward slashes (`\/`) in the JSON string values.
+///
+/// Forward slashes outside of string values, like in keys, are not affected.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_unescaped = json_key_quote_utils::json_unescape_forward_slash(r#"{"a\/b": "http:\/\/example.com"}"#);
+/// assert_eq!(json_unescaped, r#"{"a\/b": "http://example.com"}"#);
+///
+/// let json_already_unescaped = json_key_quote_utils::json_unescape_forward_slash(&json_unescaped);
+/// assert_eq!(json_already_unescaped, r#"{"a\/b": "http://example.com"}"#);
+/// ```
+pub fn json_unescape_forward_slash(json: &str) -> String {
+    rewrite_string_values(json, |c, escaped| match (c, escaped) {
+        ('/', true) => Some("/".to_string()),
+        _ => None,
+    })
+}
+
+/// Escapes the HTML-sensitive characters `<`, `>`, `&`, `'` and `"` in the JSON string values
+/// as `\u003C`, `\u003E`, `\u0026`, `\u0027` and `\u0022`.
+///
+/// This makes the JSON safe to embed in an HTML `<script>` tag.
+/// Keys are not affected, and already escaped sequences are left untouched.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_escaped = json_key_quote_utils::json_escape_html(r#"{key: "</script> & 'more'"}"#);
+/// assert_eq!(json_escaped, r#"{key: "\u003C/script\u003E \u0026 \u0027more\u0027"}"#);
+///
+/// let json_already_escaped = json_key_quote_utils::json_escape_html(&json_escaped);
+/// assert_eq!(json_already_escaped, r#"{key: "\u003C/script\u003E \u0026 \u0027more\u0027"}"#);
+/// ```
+pub fn json_escape_html(json: &str) -> String {
+    rewrite_string_values(json, |c, escaped| {
+        HTML_ESCAPES
+            .iter()
+            .find(|(html_char, _)| !escaped && *html_char == c)
+            .map(|(_, escape)| escape.to_string())
+    })
+}
+
+/// Unescapes the HTML-sensitive characters escaped by [json_escape_html] in the JSON string values.
+///
+/// The escape sequences are matched case-insensitively.
+/// An escaped quote is left untouched if it is the quote character of its string value.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_unescaped = json_key_quote_utils::json_unescape_html(r#"{key: "\u003C/script\u003e \u0026 \u0022"}"#);
+/// assert_eq!(json_unescaped, r#"{key: "</script> & \u0022"}"#);
+///
+/// let json_already_unescaped = json_key_quote_utils::json_unescape_html(&json_unescaped);
+/// assert_eq!(json_already_unescaped, r#"{key: "</script> & \u0022"}"#);
+/// ```
+pub fn json_unescape_html(json: &str) -> String {
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::String(quote) => {
+            let mut unescaped = String::from(quote);
+            let mut rest = token.inner_text(json);
+            while let Some(i) = rest.find('\\') {
+                unescaped.push_str(&rest[..i]);
+                let escape = &rest[i..];
+                let html_escape = HTML_ESCAPES.iter().find(|(html_char, html_escape)| {
+                    *html_char != quote
+                        && escape
+                            .get(..html_escape.len())
+                            .is_some_and(|text| text.eq_ignore_ascii_case(html_escape))
+                });
+                let escape_len = match html_escape {
+                    Some((html_char, html_escape)) => {
+                        unescaped.push(*html_char);
+                        html_escape.len()
+                    }
+                    None => {
+                        let escape_len = 1 + escape[1..].chars().next().map_or(0, char::len_utf8);
+                        unescaped.push_str(&escape[..escape_len]);
+                        escape_len
+                    }
+                };
+                rest = &escape[escape_len..];
+            }
+            unescaped.push_str(rest);
+            unescaped.push(quote);
+            Some(unescaped)
+        }
+        _ => None,
+    })
+}
+
+/// Rewrites the characters inside the JSON string values for which `rewrite` returns a replacement.
+///
+/// `rewrite` receives every character of the string values, and whether it was escaped by a backslash.
+/// The replacement of an escaped character replaces the backslash too.
+fn rewrite_string_values(json: &str, rewrite: impl Fn(char, bool) -> Option<String>) -> String {
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::String(quote) => {
+            let mut rewritten = String::from(quote);
+            let mut chars = token.inner_text(json).chars();
+            while let Some(c) = chars.next() {
+                let (c, escaped) = match c {
+                    '\\' => match chars.next() {
+                        Some(next) => (next, true),
+                        None => ('\\', false),
+                    },
+                    _ => (c, false),
+                };
+                match rewrite(c, escaped) {
+                    Some(replacement) => rewritten.push_str(&replacement),
+                    None => {
+                        if escaped {
+                            rewritten.push('\\');
+                        }
+                        rewritten.push(c);
+                    }
+                }
+            }
+            rewritten.push(quote);
+            Some(rewritten)
+        }
+        _ => None,
+    })
+}
+
+/// Transforms every JSON key in key position using the closure `f`.
+///
+/// The closure receives the key text without its quotes, and the returned name replaces it.
+/// Quoted keys keep their quotes, and unescaped quote characters in the returned name are escaped.
+/// Unquoted keys stay unquoted, unless the returned name would be ambiguous without quotes
+/// (when it is empty, contains `:`, `,`, `{`, `}`, `[`, `]` or surrounding whitespace),
+/// in which case it is quoted using the `quote_type`.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `f` - The closure which returns the new name of a key.
+/// * `quote_type` - The quotes to use for unquoted keys which need quotes after the transformation.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_transformed = json_key_quote_utils::json_transform_keys(
+///     r#"{"x-key": 1, x-other: {" nested ": 2}}"#,
+///     |key| key.trim().trim_start_matches("x-").to_string(),
+///     Quotes::default(),
+/// );
+/// assert_eq!(json_transformed, r#"{"key": 1, other: {"nested": 2}}"#);
+/// ```
+pub fn json_transform_keys(json: &str, f: impl Fn(&str) -> String, quote_type: Quotes) -> String {
+    rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::Key(quote) => {
+            let name = f(token.inner_text(json));
+            let quote = quote.or_else(|| {
+                let needs_quotes = name.is_empty()
+                    || name.trim() != name
+                    || name.contains([':', ',', '{', '}', '[', ']']);
+                needs_quotes.then(|| {
+                    quote_type
+                        .as_char()
+                        .unwrap_or_else(|| Quotes::default().as_char().unwrap())
+                })
+            });
+            Some(match quote {
+                Some(quote) => quote_key(&escape_quote(&name, quote), Some(quote)),
+                None => name,
+            })
+        }
+        _ => None,
+    })
+}
+
+/// Prepends the prefix to every JSON key in key position.
+///
+/// The prefix is inserted inside the quotes of quoted keys, see [json_transform_keys].
+/// Unquoted keys which would be ambiguous with the prefix are double-quoted.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `prefix` - The prefix to prepend to the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_prefixed = json_key_quote_utils::json_add_key_prefix(r#"{"key": {other: "value"}}"#, "v2_");
+/// assert_eq!(json_prefixed, r#"{"v2_key": {v2_other: "value"}}"#);
+/// ```
+pub fn json_add_key_prefix(json: &str, prefix: &str) -> String {
+    json_add_key_prefix_and_suffix(json, prefix, "")
+}
+
+/// Strips the prefix from every JSON key in key position which starts with it.
+///
+/// Keys without the prefix are left untouched, see [json_transform_keys].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `prefix` - The prefix to strip from the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_stripped = json_key_quote_utils::json_remove_key_prefix(r#"{"v2_key": {v2_other: "v2_value", key: 1}}"#, "v2_");
+/// assert_eq!(json_stripped, r#"{"key": {other: "v2_value", key: 1}}"#);
+/// ```
+pub fn json_remove_key_prefix(json: &str, prefix: &str) -> String {
+    json_transform_keys(
+        json,
+        |key| key.strip_prefix(prefix).unwrap_or(key).to_string(),
+        Quotes::default(),
+    )
+}
+
+/// Appends the suffix to every JSON key in key position.
+///
+/// The suffix is inserted inside the quotes of quoted keys, see [json_transform_keys].
+/// Unquoted keys which would be ambiguous with the suffix are double-quoted.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `suffix` - The suffix to append to the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_suffixed = json_key_quote_utils::json_add_key_suffix(r#"{"key": {other: "value"}}"#, "_v2");
+/// assert_eq!(json_suffixed, r#"{"key_v2": {other_v2: "value"}}"#);
+/// ```
+pub fn json_add_key_suffix(json: &str, suffix: &str) -> String {
+    json_add_key_prefix_and_suffix(json, "", suffix)
+}
+
+/// Strips the suffix from every JSON key in key position which ends with it.
+///
+/// Keys without the suffix are left untouched, see [json_transform_keys].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `suffix` - The suffix to strip from the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_stripped = json_key_quote_utils::json_remove_key_suffix(r#"{"key_v2": {other_v2: "value_v2", key: 1}}"#, "_v2");
+/// assert_eq!(json_stripped, r#"{"key": {other: "value_v2", key: 1}}"#);
+/// ```
+pub fn json_remove_key_suffix(json: &str, suffix: &str) -> String {
+    json_transform_keys(
+        json,
+        |key| key.strip_suffix(suffix).unwrap_or(key).to_string(),
+        Quotes::default(),
+    )
+}
+
+/// Prepends the prefix and appends the suffix to every JSON key in key position, in a single pass.
+///
+/// See [json_add_key_prefix] and [json_add_key_suffix].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `prefix` - The prefix to prepend to the keys.
+/// * `suffix` - The suffix to append to the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_renamed = json_key_quote_utils::json_add_key_prefix_and_suffix(r#"{"key": {other: 1}}"#, "x_", "_v2");
+/// assert_eq!(json_renamed, r#"{"x_key_v2": {x_other_v2: 1}}"#);
+/// ```
+pub fn json_add_key_prefix_and_suffix(json: &str, prefix: &str, suffix: &str) -> String {
+    json_transform_keys(
+        json,
+        |key| format!("{}{}{}", prefix, key, suffix),
+        Quotes::default(),
+    )
+}
+
+/// Converts every JSON key in key position to the requested case.
+///
+/// The keys are split into words on `_`, `-` and whitespace, and on changes in case:
+/// * A lowercase letter or digit followed by an uppercase letter starts a new word, e.g. `v2Config` becomes `v2` and `Config`.
+/// * An acronym ends before its last uppercase letter if a lowercase letter follows, e.g. `HTTPServer` becomes `HTTP` and `Server`.
+///
+/// Quoted keys keep their quotes, and string values are left untouched.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `case` - The case to convert the JSON keys to.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, KeyCase};
+///
+/// let json_converted = json_key_quote_utils::json_convert_key_case(r#"{"HTTPServer": {v2Config: "keepThis"}}"#, KeyCase::Snake);
+/// assert_eq!(json_converted, r#"{"http_server": {v2_config: "keepThis"}}"#);
+/// ```
+pub fn json_convert_key_case(json: &str, case: KeyCase) -> String {
+    json_transform_keys(json, |key| convert_case(key, case), Quotes::default())
+}
+
+/// Converts every JSON key in key position to `snake_case`, see [json_convert_key_case].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_converted = json_key_quote_utils::json_keys_to_snake_case(r#"{"userId": {ApiURL: "keepThis"}}"#);
+/// assert_eq!(json_converted, r#"{"user_id": {api_url: "keepThis"}}"#);
+/// ```
+pub fn json_keys_to_snake_case(json: &str) -> String {
+    json_convert_key_case(json, KeyCase::Snake)
+}
+
+/// Converts every JSON key in key position to `camelCase`, see [json_convert_key_case].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_converted = json_key_quote_utils::json_keys_to_camel_case(r#"{"user_id": {api_url: "keep_this"}}"#);
+/// assert_eq!(json_converted, r#"{"userId": {apiUrl: "keep_this"}}"#);
+/// ```
+pub fn json_keys_to_camel_case(json: &str) -> String {
+    json_convert_key_case(json, KeyCase::Camel)
+}
+
+/// Collapses every run of whitespace in the JSON keys to a single space, and trims the keys.
+///
+/// Both quoted and unquoted keys are collapsed, see [json_transform_keys].
+/// The escaped whitespace characters `\t`, `\n`, `\r` and `\f` in quoted keys count as whitespace too.
+/// String values are left untouched.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_collapsed = json_key_quote_utils::json_collapse_whitespace_in_keys("{\"key \t name\": \"a  b\", other  key: 1}");
+/// assert_eq!(json_collapsed, "{\"key name\": \"a  b\", other key: 1}");
+/// ```
+pub fn json_collapse_whitespace_in_keys(json: &str) -> String {
+    json_transform_keys(json, collapse_whitespace, Quotes::default())
+}
+
+/// Collapses the runs of (escaped) whitespace in the key text, see [json_collapse_whitespace_in_keys].
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut pending_space = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let is_whitespace = match c {
+            '\\' => chars
+                .next_if(|next| matches!(next, 't' | 'n' | 'r' | 'f'))
+                .is_some(),
+            _ => c.is_whitespace(),
+        };
+        if is_whitespace {
+            pending_space = !collapsed.is_empty();
+            continue;
+        }
+
+        if pending_space {
+            collapsed.push(' ');
+            pending_space = false;
+        }
+        collapsed.push(c);
+        // Keep the escaped character together with its backslash:
+        if c == '\\' {
+            if let Some(escaped) = chars.next() {
+                collapsed.push(escaped);
+            }
+        }
+    }
+
+    collapsed
+}
+
+/// Converts the text to the requested case, see [json_convert_key_case].
+fn convert_case(text: &str, case: KeyCase) -> String {
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => {
+                first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase()
+            }
+            None => String::new(),
+        }
+    };
+
+    let words = split_words(text);
+    match case {
+        KeyCase::Snake => words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        KeyCase::Kebab => words
+            .iter()
+            .map(|word| word.to_lowercase())
+            .collect::<Vec<_>>()
+            .join("-"),
+        KeyCase::ScreamingSnake => words
+            .iter()
+            .map(|word| word.to_uppercase())
+            .collect::<Vec<_>>()
+            .join("_"),
+        KeyCase::Pascal => words.iter().map(|word| capitalize(word)).collect(),
+        KeyCase::Camel => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                if i == 0 {
+                    word.to_lowercase()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect(),
+    }
+}
+
+/// Splits the text into words, see [json_convert_key_case].
+fn split_words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' || c == '-' || c.is_whitespace() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lowercase)
+            {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+/// Escapes the unescaped occurrences of the `quote` character in the text.
+pub(crate) fn escape_quote(text: &str, quote: char) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            escaped.push(c);
+            if let Some(next) = chars.next() {
+                escaped.push(next);
+            }
+        } else {
+            if c == quote {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+
+    escaped
+}
+
+/// Sorts the members of every JSON object by their keys.
+///
+/// Nested objects, including objects inside arrays, are sorted too.
+/// The keys are compared without their quotes, either byte-wise or case-insensitively.
+/// Members with equal keys keep their original order.
+/// Each member keeps its full text, and the whitespace and commas between the members stay in place.
+///
+/// The JSON string is returned unchanged if it is not structurally valid.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `case_insensitive` - Whether the keys should be compared case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_sorted = json_key_quote_utils::json_sort_keys(r#"{"b": 1, a: {d: 2, 'c': 3}}"#, false);
+/// assert_eq!(json_sorted, r#"{a: {'c': 3, d: 2}, "b": 1}"#);
+///
+/// let json_sorted_case_insensitive = json_key_quote_utils::json_sort_keys(r#"{b: 1, C: 2, a: 3}"#, true);
+/// assert_eq!(json_sorted_case_insensitive, r#"{a: 3, b: 1, C: 2}"#);
+/// ```
+pub fn json_sort_keys(json: &str, case_insensitive: bool) -> String {
+    match scanner::parse(json) {
+        Some(node) => {
+            json[..node.start()].to_string()
+                + &render_sorted(json, &node, case_insensitive)
+                + &json[node.end()..]
+        }
+        None => json.to_string(),
+    }
+}
+
+fn render_sorted(json: &str, node: &Node, case_insensitive: bool) -> String {
+    match node {
+        Node::Scalar(token) => token.text(json).to_string(),
+        Node::Array { start, end, items } => {
+            let rendered: Vec<String> = items
+                .iter()
+                .map(|item| render_sorted(json, item, case_insensitive))
+                .collect();
+            let spans: Vec<(usize, usize)> = items
+                .iter()
+                .map(|item| (item.start(), item.end()))
+                .collect();
+            fill_slots(json, (*start, *end), &spans, &rendered)
+        }
+        Node::Object {
+            start,
+            end,
+            members,
+        } => {
+            let mut sorted: Vec<&Member> = members.iter().collect();
+            if case_insensitive {
+                sorted.sort_by_cached_key(|(key, _)| key.inner_text(json).to_lowercase());
+            } else {
+                sorted.sort_by_key(|(key, _)| key.inner_text(json));
+            }
+
+            let rendered: Vec<String> = sorted
+                .iter()
+                .map(|(key, value)| {
+                    json[key.start..value.start()].to_string()
+                        + &render_sorted(json, value, case_insensitive)
+                })
+                .collect();
+            let spans: Vec<(usize, usize)> = members
+                .iter()
+                .map(|(key, value)| (key.start, value.end()))
+                .collect();
+            fill_slots(json, (*start, *end), &spans, &rendered)
+        }
+    }
+}
+
+/// Computes which keys were added and removed between the two JSON strings.
+///
+/// The keys of all nesting depths are compared without their quotes,
+/// so converting between key-quote styles results in no difference.
+///
+/// # Arguments
+///
+/// * `before` - The original JSON string.
+/// * `after` - The converted JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, JsonKeyDiff};
+///
+/// let diff = json_key_quote_utils::json_diff_keys(r#"{a: 1, b: {c: 2}}"#, r#"{"a": 1, "b": {"d": 2}}"#);
+/// assert_eq!(diff, JsonKeyDiff {
+///     added: vec!["d".to_string()],
+///     removed: vec!["c".to_string()],
+///     unchanged: vec!["a".to_string(), "b".to_string()],
+/// });
+/// ```
+pub fn json_diff_keys(before: &str, after: &str) -> JsonKeyDiff {
+    let key_set = |json: &str| -> BTreeSet<String> {
+        scanner::tokenize(json)
+            .into_iter()
+            .filter(|token| matches!(token.kind, scanner::TokenKind::Key(_)))
+            .map(|token| token.inner_text(json).to_string())
+            .collect()
+    };
+    let before_keys = key_set(before);
+    let after_keys = key_set(after);
+
+    JsonKeyDiff {
+        added: after_keys.difference(&before_keys).cloned().collect(),
+        removed: before_keys.difference(&after_keys).cloned().collect(),
+        unchanged: before_keys.intersection(&after_keys).cloned().collect(),
+    }
+}
+
+/// Finds the keys which occur more than once in the same JSON object.
+///
+/// Keys are compared without their quotes. Identical keys in different objects are not duplicates.
+/// The duplicates are returned in the order of their first occurrence.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, DuplicateKey};
+///
+/// let duplicates = json_key_quote_utils::json_find_duplicate_keys(r#"{"a": 1, b: {a: 2}, 'a': 3}"#);
+/// assert_eq!(duplicates, vec![DuplicateKey { key: "a".to_string(), offsets: vec![1, 20] }]);
+/// ```
+pub fn json_find_duplicate_keys(json: &str) -> Vec<DuplicateKey> {
+    let mut duplicates = Vec::new();
+    let mut objects: Vec<Vec<DuplicateKey>> = Vec::new();
+
+    for token in scanner::tokenize(json) {
+        match token.kind {
+            scanner::TokenKind::ObjectStart => objects.push(Vec::new()),
+            scanner::TokenKind::ObjectEnd => {
+                if let Some(keys) = objects.pop() {
+                    duplicates.extend(keys.into_iter().filter(|key| key.offsets.len() > 1));
+                }
+            }
+            scanner::TokenKind::Key(_) => {
+                if let Some(keys) = objects.last_mut() {
+                    let name = token.inner_text(json);
+                    match keys.iter_mut().find(|key| key.key == name) {
+                        Some(key) => key.offsets.push(token.start),
+                        None => keys.push(DuplicateKey {
+                            key: name.to_string(),
+                            offsets: vec![token.start],
+                        }),
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    duplicates.sort_by_key(|duplicate| duplicate.offsets[0]);
+
+    duplicates
+}
+
+/// Removes the duplicate keys from every JSON object, keeping only one occurrence of each key.
+///
+/// Keys are compared without their quotes, and only within the same object.
+/// The surviving member keeps its full text, including its quote style.
+/// The removed members are removed together with their separating comma.
+///
+/// The JSON string is returned unchanged if it is not structurally valid.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `keep` - Whether to keep the first or the last occurrence of a duplicate key.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, KeepDuplicate};
+///
+/// let json = r#"{"a": 1, b: 2, 'a': 3}"#;
+///
+/// let json_first = json_key_quote_utils::json_deduplicate_keys(json, KeepDuplicate::First);
+/// assert_eq!(json_first, r#"{"a": 1, b: 2}"#);
+///
+/// let json_last = json_key_quote_utils::json_deduplicate_keys(json, KeepDuplicate::Last);
+/// assert_eq!(json_last, r#"{b: 2, 'a': 3}"#);
+/// ```
+pub fn json_deduplicate_keys(json: &str, keep: KeepDuplicate) -> String {
+    let keep_members = |members: &[Member]| -> Vec<bool> {
+        let names: Vec<&str> = members
+            .iter()
+            .map(|(key, _)| key.inner_text(json))
+            .collect();
+        (0..names.len())
+            .map(|i| match keep {
+                KeepDuplicate::First => !names[..i].contains(&names[i]),
+                KeepDuplicate::Last => !names[i + 1..].contains(&names[i]),
+            })
+            .collect()
+    };
+
+    match scanner::parse(json) {
+        Some(node) => {
+            json[..node.start()].to_string()
+                + &render_filtered(json, &node, &keep_members)
+                + &json[node.end()..]
+        }
+        None => json.to_string(),
+    }
+}
+
+/// Removes the members with a `null` value from every JSON object.
+///
+/// The removed members are removed together with their separating comma.
+/// `null` items in arrays are kept, but the members of the objects in arrays are filtered too.
+///
+/// The JSON string is returned unchanged if it is not structurally valid.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_removed = json_key_quote_utils::json_remove_null_values(r#"{"a": null, b: {c: 1, "d": null}, e: [null]}"#);
+/// assert_eq!(json_removed, r#"{b: {c: 1}, e: [null]}"#);
+/// ```
+pub fn json_remove_null_values(json: &str) -> String {
+    let keep_members = |members: &[Member]| -> Vec<bool> {
+        members
+            .iter()
+            .map(|(_, value)| !matches!(value, Node::Scalar(token) if token.text(json) == "null"))
+            .collect()
+    };
+
+    match scanner::parse(json) {
+        Some(node) => {
+            json[..node.start()].to_string()
+                + &render_filtered(json, &node, &keep_members)
+                + &json[node.end()..]
+        }
+        None => json.to_string(),
+    }
+}
+
+/// Replaces every `null` value in the JSON string with the `default` value, which is inserted verbatim.
+///
+/// `null` in string values and keys named `null` are kept.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `default` - The JSON value to insert, like `""` or `0`.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_replaced = json_key_quote_utils::json_replace_null_with_default(r#"{a: null, b: "null", c: [1, null]}"#, "0");
+/// assert_eq!(json_replaced, r#"{a: 0, b: "null", c: [1, 0]}"#);
+/// ```
+pub fn json_replace_null_with_default(json: &str, default: &str) -> String {
+    rewrite_tokens(json, |token| {
+        (token.kind == scanner::TokenKind::Literal && token.text(json) == "null")
+            .then(|| default.to_string())
+    })
+}
+
+/// Renders the JSON value, keeping only the object members for which `keep` returns `true`.
+///
+/// `keep` receives the members of every object and returns whether each of them should be kept.
+/// The original separators in front of the kept members stay in place.
+fn render_filtered(json: &str, node: &Node, keep: &dyn Fn(&[Member]) -> Vec<bool>) -> String {
+    match node {
+        Node::Scalar(token) => token.text(json).to_string(),
+        Node::Array { start, end, items } => {
+            let rendered: Vec<String> = items
+                .iter()
+                .map(|item| render_filtered(json, item, keep))
+                .collect();
+            let spans: Vec<(usize, usize)> = items
+                .iter()
+                .map(|item| (item.start(), item.end()))
+                .collect();
+            fill_slots(json, (*start, *end), &spans, &rendered)
+        }
+        Node::Object {
+            start,
+            end,
+            members,
+        } => {
+            let (first, last) = match (members.first(), members.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => return json[*start..*end].to_string(),
+            };
+
+            let mut filtered = json[*start..first.0.start].to_string();
+            let mut is_first_kept = true;
+            for (i, ((key, value), kept)) in members.iter().zip(keep(members)).enumerate() {
+                if !kept {
+                    continue;
+                }
+                if !is_first_kept {
+                    filtered.push_str(&json[members[i - 1].1.end()..key.start]);
+                }
+                is_first_kept = false;
+                filtered.push_str(&json[key.start..value.start()]);
+                filtered.push_str(&render_filtered(json, value, keep));
+            }
+            let mut after_last = &json[last.1.end()..*end];
+            // Without any kept member, the trailing comma would be left on its own:
+            if is_first_kept {
+                let trimmed = after_last.trim_start_matches(scanner::is_whitespace);
+                after_last = trimmed.strip_prefix(',').unwrap_or(after_last);
+            }
+            filtered.push_str(after_last);
+
+            filtered
+        }
+    }
+}
+
+/// Copies the text of `outer`, with every span in `slots` replaced by the text at the same index in `contents`.
+fn fill_slots(
+    json: &str,
+    outer: (usize, usize),
+    slots: &[(usize, usize)],
+    contents: &[String],
+) -> String {
+    let mut filled = String::new();
+    let mut pos = outer.0;
+    for ((slot_start, slot_end), content) in slots.iter().zip(contents) {
+        filled.push_str(&json[pos..*slot_start]);
+        filled.push_str(content);
+        pos = *slot_end;
+    }
+    filled.push_str(&json[pos..outer.1]);
+
+    filled
+}
+
+/// Replaces the values of all object entries with the key by `new_value`.
+///
+/// The keys are matched without their quotes. `new_value` is inserted literally,
+/// so a string value has to include its quotes.
+/// Object and array values are replaced as a whole, including the entries nested in them.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `key` - The key of the values to replace.
+/// * `new_value` - The new value.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_replaced = json_key_quote_utils::json_replace_value(r#"{"host": "localhost", nested: {host: [1, 2]}}"#, "host", r#""example.com""#);
+/// assert_eq!(json_replaced, r#"{"host": "example.com", nested: {host: "example.com"}}"#);
+/// ```
+pub fn json_replace_value(json: &str, key: &str, new_value: &str) -> String {
+    let tokens = scanner::tokenize(json);
+    let mut slots = Vec::new();
+
+    let mut pos = 0;
+    while pos < tokens.len() {
+        let is_key = matches!(tokens[pos].kind, scanner::TokenKind::Key(_))
+            && tokens[pos].inner_text(json) == key
+            && tokens.get(pos + 1).map(|token| token.kind) == Some(scanner::TokenKind::Colon);
+        match value_end(&tokens, pos + 2).filter(|_| is_key) {
+            Some(end) => {
+                slots.push((tokens[pos + 2].start, tokens[end].end));
+                pos = end + 1;
+            }
+            None => pos += 1,
+        }
+    }
+
+    let contents = vec![new_value.to_string(); slots.len()];
+    fill_slots(json, (0, json.len()), &slots, &contents)
+}
+
+/// Replaces the `NaN`, `Infinity`, `-Infinity` and `+Infinity` literals, which are not valid JSON, using the `policy`.
+///
+/// Only bare values are replaced, the text inside keys and quoted string values is left untouched.
+///
+/// Returns `Err` with the first literal and its byte position when using [NonFinitePolicy::Error].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `policy` - How to replace the literals.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, NonFinitePolicy};
+///
+/// let json = r#"{value: NaN, max: Infinity, note: "NaN"}"#;
+///
+/// let json_null = json_key_quote_utils::json_replace_nonfinite(json, NonFinitePolicy::Null);
+/// assert_eq!(json_null, Ok(r#"{value: null, max: null, note: "NaN"}"#.to_string()));
+///
+/// let json_string = json_key_quote_utils::json_replace_nonfinite(json, NonFinitePolicy::StringLiteral);
+/// assert_eq!(json_string, Ok(r#"{value: "NaN", max: "Infinity", note: "NaN"}"#.to_string()));
+///
+/// let json_error = json_key_quote_utils::json_replace_nonfinite(json, NonFinitePolicy::Error);
+/// assert_eq!(json_error, Err("Non-finite number `NaN` at byte 8".to_string()));
+/// ```
+pub fn json_replace_nonfinite(json: &str, policy: NonFinitePolicy) -> Result<String, String> {
+    let is_nonfinite = |token: &Token| {
+        token.kind == scanner::TokenKind::Literal
+            && matches!(
+                token.text(json),
+                "NaN" | "Infinity" | "-Infinity" | "+Infinity"
+            )
+    };
+
+    if policy == NonFinitePolicy::Error {
+        return match scanner::tokenize(json)
+            .iter()
+            .find(|token| is_nonfinite(token))
+        {
+            Some(token) => Err(format!(
+                "Non-finite number `{}` at byte {}",
+                token.text(json),
+                token.start
+            )),
+            None => Ok(json.to_string()),
+        };
+    }
+
+    Ok(rewrite_tokens(json, |token| {
+        if !is_nonfinite(token) {
+            return None;
+        }
+        match policy {
+            NonFinitePolicy::StringLiteral => {
+                Some(format!("\"{}\"", token.text(json).trim_start_matches('+')))
+            }
+            _ => Some("null".to_string()),
+        }
+    }))
+}
+
+/// Returns the index of the last token of the value starting at the token index `start`.
+///
+/// Returns `None` if there is no value at `start`, or if it is an unclosed object or array.
+fn value_end(tokens: &[Token], start: usize) -> Option<usize> {
+    let token = tokens.get(start)?;
+    match token.kind {
+        scanner::TokenKind::String(_) | scanner::TokenKind::Literal => Some(start),
+        scanner::TokenKind::ObjectStart | scanner::TokenKind::ArrayStart => tokens[start + 1..]
+            .iter()
+            .position(|end| {
+                matches!(
+                    end.kind,
+                    scanner::TokenKind::ObjectEnd | scanner::TokenKind::ArrayEnd
+                ) && end.depth == token.depth
+            })
+            .map(|offset| start + 1 + offset),
+        _ => None,
+    }
+}
+
+/// Renames the JSON keys according to the `map`.
+///
+/// Only keys in key position are renamed, quoted or unquoted, and their quotes are preserved.
+/// The keys are matched literally against the unquoted key text.
+/// Keys which are not in the `map` are left untouched.
+///
+/// Returns the renamed JSON string, together with the new key names which collide
+/// with another key in the same object.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `map` - The old key names mapped to the new key names.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let map = HashMap::from([("user_name".to_string(), "username".to_string())]);
+///
+/// let (json_renamed, collisions) = json_key_quote_utils::json_rename_keys(r#"{"user_name": "user_name"}"#, &map);
+/// assert_eq!(json_renamed, r#"{"username": "user_name"}"#);
+/// assert!(collisions.is_empty());
+///
+/// let (_, collisions) = json_key_quote_utils::json_rename_keys(r#"{user_name: 1, username: 2}"#, &map);
+/// assert_eq!(collisions, vec!["username"]);
+/// ```
+pub fn json_rename_keys(json: &str, map: &HashMap<String, String>) -> (String, Vec<String>) {
+    let mut collisions = Vec::new();
+    // The (name, renamed) pairs of the keys in every object which is currently open.
+    let mut objects: Vec<Vec<(String, bool)>> = Vec::new();
+
+    let renamed_json = rewrite_tokens(json, |token| match token.kind {
+        scanner::TokenKind::ObjectStart => {
+            objects.push(Vec::new());
+            None
+        }
+        scanner::TokenKind::ObjectEnd => {
+            objects.pop();
+            None
+        }
+        scanner::TokenKind::Key(quote) => {
+            let name = token.inner_text(json);
+            let new_name = map.get(name);
+            let final_name = new_name.map_or(name, |new_name| new_name.as_str());
+
+            if let Some(keys) = objects.last_mut() {
+                let collides = keys
+                    .iter()
+                    .any(|(key, renamed)| key == final_name && (*renamed || new_name.is_some()));
+                if collides && !collisions.iter().any(|collision| collision == final_name) {
+                    collisions.push(final_name.to_string());
+                }
+                keys.push((final_name.to_string(), new_name.is_some()));
+            }
+
+            new_name.map(|new_name| quote_key(new_name, quote))
+        }
+        _ => None,
+    });
+
+    (renamed_json, collisions)
+}
+
+/// Merges the overlay JSON object into the base JSON object.
+///
+/// When a key exists in both objects and both values are objects, they are merged recursively.
+/// Otherwise the conflict is resolved using the `strategy`.
+/// Keys are compared without their quotes. The members of the base object come first,
+/// followed by the members which only exist in the overlay object.
+///
+/// Returns `Err` if either JSON string is not a structurally valid object,
+/// or with the first conflicting key when using [MergeStrategy::Error].
+///
+/// # Arguments
+///
+/// * `base` - The base JSON string.
+/// * `overlay` - The JSON string to merge into the base.
+/// * `strategy` - How to resolve keys which exist in both objects.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::json_key_quote_utils::{self, MergeStrategy};
+///
+/// let json_merged = json_key_quote_utils::json_merge(r#"{"a": {"b": 1}, "c": 2}"#, r#"{"a": {"d": 3}, "c": 4}"#, MergeStrategy::TakeRight);
+/// assert_eq!(json_merged, Ok(r#"{"a": {"b": 1, "d": 3}, "c": 4}"#.to_string()));
+///
+/// let json_conflict = json_key_quote_utils::json_merge(r#"{"c": 2}"#, r#"{c: 4}"#, MergeStrategy::Error);
+/// assert_eq!(json_conflict, Err("Conflicting key: c".to_string()));
+/// ```
+pub fn json_merge(base: &str, overlay: &str, strategy: MergeStrategy) -> Result<String, String> {
+    let base_members = match scanner::parse(base) {
+        Some(Node::Object { members, .. }) => members,
+        _ => return Err("The base JSON is not a structurally valid object".to_string()),
+    };
+    let overlay_members = match scanner::parse(overlay) {
+        Some(Node::Object { members, .. }) => members,
+        _ => return Err("The overlay JSON is not a structurally valid object".to_string()),
+    };
+
+    merge_members(base, &base_members, overlay, &overlay_members, strategy)
+}
+
+fn merge_members(
+    base: &str,
+    base_members: &[Member],
+    overlay: &str,
+    overlay_members: &[Member],
+    strategy: MergeStrategy,
+) -> Result<String, String> {
+    let mut merged = Vec::new();
+
+    for (key, value) in base_members {
+        let name = key.inner_text(base);
+        let overlay_member = overlay_members
+            .iter()
+            .find(|(overlay_key, _)| overlay_key.inner_text(overlay) == name);
+
+        let merged_value = match overlay_member {
+            None => value.text(base).to_string(),
+            Some((_, overlay_value)) => match (value, overlay_value) {
+                (
+                    Node::Object { members, .. },
+                    Node::Object {
+                        members: nested_overlay_members,
+                        ..
+                    },
+                ) => merge_members(base, members, overlay, nested_overlay_members, strategy)?,
+                _ => match strategy {
+                    MergeStrategy::TakeLeft => value.text(base).to_string(),
+                    MergeStrategy::TakeRight => overlay_value.text(overlay).to_string(),
+                    MergeStrategy::Error => return Err(format!("Conflicting key: {}", name)),
+                },
+            },
+        };
+        merged.push(key.text(base).to_string() + ": " + &merged_value);
+    }
+
+    for (key, value) in overlay_members {
+        let name = key.inner_text(overlay);
+        if !base_members
+            .iter()
+            .any(|(base_key, _)| base_key.inner_text(base) == name)
+        {
+            merged.push(key.text(overlay).to_string() + ": " + value.text(overlay));
+        }
+    }
+
+    Ok("{".to_string() + &merged.join(", ") + "}")
+}
+
+/// Checks whether the braces and brackets in the JSON string are balanced.
+///
+/// Braces and brackets inside quoted strings are ignored.
+/// This does not check whether the JSON string is otherwise valid.
+///
+/// Returns `Err` describing the byte position of the first mismatch.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// assert_eq!(json_key_quote_utils::json_validate_structure(r#"{key: "val]"}"#), Ok(()));
+/// assert_eq!(
+///     json_key_quote_utils::json_validate_structure(r#"{key: [1}"#),
+///     Err("Mismatched `}` at byte 8, expected `]`".to_string())
+/// );
+/// ```
+pub fn json_validate_structure(json: &str) -> Result<(), String> {
+    let mut open: Vec<(char, usize)> = Vec::new();
+
+    for token in scanner::tokenize(json) {
+        match token.kind {
+            scanner::TokenKind::ObjectStart => open.push(('}', token.start)),
+            scanner::TokenKind::ArrayStart => open.push((']', token.start)),
+            scanner::TokenKind::ObjectEnd | scanner::TokenKind::ArrayEnd => {
+                let close = token.text(json);
+                match open.pop() {
+                    Some((expected, _)) if close.starts_with(expected) => (),
+                    Some((expected, _)) => {
+                        return Err(format!(
+                            "Mismatched `{}` at byte {}, expected `{}`",
+                            close, token.start, expected
+                        ))
+                    }
+                    None => return Err(format!("Unexpected `{}` at byte {}", close, token.start)),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    match open.pop() {
+        Some((expected, position)) => Err(format!(
+            "Unclosed `{}` at byte {}",
+            if expected == '}' { '{' } else { '[' },
+            position
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Flattens nested JSON objects to a single-level object with delimited keys.
+///
+/// The keys of nested objects are joined with their parent keys using the `separator`.
+/// Arrays and empty objects are kept intact as values.
+/// A flattened key uses the quote style of its outermost key.
+///
+/// Unlike the other conversions, the object is rendered again instead of being edited in place:
+/// the members are put on a single line as `{key: value, ...}`, separated by `, `.
+/// The whitespace and newlines between the members are not kept, the values are kept as they are.
+///
+/// The JSON string is returned unchanged if it is not a structurally valid object.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `separator` - The separator to put between the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_flattened = json_key_quote_utils::json_flatten_keys(r#"{"a": {"b": {"c": 1}}}"#, ".");
+/// assert_eq!(json_flattened, r#"{"a.b.c": 1}"#);
+/// ```
+pub fn json_flatten_keys(json: &str, separator: &str) -> String {
+    let members = match scanner::parse(json) {
+        Some(Node::Object { members, .. }) => members,
+        _ => return json.to_string(),
+    };
+
+    let mut flattened = Vec::new();
+    flatten_members(json, &members, None, separator, &mut flattened);
+
+    "{".to_string() + &flattened.join(", ") + "}"
+}
+
+/// Reconstructs the nested JSON objects from a single-level object with delimited keys.
+///
+/// This is the inverse of [json_flatten_keys]. When a key is both used as a value
+/// and as the parent of another key, the member which comes last wins.
+/// Like [json_flatten_keys], the objects are rendered on a single line, without the original whitespace.
+/// A nested key gets the key-quotes of the first flattened key it is part of.
+///
+/// The JSON string is returned unchanged if it is not a structurally valid object
+/// or if the `separator` is empty.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `separator` - The separator between the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_unflattened = json_key_quote_utils::json_unflatten_keys(r#"{"a.b.c": 1}"#, ".");
+/// assert_eq!(json_unflattened, r#"{"a": {"b": {"c": 1}}}"#);
+/// ```
+pub fn json_unflatten_keys(json: &str, separator: &str) -> String {
+    let members = match scanner::parse(json) {
+        Some(Node::Object { members, .. }) if !separator.is_empty() => members,
+        _ => return json.to_string(),
+    };
+
+    let mut root: Vec<UnflattenedMember> = Vec::new();
+    for (key, value) in &members {
+        let quote = key_quote(key);
+        let mut segments: Vec<&str> = key.inner_text(json).split(separator).collect();
+        let last = segments.pop().unwrap_or_default();
+
+        let mut current = &mut root;
+        for segment in segments {
+            let index = match current.iter().position(|member| member.0 == segment) {
+                Some(index) => index,
+                None => {
+                    current.push((segment, quote, Unflattened::Object(Vec::new())));
+                    current.len() - 1
+                }
+            };
+            if let Unflattened::Value(_) = current[index].2 {
+                current[index].2 = Unflattened::Object(Vec::new());
+            }
+            current = match &mut current[index].2 {
+                Unflattened::Object(members) => members,
+                Unflattened::Value(_) => unreachable!(),
+            };
+        }
+
+        let unflattened_value = Unflattened::Value(value.text(json));
+        match current.iter_mut().find(|member| member.0 == last) {
+            Some(member) => member.2 = unflattened_value,
+            None => current.push((last, quote, unflattened_value)),
+        }
+    }
+
+    render_unflattened(&root)
+}
+
+/// Whether the character is a control character in the range U+0000–U+001F.
+fn is_ctrlchar(c: char) -> bool {
+    c < '\u{20}'
+}
+
+/// Returns the escaped variant of the control character, or `None` if it is not a control character.
+///
+/// The short escaped variants of [CTRLCHAR_ESCAPES] are used where possible, `\uXXXX` otherwise.
+pub(crate) fn escape_ctrlchar(c: char) -> Option<String> {
+    if !is_ctrlchar(c) {
+        return None;
+    }
+
+    let escaped = CTRLCHAR_ESCAPES
+        .iter()
+        .find(|(ctrlchar, _)| ctrlchar.starts_with(c))
+        .map_or_else(
+            || format!("\\u{:04X}", c as u32),
+            |(_, escaped)| escaped.to_string(),
+        );
+
+    Some(escaped)
+}
+
+/// The result of [match_escaped_ctrlchar].
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum EscapedCtrlchar {
+    /// The escape sequence is an escaped control character.
+    Complete(char),
+    /// The escape sequence is the start of an escaped control character.
+    Incomplete,
+    /// The escape sequence is not an escaped control character.
+    Mismatch,
+}
+
+/// Matches the escape sequence, starting with its backslash, against the escaped control characters.
+///
+/// Both the short escaped variants of [CTRLCHAR_ESCAPES] and `\u0000` through `\u001F`,
+/// in upper- or lowercase, are matched.
+pub(crate) fn match_escaped_ctrlchar(escape: &[u8]) -> EscapedCtrlchar {
+    if let Some((ctrlchar, _)) = CTRLCHAR_ESCAPES
+        .iter()
+        .find(|(_, escaped)| escaped.as_bytes() == escape)
+    {
+        return EscapedCtrlchar::Complete(ctrlchar.chars().next().unwrap());
+    }
+
+    let pattern: [fn(u8) -> bool; 6] = [
+        |b| b == b'\\',
+        |b| b == b'u',
+        |b| b == b'0',
+        |b| b == b'0',
+        |b| b == b'0' || b == b'1',
+        |b| b.is_ascii_hexdigit(),
+    ];
+    if escape.len() > pattern.len() || !escape.iter().zip(pattern).all(|(&b, matches)| matches(b)) {
+        return EscapedCtrlchar::Mismatch;
+    }
+    if escape.len() < pattern.len() {
+        return EscapedCtrlchar::Incomplete;
+    }
+
+    let code = u32::from_str_radix(std::str::from_utf8(&escape[2..]).unwrap(), 16).unwrap();
+    EscapedCtrlchar::Complete(char::from_u32(code).unwrap())
+}
+
+/// Returns the length of the escaped control character at the start of the text, if any.
+fn escaped_ctrlchar_len(text: &str) -> Option<(char, usize)> {
+    let bytes = text.as_bytes();
+    (2..=bytes.len().min(6)).find_map(|len| match match_escaped_ctrlchar(&bytes[..len]) {
+        EscapedCtrlchar::Complete(c) => Some((c, len)),
+        _ => None,
+    })
+}
+
+/// Escapes the control characters in a JSON string value.
+fn escape_ctrlchars(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match escape_ctrlchar(c) {
+            Some(escaped_ctrlchar) => escaped.push_str(&escaped_ctrlchar),
+            None => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Calls `f` with every part of the text, which is either an escaped control character or not.
+///
+/// Other escape sequences, like an escaped backslash, are never split.
+fn for_each_escaped_ctrlchar(text: &str, mut f: impl FnMut(&str, Option<char>)) {
+    let mut rest = text;
+    while let Some(i) = rest.find('\\') {
+        f(&rest[..i], None);
+        rest = &rest[i..];
+        match escaped_ctrlchar_len(rest) {
+            Some((c, len)) => {
+                f(&rest[..len], Some(c));
+                rest = &rest[len..];
+            }
+            None => {
+                let len = rest[1..].chars().next().map_or(1, |c| 1 + c.len_utf8());
+                f(&rest[..len], None);
+                rest = &rest[len..];
+            }
+        }
+    }
+    f(rest, None);
+}
+
+/// Unescapes the escaped control characters in a JSON string value.
+fn unescape_ctrlchars(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    for_each_escaped_ctrlchar(value, |part, ctrlchar| match ctrlchar {
+        Some(c) => unescaped.push(c),
+        None => unescaped.push_str(part),
+    });
+
+    unescaped
+}
+
+/// Removes the control characters from a JSON key.
+fn remove_ctrlchars(key: &str) -> String {
+    key.chars().filter(|&c| !is_ctrlchar(c)).collect()
+}
+
+/// Removes the escaped control characters from a JSON key.
+fn remove_escaped_ctrlchars(key: &str) -> String {
+    let mut removed = String::with_capacity(key.len());
+    for_each_escaped_ctrlchar(key, |part, ctrlchar| {
+        if ctrlchar.is_none() {
+            removed.push_str(part);
+        }
+    });
+
+    removed
+}
+
+/// Adds a sibling key `<key>_type` after every member, with the type of its value as a string value.
+///
+/// The types are `"string"`, `"number"`, `"boolean"`, `"null"`, `"object"` and `"array"`.
+/// The members of nested objects, also inside arrays, are annotated too.
+/// Members which already have their annotation are skipped, so annotating twice changes nothing.
+///
+/// The JSON string is returned unchanged if it is not a structurally valid value.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to put around the annotation keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_annotated = json_key_quote_utils::json_add_type_annotations(r#"{"a": 1, "b": [true]}"#, Quotes::DoubleQuote);
+/// assert_eq!(json_annotated, r#"{"a": 1, "a_type": "number", "b": [true], "b_type": "array"}"#);
+/// ```
+pub fn json_add_type_annotations(json: &str, quote_type: Quotes) -> String {
+    let root = match scanner::parse(json) {
+        Some(root) => root,
+        None => return json.to_string(),
+    };
+
+    let mut annotations = Vec::new();
+    collect_type_annotations(json, &root, quote_type, &mut annotations);
+
+    let mut annotated = String::with_capacity(json.len());
+    let mut pos = 0;
+    for (offset, annotation) in annotations {
+        annotated.push_str(&json[pos..offset]);
+        annotated.push_str(&annotation);
+        pos = offset;
+    }
+    annotated.push_str(&json[pos..]);
+
+    annotated
+}
+
+/// Collects the `(byte offset, annotation)` pairs of [json_add_type_annotations], in document order.
+fn collect_type_annotations(
+    json: &str,
+    node: &Node,
+    quote_type: Quotes,
+    annotations: &mut Vec<(usize, String)>,
+) {
+    match node {
+        Node::Object { members, .. } => {
+            let names: Vec<&str> = members
+                .iter()
+                .map(|(key, _)| key.inner_text(json))
+                .collect();
+            for (key, value) in members {
+                collect_type_annotations(json, value, quote_type, annotations);
+
+                let name = key.inner_text(json);
+                let is_annotation = name
+                    .strip_suffix("_type")
+                    .is_some_and(|annotated| names.contains(&annotated));
+                let annotation_name = name.to_string() + "_type";
+                if !is_annotation && !names.contains(&annotation_name.as_str()) {
+                    let annotation_key = match quote_type.as_char() {
+                        Some(quote) => {
+                            quote_key(&escape_quote(&annotation_name, quote), Some(quote))
+                        }
+                        None => annotation_name,
+                    };
+                    annotations.push((
+                        value.end(),
+                        format!(", {}: \"{}\"", annotation_key, value_type(json, value)),
+                    ));
+                }
+            }
+        }
+        Node::Array { items, .. } => {
+            for item in items {
+                collect_type_annotations(json, item, quote_type, annotations);
+            }
+        }
+        Node::Scalar(_) => (),
+    }
+}
+
+/// Returns the JSON type of the value, as used by [json_add_type_annotations].
+fn value_type(json: &str, value: &Node) -> &'static str {
+    match value {
+        Node::Object { .. } => "object",
+        Node::Array { .. } => "array",
+        Node::Scalar(token) => match (token.kind, token.text(json)) {
+            (scanner::TokenKind::String(_), _) => "string",
+            (_, "true" | "false") => "boolean",
+            (_, "null") => "null",
+            _ => "number",
+        },
+    }
+}
+
+/// Removes all insignificant whitespace from the JSON string.
+///
+/// Whitespace inside single- and double-quoted strings is preserved exactly,
+/// as well as the whitespace inside unquoted keys.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_minified = json_key_quote_utils::json_minify("{\n\t\"key\": \"a value\",\n\tother: [1, 2]\n}");
+/// assert_eq!(json_minified, r#"{"key":"a value",other:[1,2]}"#);
+/// ```
+pub fn json_minify(json: &str) -> String {
+    // Empty and whitespace-only input has nothing to minify:
+    if json.trim().is_empty() {
+        return json.to_string();
+    }
+    let mut minified = String::with_capacity(json.len());
+    let mut previous: Option<Token> = None;
+
+    for token in scanner::tokenize(json) {
+        // Keep adjacent bare words apart, so they don't merge into one.
+        if let Some(previous) = previous {
+            if is_bare(&previous) && is_bare(&token) {
+                minified.push(' ');
+            }
+        }
+        minified.push_str(token.text(json));
+        previous = Some(token);
+    }
+
+    minified
+}
+
+/// Removes the `//` line comments and `/* */` block comments from the JSON string.
+///
+/// Comments inside quoted strings are not removed. The line break ending a line comment is kept.
+/// Note that `//` and `/*` outside of quoted strings always start a comment, even in unquoted keys.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_stripped = json_key_quote_utils::json_strip_comments("{key: \"a // b\", // comment\n/* other */ other: 1}");
+/// assert_eq!(json_stripped, "{key: \"a // b\", \n other: 1}");
+/// ```
+pub fn json_strip_comments(json: &str) -> String {
+    let mut stripped = String::with_capacity(json.len());
+    let mut pos = 0;
+
+    for comment in comment_ranges(json) {
+        stripped.push_str(&json[pos..comment.start]);
+        pos = comment.end;
+    }
+    stripped.push_str(&json[pos..]);
+
+    stripped
+}
+
+/// Returns the byte ranges of the `//` and `/* */` comments outside of the strings.
+///
+/// Line comments end before the line break, an unterminated block comment runs until the end of the input.
+fn comment_ranges(json: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut chars = json.char_indices().peekable();
+    let mut quote: Option<char> = None;
+    let mut escaped = false;
+
+    while let Some((i, c)) = chars.next() {
+        if let Some(current_quote) = quote {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == current_quote {
+                quote = None;
+            }
+            continue;
+        }
+
+        match (c, chars.peek().map(|&(_, next)| next)) {
+            ('"' | '\'' | '`', _) => quote = Some(c),
+            ('/', Some('/')) => {
+                while chars
+                    .next_if(|&(_, next)| next != '\n' && next != '\r')
+                    .is_some()
+                {}
+                ranges.push(i..chars.peek().map_or(json.len(), |&(end, _)| end));
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = None;
+                let mut end = json.len();
+                for (j, next) in chars.by_ref() {
+                    if previous == Some('*') && next == '/' {
+                        end = j + 1;
+                        break;
+                    }
+                    previous = Some(next);
+                }
+                ranges.push(i..end);
+            }
+            _ => (),
+        }
+    }
+
+    ranges
+}
+
+/// Splits a string of concatenated JSON documents, like `{a: 1}\n{b: 2}`, into the documents.
+///
+/// Every top-level value is a document. The whitespace and comments in between the documents
+/// are not part of the returned slices, see [json_convert_each] to convert the documents while keeping them.
+///
+/// # Arguments
+///
+/// * `json` - The string of JSON documents.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let documents = json_key_quote_utils::json_split_documents("{a: 1}\n// second\n{b: [2]} \"c\"");
+/// assert_eq!(documents, vec!["{a: 1}", "{b: [2]}", "\"c\""]);
+/// ```
+pub fn json_split_documents(json: &str) -> Vec<&str> {
+    document_ranges(json)
+        .into_iter()
+        .map(|range| &json[range])
+        .collect()
+}
+
+/// Returns the byte ranges of the top-level values, ignoring the comments in between them.
+fn document_ranges(json: &str) -> Vec<Range<usize>> {
+    // Blanking the comments keeps the byte offsets of the tokens intact.
+    let mut uncommented = json.as_bytes().to_vec();
+    for comment in comment_ranges(json) {
+        uncommented[comment].fill(b' ');
+    }
+    let uncommented = String::from_utf8(uncommented).unwrap_or_default();
+
+    let mut ranges = Vec::new();
+    let mut document_start = None;
+    for token in scanner::tokenize(&uncommented) {
+        if token.depth > 0 {
+            continue;
+        }
+        match token.kind {
+            scanner::TokenKind::ObjectStart | scanner::TokenKind::ArrayStart => {
+                document_start = Some(token.start)
+            }
+            scanner::TokenKind::ObjectEnd | scanner::TokenKind::ArrayEnd => {
+                ranges.push(document_start.take().unwrap_or(token.start)..token.end);
+            }
+            _ if document_start.is_none() => ranges.push(token.start..token.end),
+            _ => (),
+        }
+    }
+    if let Some(start) = document_start {
+        ranges.push(start..uncommented.trim_end_matches(scanner::is_whitespace).len());
+    }
+
+    ranges
+}
+
+/// Converts each of the concatenated JSON documents, keeping the whitespace and comments in between them.
+///
+/// # Arguments
+///
+/// * `json` - The string of JSON documents.
+/// * `f` - The conversion of a single document, returning the converted document.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_converted = json_key_quote_utils::json_convert_each("{a: 1}\n// second\n{b: 2}", |document| {
+///     json_key_quote_utils::json_add_key_quotes(document, Quotes::DoubleQuote)
+/// });
+/// assert_eq!(json_converted, "{\"a\": 1}\n// second\n{\"b\": 2}");
+/// ```
+pub fn json_convert_each(json: &str, mut f: impl FnMut(&str) -> String) -> String {
+    let mut converted = String::with_capacity(json.len());
+    let mut pos = 0;
+
+    for document in document_ranges(json) {
+        converted.push_str(&json[pos..document.start]);
+        pos = document.end;
+        converted.push_str(&f(&json[document]));
+    }
+    converted.push_str(&json[pos..]);
+
+    converted
+}
+
+/// Wraps the JSON value in an object with a single key.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `key` - The key of the wrapping object.
+/// * `quote_type` - Whether the key should be single- or double-quoted.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_wrapped = json_key_quote_utils::json_wrap_in_object("[1, 2]", "items", Quotes::DoubleQuote);
+/// assert_eq!(json_wrapped, r#"{"items": [1, 2]}"#);
+/// ```
+pub fn json_wrap_in_object(json: &str, key: &str, quote_type: Quotes) -> String {
+    let quoted_key = match quote_type.as_char() {
+        Some(quote) => quote_key(&escape_quote(key, quote), Some(quote)),
+        None => key.to_string(),
+    };
+
+    format!(
+        "{{{}: {}}}",
+        quoted_key,
+        json.trim_matches(scanner::is_whitespace)
+    )
+}
+
+/// Extracts the value of the key from an object with only that key.
+///
+/// Returns `None` if the JSON string is not a structurally valid object with `key` as its single key.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `key` - The key of the wrapping object.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_unwrapped = json_key_quote_utils::json_unwrap_object(r#"{items: [1, 2]}"#, "items");
+/// assert_eq!(json_unwrapped, Some("[1, 2]".to_string()));
+///
+/// let json_not_unwrapped = json_key_quote_utils::json_unwrap_object(r#"{items: [1, 2], other: 3}"#, "items");
+/// assert_eq!(json_not_unwrapped, None);
+/// ```
+pub fn json_unwrap_object(json: &str, key: &str) -> Option<String> {
+    match scanner::parse(json)? {
+        Node::Object { members, .. } => match members.as_slice() {
+            [(member_key, value)] if member_key.inner_text(json) == key => {
+                Some(value.text(json).to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Converts an array of key-value pairs, like `[["a", 1], ["b", 2]]`, into an object, like `{"a": 1, "b": 2}`.
+///
+/// The first element of every pair becomes the key, quoted with the `quote_type`.
+/// String keys are re-quoted, other scalars like numbers are used as they are.
+/// The second element is kept as it is, including nested objects and arrays.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote_type` - The quotes to put around the keys.
+///
+/// # Errors
+///
+/// Returns an error if the JSON string is not a structurally valid array
+/// or if any of its items is not an array of two elements with a scalar first element.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json_object = json_key_quote_utils::json_array_to_object(r#"[["a", 1], ['b', {c: 2}], [3, "d"]]"#, Quotes::DoubleQuote);
+/// assert_eq!(json_object, Ok(r#"{"a": 1, "b": {c: 2}, "3": "d"}"#.to_string()));
+///
+/// let json_invalid = json_key_quote_utils::json_array_to_object(r#"[["a", 1, 2]]"#, Quotes::DoubleQuote);
+/// assert_eq!(json_invalid, Err("Item 0 is not an array of two elements".to_string()));
+/// ```
+pub fn json_array_to_object(json: &str, quote_type: Quotes) -> Result<String, String> {
+    let items = match scanner::parse(json) {
+        Some(Node::Array { items, .. }) => items,
+        _ => return Err("The JSON is not a structurally valid array".to_string()),
+    };
+    let quote = quote_type.as_char();
+
+    let mut members = Vec::with_capacity(items.len());
+    for (i, item) in items.iter().enumerate() {
+        let (key, value) = match item {
+            Node::Array { items: pair, .. } if pair.len() == 2 => (&pair[0], &pair[1]),
+            _ => return Err(format!("Item {} is not an array of two elements", i)),
+        };
+        let name = match key {
+            Node::Scalar(token) => match token.kind {
+                scanner::TokenKind::String(_) => {
+                    escape_string(&unescape_string(token.inner_text(json)), quote)
+                }
+                _ => token.text(json).to_string(),
+            },
+            _ => return Err(format!("The key of item {} is not a scalar", i)),
+        };
+        members.push(quote_key(&name, quote) + ": " + value.text(json));
+    }
+
+    Ok("{".to_string() + &members.join(", ") + "}")
+}
+
+/// Converts an object, like `{"a": 1, b: 2}`, into an array of key-value pairs, like `[["a", 1], ["b", 2]]`.
+///
+/// This is the inverse of [json_array_to_object]. The keys become double-quoted string values,
+/// whichever key-quotes they had. The values are kept as they are, including nested objects and arrays.
+///
+/// The JSON string is returned unchanged if it is not a structurally valid object.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json_array = json_key_quote_utils::json_object_to_array(r#"{"a": 1, b: {c: 2}, 'd': "e"}"#);
+/// assert_eq!(json_array, r#"[["a", 1], ["b", {c: 2}], ["d", "e"]]"#);
+/// ```
+pub fn json_object_to_array(json: &str) -> String {
+    let members = match scanner::parse(json) {
+        Some(Node::Object { members, .. }) => members,
+        _ => return json.to_string(),
+    };
+
+    let pairs: Vec<String> = members
+        .iter()
+        .map(|(key, value)| {
+            let name = escape_string(&unescape_string(key.inner_text(json)), Some('"'));
+            format!("[\"{}\", {}]", name, value.text(json))
+        })
+        .collect();
+
+    "[".to_string() + &pairs.join(", ") + "]"
+}
+
+/// Counts the object keys in the JSON string, at all nesting depths.
+///
+/// Keys of objects inside arrays are counted too.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let count = json_key_quote_utils::json_count_keys(r#"{"a": {"b": 1}, c: [{"d": 2}]}"#);
+/// assert_eq!(count, 4);
+/// ```
+pub fn json_count_keys(json: &str) -> usize {
+    scanner::tokenize(json)
+        .iter()
+        .filter(|token| matches!(token.kind, scanner::TokenKind::Key(_)))
+        .count()
+}
+
+/// Counts the object keys in the JSON string at a specific nesting depth.
+///
+/// The keys of the top-level object are at depth `1`,
+/// every enclosing object or array adds one to the depth.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `depth` - The nesting depth of the keys to count.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json = r#"{"a": {"b": 1, "c": 2}, d: [{"e": 3}]}"#;
+/// assert_eq!(json_key_quote_utils::json_count_keys_at_depth(json, 1), 2);
+/// assert_eq!(json_key_quote_utils::json_count_keys_at_depth(json, 2), 2);
+/// assert_eq!(json_key_quote_utils::json_count_keys_at_depth(json, 3), 1);
+/// ```
+pub fn json_count_keys_at_depth(json: &str, depth: usize) -> usize {
+    scanner::tokenize(json)
+        .iter()
+        .filter(|token| matches!(token.kind, scanner::TokenKind::Key(_)) && token.depth == depth)
+        .count()
+}
+
+/// Returns the maximum nesting depth of the objects and arrays in the JSON string.
+///
+/// A flat object or array has depth `1`, every enclosing object or array adds one to the depth.
+/// Brackets in string values and keys are not counted. A bare scalar has depth `0`.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// assert_eq!(json_key_quote_utils::json_count_nesting_depth(r#"{"key": "val"}"#), 1);
+/// assert_eq!(json_key_quote_utils::json_count_nesting_depth(r#"{"a": [{"b": "{["}]}"#), 3);
+/// ```
+pub fn json_count_nesting_depth(json: &str) -> usize {
+    scanner::tokenize(json)
+        .iter()
+        .filter(|token| {
+            matches!(
+                token.kind,
+                scanner::TokenKind::ObjectStart | scanner::TokenKind::ArrayStart
+            )
+        })
+        .map(|token| token.depth + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Collects all single- and double-quoted string values in the JSON string, in document order.
+///
+/// The surrounding quotes are stripped, the escape sequences are kept as they are.
+/// The string values inside arrays are included, the keys are not.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let values = json_key_quote_utils::json_extract_values(r#"{"a": "b", c: ['d', 1], "e": {f: "g"}}"#);
+/// assert_eq!(values, vec!["b", "d", "g"]);
+/// ```
+pub fn json_extract_values(json: &str) -> Vec<String> {
+    scanner::tokenize(json)
+        .iter()
+        .filter(|token| matches!(token.kind, scanner::TokenKind::String(_)))
+        .map(|token| token.inner_text(json).to_string())
+        .collect()
+}
+
+/// Collects the string values of the members with the key, at all nesting depths, in document order.
+///
+/// The key is compared without its quotes. Only the string values directly following the key are collected,
+/// the string values inside an array or object value of the key are not.
+/// See [json_extract_values].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `key` - The key of the string values to collect, without quotes.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let json = r#"{"name": "a", other: "b", "nested": {'name': 'c', name2: "d"}}"#;
+/// let values = json_key_quote_utils::json_extract_values_for_key(json, "name");
+/// assert_eq!(values, vec!["a", "c"]);
+/// ```
+pub fn json_extract_values_for_key(json: &str, key: &str) -> Vec<String> {
+    scanner::tokenize(json)
+        .windows(3)
+        .filter_map(|window| match window {
+            [key_token, colon, value]
+                if matches!(key_token.kind, scanner::TokenKind::Key(_))
+                    && key_token.inner_text(json) == key
+                    && colon.kind == scanner::TokenKind::Colon
+                    && matches!(value.kind, scanner::TokenKind::String(_)) =>
+            {
+                Some(value.inner_text(json).to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Serializes a flat JSON object to a URL query string, like `foo=bar&baz=42`.
+///
+/// The escape sequences in the keys and string values are decoded, after which both are percent-encoded.
+/// Numbers and booleans are written as they are, `null` values are written as an empty value.
+///
+/// Returns `Err` if the JSON string is not a structurally valid object, or if a value is an object or array.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let query = json_key_quote_utils::json_to_query_string(r#"{foo: "bar baz", "n": 42, 'ok': true, none: null}"#);
+/// assert_eq!(query, Ok("foo=bar%20baz&n=42&ok=true&none=".to_string()));
+///
+/// let nested = json_key_quote_utils::json_to_query_string(r#"{"a": {"b": 1}}"#);
+/// assert_eq!(nested, Err("Nested value for key: a".to_string()));
+/// ```
+pub fn json_to_query_string(json: &str) -> Result<String, String> {
+    let members = match scanner::parse(json) {
+        Some(Node::Object { members, .. }) => members,
+        _ => return Err("The JSON is not a structurally valid object".to_string()),
+    };
+
+    let mut pairs = Vec::with_capacity(members.len());
+    for (key, value) in &members {
+        let name = unescape_string(key.inner_text(json));
+        let value = match value {
+            Node::Scalar(token) if token.text(json) == "null" => String::new(),
+            Node::Scalar(token) => match token.kind {
+                scanner::TokenKind::String(_) => unescape_string(token.inner_text(json)),
+                _ => token.text(json).to_string(),
+            },
+            _ => return Err(format!("Nested value for key: {}", name)),
+        };
+        pairs.push(percent_encode(&name) + "=" + &percent_encode(&value));
+    }
+
+    Ok(pairs.join("&"))
+}
+
+/// Parses a URL query string, like `foo=bar&baz`, into a flat JSON object.
+///
+/// This is the inverse of [json_to_query_string]. The keys and values are percent-decoded, with `+` as a space.
+/// The values become double-quoted string values, a key without a `=` gets the value `true`.
+/// A leading `?` and empty pairs are skipped.
+///
+/// # Arguments
+///
+/// * `qs` - The query string.
+/// * `quote_type` - The quotes to put around the keys.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils, Quotes};
+///
+/// let json = json_key_quote_utils::json_from_query_string("?foo=bar%20baz&n=42&flag", Quotes::DoubleQuote);
+/// assert_eq!(json, r#"{"foo": "bar baz", "n": "42", "flag": true}"#);
+/// ```
+pub fn json_from_query_string(qs: &str, quote_type: Quotes) -> String {
+    let qs = qs.strip_prefix('?').unwrap_or(qs);
+    let quote = quote_type.as_char();
+
+    let members: Vec<String> = qs
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = match pair.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (pair, None),
+            };
+            let name = escape_string(&percent_decode(name), quote);
+            let value = match value {
+                Some(value) => {
+                    "\"".to_string() + &escape_string(&percent_decode(value), Some('"')) + "\""
+                }
+                None => "true".to_string(),
+            };
+            quote_key(&name, quote) + ": " + &value
+        })
+        .collect();
+
+    "{".to_string() + &members.join(", ") + "}"
+}
+
+/// Decodes the escape sequences in the text of a key or string value, without its quotes.
+///
+/// Invalid escape sequences are kept as they are.
+fn unescape_string(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('b') => unescaped.push('\u{8}'),
+            Some('f') => unescaped.push('\u{c}'),
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some('u') => {
+                let hex: String = chars.clone().take(4).collect();
+                match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    Some(decoded) if hex.len() == 4 => {
+                        unescaped.push(decoded);
+                        chars.nth(3);
+                    }
+                    _ => unescaped.push_str("\\u"),
+                }
+            }
+            Some(escaped) => unescaped.push(escaped),
+            None => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+/// Escapes the backslashes, the `quote` character and the ctrl-characters in the text of a key or string value.
+///
+/// This is the inverse of [unescape_string].
+fn escape_string(text: &str, quote: Option<char>) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        if c == '\\' || Some(c) == quote {
+            escaped.push('\\');
+            escaped.push(c);
+        } else if let Some(escaped_ctrlchar) = escape_ctrlchar(c) {
+            escaped.push_str(&escaped_ctrlchar);
+        } else {
+            escaped.push(c);
+        }
+    }
+
+    escaped
+}
+
+/// Decodes the percent-encoded bytes in the text, with `+` as a space.
+///
+/// Invalid percent-encodings are kept as they are, invalid UTF-8 is replaced.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(b)) => {
+                decoded.push(b);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (b, _) => {
+                decoded.push(b);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Percent-encodes all characters except for the unreserved characters of RFC 3986.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+
+    for b in text.bytes() {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", b));
+        }
+    }
+
+    encoded
+}
+
+/// A value of the object which is being reconstructed by [json_unflatten_keys].
+enum Unflattened<'a> {
+    Value(&'a str),
+    Object(Vec<UnflattenedMember<'a>>),
+}
+
+type UnflattenedMember<'a> = (&'a str, Option<char>, Unflattened<'a>);
+
+fn render_unflattened(members: &[UnflattenedMember]) -> String {
+    let rendered: Vec<String> = members
+        .iter()
+        .map(|(key, quote, value)| {
+            let value = match value {
+                Unflattened::Value(value) => value.to_string(),
+                Unflattened::Object(members) => render_unflattened(members),
+            };
+            quote_key(key, *quote) + ": " + &value
+        })
+        .collect();
+
+    "{".to_string() + &rendered.join(", ") + "}"
+}
+
+fn flatten_members(
+    json: &str,
+    members: &[Member],
+    prefix: Option<(&str, Option<char>)>,
+    separator: &str,
+    flattened: &mut Vec<String>,
+) {
+    for (key, value) in members {
+        let (name, quote) = match prefix {
+            Some((prefix, quote)) => (prefix.to_string() + separator + key.inner_text(json), quote),
+            None => (key.inner_text(json).to_string(), key_quote(key)),
+        };
+
+        match value {
+            Node::Object { members, .. } if !members.is_empty() => {
+                flatten_members(json, members, Some((&name, quote)), separator, flattened);
+            }
+            _ => flattened.push(quote_key(&name, quote) + ": " + value.text(json)),
+        }
+    }
+}
+
+/// Computes the line-based unified diff between the two strings, without context lines.
+///
+/// Every hunk starts with a `@@ -start,count +start,count @@` header,
+/// followed by the removed lines prefixed with `-` and the added lines prefixed with `+`.
+/// Returns an empty string if the lines are equal.
+///
+/// # Arguments
+///
+/// * `before` - The original string.
+/// * `after` - The modified string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{json_key_quote_utils};
+///
+/// let diff = json_key_quote_utils::diff_lines("{\n  a: 1,\n  b: 2\n}", "{\n  \"a\": 1,\n  b: 2\n}");
+/// assert_eq!(diff, "@@ -2,1 +2,1 @@\n-  a: 1,\n+  \"a\": 1,\n");
+/// ```
+pub fn diff_lines(before: &str, after: &str) -> String {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+
+    let prefix = before
+        .iter()
+        .zip(&after)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = before[prefix..]
+        .iter()
+        .rev()
+        .zip(after[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old = &before[prefix..before.len() - suffix];
+    let new = &after[prefix..after.len() - suffix];
+
+    // The length of the longest common subsequence of old[i..] and new[j..].
+    let width = new.len() + 1;
+    let mut lcs = vec![0usize; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i * width + j] = if old[i] == new[j] {
+                lcs[(i + 1) * width + j + 1] + 1
+            } else {
+                lcs[(i + 1) * width + j].max(lcs[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let (hunk_i, hunk_j) = (i, j);
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && old[i] == new[j] {
+                break;
+            }
+            if j == new.len()
+                || (i < old.len() && lcs[(i + 1) * width + j] >= lcs[i * width + j + 1])
+            {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        diff.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            hunk_range(prefix + hunk_i, i - hunk_i),
+            hunk_range(prefix + hunk_j, j - hunk_j)
+        ));
+        for line in &old[hunk_i..i] {
+            diff.push_str(&format!("-{}\n", line));
+        }
+        for line in &new[hunk_j..j] {
+            diff.push_str(&format!("+{}\n", line));
+        }
+    }
+
+    diff
+}
+
+/// Formats the `start,count` range of a hunk header, see [diff_lines].
+///
+/// `start` is the zero-based index of the first line. An empty range refers to the line before it.
+fn hunk_range(start: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{},0", start)
+    } else {
+        format!("{},{}", start + 1, count)
+    }
+}
+
+/// Rewrites the tokens of the JSON string for which `rewrite` returns a replacement.
+///
+/// Everything in between the tokens is kept as-is.
+fn rewrite_tokens(json: &str, mut rewrite: impl FnMut(&Token) -> Option<String>) -> String {
+    let mut rewritten = String::with_capacity(json.len());
+    let mut pos = 0;
+
+    for token in scanner::tokenize(json) {
+        if let Some(replacement) = rewrite(&token) {
+            rewritten.push_str(&json[pos..token.start]);
+            rewritten.push_str(&replacement);
+            pos = token.end;
+        }
+    }
+    rewritten.push_str(&json[pos..]);
+
+    rewritten
+}
+
+/// Whether the token is an unquoted key or a literal.
+fn is_bare(token: &Token) -> bool {
+    matches!(
+        token.kind,
+        scanner::TokenKind::Key(None) | scanner::TokenKind::Literal
+    )
+}
+
+/// Returns the quote character around the key token, if any.
+fn key_quote(key: &Token) -> Option<char> {
+    match key.kind {
+        scanner::TokenKind::Key(quote) => quote,
+        _ => None,
+    }
+}
+
+/// Returns the [Quotes] of a quote character found by the scanner.
+fn quotes_from_char(quote: Option<char>) -> Quotes {
+    match quote {
+        Some('"') => Quotes::DoubleQuote,
+        Some('\'') => Quotes::SingleQuote,
+        Some('`') => Quotes::Backtick,
+        Some(c) => Quotes::Custom(c),
+        None => Quotes::NoQuotes,
+    }
+}
+
+/// Surrounds the key with the quote character, if any.
+fn quote_key(key: &str, quote: Option<char>) -> String {
+    match quote {
+        Some(quote) => format!("{}{}{}", quote, key, quote),
+        None => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::json_key_quote_utils::{
+        ConversionStats, Direction, DuplicateKey, FileConversion, JsonKeyDiff, KeepDuplicate,
+        MergeStrategy, NonFinitePolicy,
+    };
+    use crate::load_write_utils::{Newline, WriteOptions};
+    use crate::{
+        json_key_quote_utils, load_write_utils, ConvertOptions, Dialect, Indent, KeyCase, Quotes,
+    };
+    use std::collections::HashMap;
+    use std::path::{Path, PathBuf};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    const SUPPORTED_KEY_CHARS: &str = r#"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789`~!@#$%€^&*()-_=+\|;"'.<>/?"#;
+    const SUPPORTED_VALUE_CHARS: &str = r#"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789`~!@#$%€^&*()-_=+\|:;"'.<>/?"#;
+
+    #[test]
+    fn test_json_convert_without_to_with_keyquotes() {
+        let path = Path::new("./tmp_without_keyquotes");
+        std::fs::copy(
+            "./test_resources/Test_without_keyquotes.json",
+            "./tmp_without_keyquotes",
+        )
+        .unwrap();
+        let conversion = json_key_quote_utils::json_convert_without_to_with_keyquotes(
+            path,
+            crate::Quotes::DoubleQuote,
+        )
+        .unwrap();
+        assert_eq!(conversion, FileConversion::Converted);
+        let converted_file_contents = load_write_utils::load_json(path).unwrap();
+        let expected_file_contents =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        assert!(converted_file_contents == expected_file_contents);
+        std::fs::remove_file("./tmp_without_keyquotes").unwrap();
+    }
+
+    #[test]
+    fn test_json_convert_with_to_without_keyquotes() {
+        let path = Path::new("./tmp_with_keyquotes");
+        std::fs::copy(
+            "./test_resources/Test_with_keyquotes.json",
+            "./tmp_with_keyquotes",
+        )
+        .unwrap();
+        let conversion =
+            json_key_quote_utils::json_convert_with_to_without_keyquotes(path).unwrap();
+        assert_eq!(conversion, FileConversion::Converted);
+        let converted_file_contents = load_write_utils::load_json(path).unwrap();
+        let expected_file_contents =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
+        assert!(converted_file_contents == expected_file_contents);
+        std::fs::remove_file("./tmp_with_keyquotes").unwrap();
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_json_convert_without_to_with_keyquotes() {
+        let path = Path::new("./tmp_async_convert_without_keyquotes");
+        std::fs::copy("./test_resources/Test_without_keyquotes.json", path).unwrap();
+
+        let conversion = json_key_quote_utils::async_json_convert_without_to_with_keyquotes(
+            path,
+            Quotes::DoubleQuote,
+        )
+        .await
+        .unwrap();
+        let second_conversion = json_key_quote_utils::async_json_convert_without_to_with_keyquotes(
+            path,
+            Quotes::DoubleQuote,
+        )
+        .await
+        .unwrap();
+
+        let converted_file_contents = load_write_utils::async_load_json(path).await.unwrap();
+        let expected_file_contents = load_write_utils::async_load_json(Path::new(
+            "./test_resources/Test_with_keyquotes.json",
+        ))
+        .await
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(conversion, FileConversion::Converted);
+        assert_eq!(second_conversion, FileConversion::Unchanged);
+        assert_eq!(converted_file_contents, expected_file_contents);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_json_convert_with_to_without_keyquotes() {
+        let path = Path::new("./tmp_async_convert_with_keyquotes");
+        std::fs::copy("./test_resources/Test_with_keyquotes.json", path).unwrap();
+
+        let conversion = json_key_quote_utils::async_json_convert_with_to_without_keyquotes(path)
+            .await
+            .unwrap();
+
+        let converted_file_contents = load_write_utils::async_load_json(path).await.unwrap();
+        let expected_file_contents = load_write_utils::async_load_json(Path::new(
+            "./test_resources/Test_without_keyquotes.json",
+        ))
+        .await
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(conversion, FileConversion::Converted);
+        assert_eq!(converted_file_contents, expected_file_contents);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_async_load_json_async_write_json() {
+        let path = Path::new("./tmp_async_load_write");
+        let json = load_write_utils::async_load_json(Path::new(
+            "./test_resources/Test_without_keyquotes.json",
+        ))
+        .await
+        .unwrap();
+
+        load_write_utils::async_write_json(path, &json)
+            .await
+            .unwrap();
+        let actual = load_write_utils::async_load_json(path).await.unwrap();
+        load_write_utils::async_write_json_atomic(path, "{a: 1}")
+            .await
+            .unwrap();
+        let actual_atomic = load_write_utils::async_load_json(path).await.unwrap();
+
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(actual, json);
+        assert_eq!(actual_atomic, "{a: 1}");
+        assert!(!Path::new("./.tmp_async_load_write.tmp").exists());
+    }
+
+    #[test]
+    fn test_json_quote_only_keys_matching() {
+        let json =
+            r#"{first name: {last name: 1, age: 2}, "quoted key": 3, 'other': [{nick name: 4}]}"#;
+
+        let actual = json_key_quote_utils::json_quote_only_keys_matching(
+            json,
+            |key| key.contains(' '),
+            Quotes::DoubleQuote,
+        );
+        let expected = r#"{"first name": {"last name": 1, age: 2}, "quoted key": 3, 'other': [{"nick name": 4}]}"#;
+        assert_eq!(actual, expected);
+
+        let actual = json_key_quote_utils::json_quote_only_keys_matching(
+            r#"{it's: 1, plain: "it's"}"#,
+            |key| key.contains('\''),
+            Quotes::SingleQuote,
+        );
+        assert_eq!(actual, r#"{'it\'s': 1, plain: "it's"}"#);
+
+        let actual =
+            json_key_quote_utils::json_quote_only_keys_matching(json, |_| true, Quotes::NoQuotes);
+        assert_eq!(actual, json);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        assert_eq!(
+            json_key_quote_utils::diff_lines("{\n  a: 1\n}", "{\n  a: 1\n}"),
+            ""
+        );
+
+        let before = "{\n  a: \"x\n y\",\n  b: 2,\n  c: 3\n}";
+        let after = "{\n  \"a\": \"x\\n y\",\n  b: 2,\n  \"c\": 3,\n  \"d\": 4\n}";
+        let expected = "@@ -2,2 +2,1 @@\n-  a: \"x\n- y\",\n+  \"a\": \"x\\n y\",\n\
+            @@ -5,1 +4,2 @@\n-  c: 3\n+  \"c\": 3,\n+  \"d\": 4\n";
+        assert_eq!(json_key_quote_utils::diff_lines(before, after), expected);
+
+        assert_eq!(
+            json_key_quote_utils::diff_lines("a\nc", "a\nb\nc"),
+            "@@ -1,0 +2,1 @@\n+b\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_preview() {
+        let path = Path::new("./tmp_diff_preview");
+        std::fs::write(path, "{\n  a: 1,\n  \"b\": 2\n}").unwrap();
+
+        let actual = json_key_quote_utils::diff_preview(path, Quotes::DoubleQuote).unwrap();
+        let contents = load_write_utils::load_json(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(actual, "@@ -2,1 +2,1 @@\n-  a: 1,\n+  \"a\": 1,\n");
+        assert_eq!(contents, "{\n  a: 1,\n  \"b\": 2\n}");
+    }
+
+    #[test]
+    fn test_json_add_remove_key_prefix() {
+        let json = r#"{"a": {b: "c", 'd e': [{f: 1}]}, v2_g: 2}"#;
+
+        let actual = json_key_quote_utils::json_add_key_prefix(json, "v2_");
+        let expected = r#"{"v2_a": {v2_b: "c", 'v2_d e': [{v2_f: 1}]}, v2_v2_g: 2}"#;
+        assert_eq!(actual, expected);
+        assert_eq!(
+            json_key_quote_utils::json_remove_key_prefix(&actual, "v2_"),
+            json
+        );
+
+        let actual = json_key_quote_utils::json_add_key_prefix(r#"{a: 1, 'b': 2}"#, "x: '");
+        assert_eq!(actual, r#"{"x: 'a": 1, 'x: \'b': 2}"#);
+
+        let actual = json_key_quote_utils::json_remove_key_prefix(r#"{a: "v2_a", v2: 1}"#, "v2_");
+        assert_eq!(actual, r#"{a: "v2_a", v2: 1}"#);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_with() {
+        let json = r#"{a: [{'b': 1}, {c d: {e: "f: g"}}], "h": null}"#;
+        let mut events = Vec::new();
+
+        let actual =
+            json_key_quote_utils::json_add_key_quotes_with(json, Quotes::DoubleQuote, |event| {
+                events.push(event)
+            });
+
+        assert_eq!(
+            actual,
+            json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote)
+        );
+        let expected = vec![
+            ("a", false),
+            ("b", true),
+            ("c d", false),
+            ("e", false),
+            ("h", true),
+        ];
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.key.as_str(), event.quoted))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(events[0].span, 1..2);
+        assert_eq!(&json[events[1].span.clone()], "'b'");
+        assert_eq!(&json[events[2].span.clone()], "c d");
+        assert_eq!(&json[events[4].span.clone()], r#""h""#);
+
+        let mut events = Vec::new();
+        let actual = json_key_quote_utils::json_add_key_quotes_with(
+            "{: 1, §a§: 2}",
+            Quotes::Custom('§'),
+            |event| events.push((event.key, event.span, event.quoted)),
+        );
+
+        assert_eq!(actual, "{§§: 1, §a§: 2}");
+        assert_eq!(
+            events,
+            vec![
+                ("".to_string(), 1..1, false),
+                ("a".to_string(), 6..11, true)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_add_remove_key_suffix() {
+        let json = r#"{"a": {b: "c", 'd e': [{f: 1}]}, g_v2: 2}"#;
+
+        let actual = json_key_quote_utils::json_add_key_suffix(json, "_v2");
+        let expected = r#"{"a_v2": {b_v2: "c", 'd e_v2': [{f_v2: 1}]}, g_v2_v2: 2}"#;
+        assert_eq!(actual, expected);
+        assert_eq!(
+            json_key_quote_utils::json_remove_key_suffix(&actual, "_v2"),
+            json
+        );
+
+        let stripped = json_key_quote_utils::json_remove_key_suffix(json, "_v2");
+        assert_eq!(stripped, r#"{"a": {b: "c", 'd e': [{f: 1}]}, g: 2}"#);
+        assert_eq!(
+            json_key_quote_utils::json_remove_key_suffix(&stripped, "_v2"),
+            stripped
+        );
+
+        let actual = json_key_quote_utils::json_add_key_prefix_and_suffix(json, "x_", "_v2");
+        let expected = r#"{"x_a_v2": {x_b_v2: "c", 'x_d e_v2': [{x_f_v2: 1}]}, x_g_v2_v2: 2}"#;
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual,
+            json_key_quote_utils::json_add_key_suffix(
+                &json_key_quote_utils::json_add_key_prefix(json, "x_"),
+                "_v2"
+            )
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        let json = "{a: {'b': \"c: d, e: f\"}, `g`: [{h i: 1}, \"j: k\", [{\"l\": {}}]]}";
+
+        let actual: Vec<_> = json_key_quote_utils::keys(json)
+            .map(|key| (key.key, key.quotes, &json[key.span]))
+            .collect();
+
+        let expected = vec![
+            ("a".to_string(), Quotes::NoQuotes, "a"),
+            ("b".to_string(), Quotes::SingleQuote, "'b'"),
+            ("g".to_string(), Quotes::Backtick, "`g`"),
+            ("h i".to_string(), Quotes::NoQuotes, "h i"),
+            ("l".to_string(), Quotes::DoubleQuote, "\"l\""),
+        ];
+        assert_eq!(actual, expected);
+        assert_eq!(json_key_quote_utils::keys("[1, \"a: b\"]").count(), 0);
+    }
+
+    #[test]
+    fn test_json_quote_numeric_keys_only() {
+        let json = r#"{0: "a", 1a: {12: [{"3": 4, 1.5: 5, -6: 6, key7: 7}]}, '8': 8}"#;
+
+        let actual = json_key_quote_utils::json_quote_numeric_keys_only(json, Quotes::SingleQuote);
+
+        let expected = r#"{'0': "a", 1a: {'12': [{"3": 4, 1.5: 5, -6: 6, key7: 7}]}, '8': 8}"#;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_json_conditional_key_quotes() {
+        let quoted = r#"{simple_key: 1, "key with spaces": 2}"#;
+        let unquoted = r#"{simple_key: 1, key with spaces: {key-2: [{Key3: 3, k.4: 4}]}}"#;
+
+        let actual_quoted =
+            json_key_quote_utils::json_conditional_key_quotes(quoted, Quotes::default());
+        let actual_unquoted =
+            json_key_quote_utils::json_conditional_key_quotes(unquoted, Quotes::SingleQuote);
+
+        let expected_unquoted =
+            r#"{simple_key: 1, 'key with spaces': {'key-2': [{Key3: 3, 'k.4': 4}]}}"#;
+        assert_eq!(actual_quoted, quoted);
+        assert_eq!(actual_unquoted, expected_unquoted);
+    }
+
+    #[test]
+    fn test_json_infer_quote_type() {
+        let majority_single = r#"{'a': 1, "b": {'c': "d: e", `f`: 2}, g: 3}"#;
+        let majority_double = r#"{'a': 1, "b": {"c": 'd: e', "f": 2}, g: 3}"#;
+        let tie = r#"{'a': 1, "b": 2, `c`: 3}"#;
+
+        assert_eq!(
+            json_key_quote_utils::json_infer_quote_type(majority_single),
+            Some(Quotes::SingleQuote)
+        );
+        assert_eq!(
+            json_key_quote_utils::json_infer_quote_type(majority_double),
+            Some(Quotes::DoubleQuote)
+        );
+        assert_eq!(
+            json_key_quote_utils::json_infer_quote_type(tie),
+            Some(Quotes::DoubleQuote)
+        );
+        assert_eq!(
+            json_key_quote_utils::json_infer_quote_type("{`a`: {b: 'c'}}"),
+            Some(Quotes::Backtick)
+        );
+        assert_eq!(
+            json_key_quote_utils::json_infer_quote_type(r#"{a: {b: "c: d"}, e: ['f']}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_max_key_len() {
+        let long_key = "k".repeat(5000);
+        let json = format!("{{short: 1, {}: {{other: 2}}}}", long_key);
+
+        let actual = json_key_quote_utils::json_add_key_quotes_with_options(
+            &json,
+            Quotes::DoubleQuote,
+            4096,
+        );
+        assert_eq!(
+            actual,
+            Err(
+                "the key at byte 11 is 5000 bytes long, longer than the maximum of 4096 bytes"
+                    .to_string()
+            )
+        );
+
+        let actual = json_key_quote_utils::json_add_key_quotes_with_options(
+            &json,
+            Quotes::DoubleQuote,
+            5000,
+        );
+        let expected = format!("{{\"short\": 1, \"{}\": {{\"other\": 2}}}}", long_key);
+        assert_eq!(actual, Ok(expected));
+
+        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::DoubleQuote);
+        let expected = format!("{{\"short\": 1, {}: {{\"other\": 2}}}}", long_key);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_json_escape_ctrlchars_unescape_ctrlchars_all_ctrlchars() {
+        let ctrlchars: String = ('\u{1}'..='\u{1f}').collect();
+        let json = format!("{{\"key\": \"{}\", other: '{}'}}", ctrlchars, ctrlchars);
+        let escaped_ctrlchars = concat!(
+            r#"\u0001\u0002\u0003\u0004\u0005\u0006\u0007\b\t\n\u000B\f\r\u000E\u000F"#,
+            r#"\u0010\u0011\u0012\u0013\u0014\u0015\u0016\u0017\u0018\u0019\u001A\u001B\u001C\u001D\u001E\u001F"#,
+        );
+        let expected_escaped = format!(
+            "{{\"key\": \"{}\", other: '{}'}}",
+            escaped_ctrlchars, escaped_ctrlchars
+        );
+
+        let actual_escaped = json_key_quote_utils::json_escape_ctrlchars(&json);
+        let actual_escaped_second_pass =
+            json_key_quote_utils::json_escape_ctrlchars(&actual_escaped);
+        let actual_unescaped = json_key_quote_utils::json_unescape_ctrlchars(&actual_escaped);
+        let actual_lowercase =
+            json_key_quote_utils::json_unescape_ctrlchars(r#"{key: "\u001f\u001A\u000b"}"#);
+        let mixed = "{\"a\": \"b\u{7}c\u{b}d\ne\", 'f': 'g\u{7}'}";
+        let actual_key_removed = json_key_quote_utils::json_escape_ctrlchars(&format!(
+            "{{\"k{}ey\": \"x\", 'a\u{1}b': 1}}",
+            ctrlchars
+        ));
+        let actual_mixed_escaped = json_key_quote_utils::json_escape_ctrlchars(mixed);
+        let actual_escaped_backslash =
+            json_key_quote_utils::json_unescape_ctrlchars(r#"{key: "\\n\\u0001\u0020"}"#);
+
+        assert_eq!(actual_escaped, expected_escaped);
+        assert_eq!(actual_escaped_second_pass, expected_escaped);
+        assert_eq!(actual_unescaped, json);
+        assert_eq!(actual_lowercase, "{key: \"\u{1f}\u{1a}\u{b}\"}");
+        assert_eq!(actual_key_removed, "{\"key\": \"x\", 'ab': 1}");
+        assert_eq!(
+            actual_mixed_escaped,
+            r#"{"a": "b\u0007c\u000Bd\ne", 'f': 'g\u0007'}"#
+        );
+        assert_eq!(
+            json_key_quote_utils::json_unescape_ctrlchars(&actual_mixed_escaped),
+            mixed
+        );
+        assert_eq!(actual_escaped_backslash, r#"{key: "\\n\\u0001\u0020"}"#);
+    }
+
+    #[test]
+    fn test_json_convert_unchanged_file_is_not_written() {
+        let path = Path::new("./tmp_unchanged_keyquotes");
+        std::fs::copy("./test_resources/Test_with_keyquotes.json", path).unwrap();
+        let modified = UNIX_EPOCH + Duration::from_secs(1_000_000_000);
+        std::fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+
+        let conversion =
+            json_key_quote_utils::json_convert_without_to_with_keyquotes(path, Quotes::DoubleQuote)
+                .unwrap();
+
+        let actual_modified = std::fs::metadata(path).unwrap().modified().unwrap();
+        let actual = load_write_utils::load_json(path).unwrap();
+        let expected =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(conversion, FileConversion::Unchanged);
+        assert_eq!(actual_modified, modified);
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_json_convert_to_output_file() {
+        let dir = Path::new("./tmp_convert_to");
+        let output = dir.join("nested").join("Test_with_keyquotes.json");
+        let input = Path::new("./test_resources/Test_without_keyquotes.json");
+        let input_before = std::fs::read(input).unwrap();
+
+        let without_dirs = json_key_quote_utils::json_convert_without_to_with_keyquotes_to(
+            input,
+            &output,
+            Quotes::DoubleQuote,
+        );
+        let options = WriteOptions {
+            create_dirs: true,
+            ..WriteOptions::default()
+        };
+        json_key_quote_utils::json_convert_without_to_with_keyquotes_to_with_options(
+            input,
+            &output,
+            Quotes::DoubleQuote,
+            &options,
+        )
+        .unwrap();
+
+        let actual = load_write_utils::load_json(&output).unwrap();
+        let expected =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+        assert!(without_dirs.is_err());
+        assert_eq!(actual, expected);
+        assert_eq!(std::fs::read(input).unwrap(), input_before);
+    }
+
+    #[test]
+    fn test_json_convert_to_input_file_returns_error() {
+        let path = Path::new("./tmp_convert_to_input");
+        std::fs::copy("./test_resources/Test_with_keyquotes.json", path).unwrap();
+        let before = std::fs::read(path).unwrap();
+
+        let result = json_key_quote_utils::json_convert_with_to_without_keyquotes_to(
+            path,
+            &Path::new(".").join(path),
+        );
+
+        let after = std::fs::read(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(after, before);
+    }
+
+    #[test]
+    fn test_convert_dir() {
+        let dir = Path::new("./tmp_convert_dir");
+        std::fs::create_dir_all(dir.join("nested")).unwrap();
+        std::fs::write(dir.join("a.json"), "{a: \"va\nl\"}").unwrap();
+        std::fs::write(dir.join("b.json"), "{\"b\": 1}").unwrap();
+        std::fs::write(dir.join("broken.json"), [b'{', 0xff, b'}']).unwrap();
+        std::fs::write(dir.join("skipped.txt"), "{c: 1}").unwrap();
+        std::fs::write(dir.join("nested").join("d.json"), "{d: 1}").unwrap();
+        let options = ConvertOptions {
+            escape_ctrlchars: true,
+            ..ConvertOptions::default()
+        };
+        let is_json = |path: &Path| {
+            path.extension()
+                .is_some_and(|extension| extension == "json")
+        };
+
+        let results = json_key_quote_utils::convert_dir(
+            dir,
+            Direction::AddKeyQuotes,
+            &options,
+            false,
+            is_json,
+        );
+        let recursive_results = json_key_quote_utils::convert_dir(
+            dir,
+            Direction::RemoveKeyQuotes,
+            &options,
+            true,
+            is_json,
+        );
+
+        let actual_a = std::fs::read_to_string(dir.join("a.json")).unwrap();
+        let actual_skipped = std::fs::read_to_string(dir.join("skipped.txt")).unwrap();
+        std::fs::remove_dir_all(dir).unwrap();
+
+        let paths: Vec<&Path> = results.iter().map(|result| result.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            [
+                dir.join("a.json"),
+                dir.join("b.json"),
+                dir.join("broken.json")
+            ]
+        );
+        assert_eq!(
+            results[0].outcome.as_ref().unwrap(),
+            &FileConversion::Converted
+        );
+        assert_eq!(
+            results[1].outcome.as_ref().unwrap(),
+            &FileConversion::Unchanged
+        );
+        assert_eq!(
+            results[2].outcome.as_ref().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+
+        let outcomes: Vec<Option<FileConversion>> = recursive_results
+            .iter()
+            .map(|result| result.outcome.as_ref().ok().copied())
+            .collect();
+        assert_eq!(recursive_results[3].path, dir.join("nested").join("d.json"));
+        assert_eq!(
+            outcomes,
+            [
+                Some(FileConversion::Converted),
+                Some(FileConversion::Converted),
+                None,
+                Some(FileConversion::Unchanged),
+            ]
+        );
+        assert_eq!(actual_a, "{a: \"va\nl\"}");
+        assert_eq!(actual_skipped, "{c: 1}");
+    }
+
+    #[test]
+    fn test_convert_dir_missing_dir_returns_error() {
+        let dir = Path::new("./tmp_missing_convert_dir");
+
+        let results = json_key_quote_utils::convert_dir(
+            dir,
+            Direction::AddKeyQuotes,
+            &ConvertOptions::default(),
+            true,
+            |_| true,
+        );
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, dir);
+        assert!(results[0].outcome.is_err());
+    }
+
+    /// Converts the JSON string with [json_key_quote_utils::convert_stream], reading chunks of `chunk_size` bytes.
+    fn convert_stream_in_chunks(
+        json: &str,
+        chunk_size: usize,
+        direction: Direction,
+        quote_type: Quotes,
+    ) -> (String, ConversionStats) {
+        let reader = std::io::BufReader::with_capacity(chunk_size, json.as_bytes());
+        let mut output = Vec::new();
+        let stats =
+            json_key_quote_utils::convert_stream(reader, &mut output, direction, quote_type)
+                .unwrap();
+
+        (String::from_utf8(output).unwrap(), stats)
+    }
+
+    #[test]
+    fn test_convert_stream_matches_test_resources() {
+        let json_without_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
+        let json_with_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+
+        for chunk_size in [1, 7, 8192] {
+            let (actual_added, _) = convert_stream_in_chunks(
+                &json_without_keyquotes,
+                chunk_size,
+                Direction::AddKeyQuotes,
+                Quotes::DoubleQuote,
+            );
+            let (actual_removed, _) = convert_stream_in_chunks(
+                &json_with_keyquotes,
+                chunk_size,
+                Direction::RemoveKeyQuotes,
+                Quotes::DoubleQuote,
+            );
+
+            assert_eq!(actual_added, json_with_keyquotes);
+            assert_eq!(actual_removed, json_without_keyquotes);
+        }
+    }
+
+    #[test]
+    fn test_convert_stream_split_tokens() {
+        let json = "{key: \"va\nl\", 'b\\'c': ['x\ty', 1], nested : {k-2: \"\\\"\\u0000\\\\n\"}, `d`: true}";
+        let expected_added = "{\"key\": \"va\\nl\", 'b\\'c': ['x\\ty', 1], \"nested\" : {\"k-2\": \"\\\"\\u0000\\\\n\"}, `d`: true}";
+
+        let (actual_added, stats_added) =
+            convert_stream_in_chunks(json, 1, Direction::AddKeyQuotes, Quotes::DoubleQuote);
+        let (actual_removed, stats_removed) = convert_stream_in_chunks(
+            &actual_added,
+            1,
+            Direction::RemoveKeyQuotes,
+            Quotes::DoubleQuote,
+        );
+
+        let expected_removed =
+            "{key: \"va\nl\", 'b\\'c': ['x\ty', 1], nested : {k-2: \"\\\"\0\\\\n\"}, d: true}";
+        assert_eq!(actual_added, expected_added);
+        assert_eq!(actual_removed, expected_removed);
+        assert_eq!(
+            stats_added,
+            ConversionStats {
+                keys_quoted: 3,
+                values_escaped: 2,
+                ..ConversionStats::default()
+            }
+        );
+        assert_eq!(
+            stats_removed,
+            ConversionStats {
+                keys_unquoted: 4,
+                values_unescaped: 3,
+                ..ConversionStats::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_stream_matches_json_add_key_quotes() {
+        let jsons = [
+            "{a: x}",
+            "{a: [1, {b: c}]}",
+            "{a: null, b: nullish, c: ObjectId (\"x\"), d: `e`, f: +5}",
+            "{a: 'va\nl', b: {c: true}, d: }",
+            "{a: undefined",
+        ];
+
+        for json in jsons {
+            let expected = json_key_quote_utils::json_escape_ctrlchars(
+                &json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote),
+            );
+            for chunk_size in [1, 3, 8192] {
+                let (actual, _) = convert_stream_in_chunks(
+                    json,
+                    chunk_size,
+                    Direction::AddKeyQuotes,
+                    Quotes::DoubleQuote,
+                );
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_convert_missing_file_returns_error() {
+        let path = Path::new("./tmp_missing_keyquotes");
+        assert!(json_key_quote_utils::json_convert_with_to_without_keyquotes(path).is_err());
+    }
+
+    #[test]
+    fn test_load_json_from_reader_write_json_to_writer() {
+        let mut writer = std::io::Cursor::new(Vec::new());
+        load_write_utils::write_json_to_writer(&mut writer, "{key: \"vä\nl\"}").unwrap();
+
+        let reader = std::io::Cursor::new(writer.into_inner());
+        let actual = load_write_utils::load_json_from_reader(reader).unwrap();
+        assert_eq!(actual, "{key: \"vä\nl\"}");
+
+        let reader = std::io::Cursor::new(vec![b'{', 0xc3, 0x28, b'}']);
+        let err = load_write_utils::load_json_from_reader(reader).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let path = Path::new("./tmp_invalid_utf8.json");
+        std::fs::write(path, [b'{', 0xff, b'}']).unwrap();
+        let err = load_write_utils::load_json(path).unwrap_err();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_write_json_atomic() {
+        let path = Path::new("./tmp_atomic.json");
+        std::fs::write(path, "{key: 1}").unwrap();
+
+        load_write_utils::write_json_atomic(path, "{\"key\": 2}").unwrap();
+        assert_eq!(load_write_utils::load_json(path).unwrap(), "{\"key\": 2}");
+        assert!(!Path::new("./.tmp_atomic.json.tmp").exists());
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_write_json_atomic_failure_keeps_original() {
+        let path = Path::new("./tmp_atomic_failure.json");
+        let tmp_path = Path::new("./.tmp_atomic_failure.json.tmp");
+        std::fs::write(path, "{key: \"va\nl\"}").unwrap();
+        std::fs::create_dir(tmp_path).unwrap();
+
+        let write_result = load_write_utils::write_json_atomic(path, "{\"key\": 2}");
+        let convert_result =
+            json_key_quote_utils::json_convert_without_to_with_keyquotes(path, Quotes::DoubleQuote);
+
+        let actual = load_write_utils::load_json(path).unwrap();
+        std::fs::remove_dir(tmp_path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert!(write_result.is_err());
+        assert!(convert_result.is_err());
+        assert_eq!(actual, "{key: \"va\nl\"}");
+    }
+
+    #[test]
+    fn test_json_convert_with_backup() {
+        let path = Path::new("./tmp_backup.json");
+        let backup_path = Path::new("./tmp_backup.json.bak");
+        std::fs::copy("./test_resources/Test_without_keyquotes.json", path).unwrap();
+        std::fs::write(backup_path, "stale backup").unwrap();
+        let options = WriteOptions {
+            backup: Some(".bak".to_string()),
+            ..WriteOptions::default()
+        };
+
+        let conversion = json_key_quote_utils::json_convert_without_to_with_keyquotes_with_options(
+            path,
+            Quotes::DoubleQuote,
+            &options,
+        )
+        .unwrap();
+
+        let actual = load_write_utils::load_json(path).unwrap();
+        let actual_backup = std::fs::read(backup_path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(backup_path).unwrap();
+        assert_eq!(conversion, FileConversion::Converted);
+        assert_eq!(
+            actual,
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap()
+        );
+        assert_eq!(
+            actual_backup,
+            std::fs::read("./test_resources/Test_without_keyquotes.json").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_json_backup_failure_keeps_original() {
+        let path = Path::new("./tmp_backup_failure.json");
+        let backup_path = Path::new("./tmp_backup_failure.json.bak");
+        std::fs::write(path, "{\"key\": 1}").unwrap();
+        std::fs::create_dir(backup_path).unwrap();
+        let options = WriteOptions {
+            backup: Some(".bak".to_string()),
+            ..WriteOptions::default()
+        };
+
+        let write_result = load_write_utils::write_json_with_options(path, "{key: 2}", &options);
+        let convert_result =
+            json_key_quote_utils::json_convert_with_to_without_keyquotes_with_options(
+                path, &options,
+            );
+        let empty_suffix_result = load_write_utils::write_json_with_options(
+            path,
+            "{key: 2}",
+            &WriteOptions {
+                backup: Some(String::new()),
+                ..WriteOptions::default()
+            },
+        );
+
+        let actual = load_write_utils::load_json(path).unwrap();
+        std::fs::remove_dir(backup_path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert!(write_result.is_err());
+        assert!(convert_result.is_err());
+        assert!(empty_suffix_result.is_err());
+        assert_eq!(actual, "{\"key\": 1}");
+    }
+
+    #[test]
+    fn test_load_json_byte_order_marks() {
+        let json =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
+        let expected =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        let utf16 = |to_bytes: fn(u16) -> [u8; 2]| -> Vec<u8> {
+            json.encode_utf16().flat_map(to_bytes).collect()
+        };
+        let encodings = [
+            ("utf8", [b"".as_slice(), json.as_bytes()].concat()),
+            (
+                "utf8_bom",
+                [[0xEF, 0xBB, 0xBF].as_slice(), json.as_bytes()].concat(),
+            ),
+            (
+                "utf16le",
+                [vec![0xFF, 0xFE], utf16(u16::to_le_bytes)].concat(),
+            ),
+            (
+                "utf16be",
+                [vec![0xFE, 0xFF], utf16(u16::to_be_bytes)].concat(),
+            ),
+        ];
+
+        for (encoding, bytes) in encodings {
+            let path = PathBuf::from(format!("./tmp_encoding_{}.json", encoding));
+            std::fs::write(&path, bytes).unwrap();
+
+            assert_eq!(load_write_utils::load_json(&path).unwrap(), json);
+            json_key_quote_utils::json_convert_without_to_with_keyquotes(
+                &path,
+                Quotes::DoubleQuote,
+            )
+            .unwrap();
+            let actual = load_write_utils::load_json(&path).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(actual, expected, "{}", encoding);
+        }
+
+        let odd_utf16 = std::io::Cursor::new(vec![0xFF, 0xFE, b'{', 0x00, b'}']);
+        let err = load_write_utils::load_json_from_reader(odd_utf16).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_json_convert_preserves_newlines() {
+        let path = Path::new("./tmp_newlines_crlf.json");
+        std::fs::write(
+            path,
+            "{\r\n  a: 1,\r\n  \"b\": {\r\n    e: \"c\r\nd\"\r\n  }\r\n}",
+        )
+        .unwrap();
+
+        json_key_quote_utils::json_convert_without_to_with_keyquotes(path, Quotes::DoubleQuote)
+            .unwrap();
+
+        let actual = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let expected = "{\r\n  \"a\": 1,\r\n  \"b\": {\r\n    \"e\": \"c\\r\\nd\"\r\n  }\r\n}";
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_json_convert_newline_options() {
+        let path = Path::new("./tmp_newlines_options.json");
+        let json = "{\r\n  \"a\": \"b\\nc\",\r\n  \"d\": [\r\n    1\r\n  ]\r\n}";
+        let convert = |options: &WriteOptions| {
+            std::fs::write(path, json).unwrap();
+            json_key_quote_utils::json_convert_with_to_without_keyquotes_with_options(
+                path, options,
+            )
+            .unwrap();
+            std::fs::read_to_string(path).unwrap()
+        };
+
+        let actual_preserved = convert(&WriteOptions {
+            ensure_trailing_newline: true,
+            ..WriteOptions::default()
+        });
+        let actual_lf = convert(&WriteOptions {
+            newline: Newline::Lf,
+            ..WriteOptions::default()
+        });
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(
+            actual_preserved,
+            "{\r\n  a: \"b\nc\",\r\n  d: [\r\n    1\r\n  ]\r\n}\r\n"
+        );
+        assert_eq!(actual_lf, "{\n  a: \"b\nc\",\n  d: [\n    1\n  ]\n}");
+
+        let path = Path::new("./tmp_newlines_write.json");
+        std::fs::write(path, "{\r\n}\r\n").unwrap();
+        load_write_utils::write_json_with_options(path, "{\n  a: 1\n}", &WriteOptions::default())
+            .unwrap();
+        let actual = std::fs::read_to_string(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(actual, "{\r\n  a: 1\r\n}");
+    }
+
+    #[test]
+    fn test_load_json_glob_write_json_bulk() {
+        let pairs = vec![
+            (PathBuf::from("./tmp_bulk_b.json"), String::from("{b: 2}")),
+            (PathBuf::from("./tmp_bulk_a.json"), String::from("{a: 1}")),
+        ];
+        load_write_utils::write_json_bulk(&pairs).unwrap();
+
+        let actual = load_write_utils::load_json_glob("./tmp_bulk_*.json").unwrap();
+        let expected = vec![
+            (PathBuf::from("tmp_bulk_a.json"), String::from("{a: 1}")),
+            (PathBuf::from("tmp_bulk_b.json"), String::from("{b: 2}")),
+        ];
+        std::fs::remove_file("./tmp_bulk_a.json").unwrap();
+        std::fs::remove_file("./tmp_bulk_b.json").unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_single_quote_add_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+
+        let json =
+            r#"{"#.to_string() + &supported_key_chars + r#": '"# + &supported_value_chars + r#"'}"#;
+        let expected = r#"{'"#.to_string()
+            + &supported_key_chars
+            + r#"': '"#
+            + &supported_value_chars
+            + r#"'}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::SingleQuote);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::SingleQuote);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_double_quote_add_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let json =
+            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+        let expected = r#"{""#.to_string()
+            + &supported_key_chars
+            + r#"": ""#
+            + &supported_value_chars
+            + r#""}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::DoubleQuote);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::DoubleQuote);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_single_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+
+        let json = r#"{'"#.to_string()
+            + &supported_key_chars
+            + r#"': ""#
+            + &supported_value_chars
+            + r#""}"#;
+        let expected =
+            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+
+        let actual = json_key_quote_utils::json_remove_key_quotes(&json);
+        let actual_second_pass = json_key_quote_utils::json_remove_key_quotes(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_double_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let json = r#"{""#.to_string()
+            + &supported_key_chars
+            + r#"": ""#
+            + &supported_value_chars
+            + r#""}"#;
+        let expected =
+            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+
+        let actual = json_key_quote_utils::json_remove_key_quotes(&json);
+        let actual_second_pass = json_key_quote_utils::json_remove_key_quotes(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_backtick_add_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen("`", r#"\`"#, 1);
+
+        let json =
+            r#"{"#.to_string() + &supported_key_chars + r#": ""# + SUPPORTED_VALUE_CHARS + r#"`"}"#;
+        let expected = r#"{`"#.to_string()
+            + &supported_key_chars
+            + r#"`: ""#
+            + SUPPORTED_VALUE_CHARS
+            + r#"`"}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::Backtick);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::Backtick);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_backtick_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen("`", r#"\`"#, 1);
+
+        let json = r#"{`"#.to_string() + &supported_key_chars + r#"`: "`, `a`: `"}"#;
+        let expected = r#"{"#.to_string() + &supported_key_chars + r#": "`, `a`: `"}"#;
+
+        let actual = json_key_quote_utils::json_remove_key_quotes(&json);
+        let actual_second_pass = json_key_quote_utils::json_remove_key_quotes(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_no_quotes_removes_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let json = r#"{""#.to_string()
+            + &supported_key_chars
+            + r#"": ""#
+            + &supported_value_chars
+            + r#""}"#;
+        let expected =
+            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::NoQuotes);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::NoQuotes);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+        assert_eq!(json_key_quote_utils::json_remove_key_quotes(&json), actual);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_custom_quotes() {
+        for quote in ['|', '#', '$', '§'] {
+            let json = r#"{d: {e: 1, 'f': [true]}, a: "b: c"}"#;
+            let expected = format!(
+                r#"{{{q}d{q}: {{{q}e{q}: 1, 'f': [true]}}, {q}a{q}: "b: c"}}"#,
+                q = quote
+            );
+            let expected_removed = r#"{d: {e: 1, f: [true]}, a: "b: c"}"#;
+
+            let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::Custom(quote));
+            let actual_second_pass =
+                json_key_quote_utils::json_add_key_quotes(&actual, Quotes::Custom(quote));
+            let actual_removed =
+                json_key_quote_utils::json_remove_custom_key_quotes(&actual, Quotes::Custom(quote));
+
+            assert_eq!(expected, actual);
+            assert_eq!(expected, actual_second_pass);
+            assert_eq!(expected_removed, actual_removed);
+        }
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_surrounding_whitespace() {
+        let json = "{ key name : \"val\", \n\t obj : { num :1, bool\t: true }, arr: [ 'a' ] }";
+        let expected = "{ \"key name\" : \"val\", \n\t \"obj\" : { \"num\" :1, \"bool\"\t: true }, \"arr\": [ 'a' ] }";
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::DoubleQuote);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_keys_in_string_values() {
+        let json = r#"{a: "b, c: 1", d: 'e: {f: 2}', g: ["h: 3", {i: "j, k: 4"}]}"#;
+        let expected = r#"{"a": "b, c: 1", "d": 'e: {f: 2}', "g": ["h: 3", {"i": "j, k: 4"}]}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_aligned_colons() {
+        let json = "{\n  a      : 1,\n  bb     : 'x',\n  ccc    : \"y\",\n  dddd   : {e  :null},\n  f      : [true],\n\tg\t\t:\tfalse,\n\t\th:-1\n}";
+        let expected = "{\n  \"a\"      : 1,\n  \"bb\"     : 'x',\n  \"ccc\"    : \"y\",\n  \"dddd\"   : {\"e\"  :null},\n  \"f\"      : [true],\n\t\"g\"\t\t:\tfalse,\n\t\t\"h\":-1\n}";
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::DoubleQuote);
+        let actual_custom = json_key_quote_utils::json_add_key_quotes(json, Quotes::Custom('§'));
+        let mut actual_stream = Vec::new();
+        json_key_quote_utils::convert_stream(
+            json.as_bytes(),
+            &mut actual_stream,
+            Direction::AddKeyQuotes,
+            Quotes::DoubleQuote,
+        )
+        .unwrap();
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+        assert_eq!(
+            expected.replace('"', "§").replace("§y§", "\"y\""),
+            actual_custom
+        );
+        assert_eq!(expected.as_bytes(), actual_stream);
+        assert_eq!(json, json_key_quote_utils::json_remove_key_quotes(&actual));
+    }
+
+    #[test]
+    fn test_json_escape_ctrlchars_single_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+
+        let key = supported_key_chars.to_string();
+        let value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let expected_key = supported_key_chars.to_string();
+        let expected_value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let json = r#"{'"#.to_string() + &key + r#"': '"# + &value + r#"'}"#;
+        let expected = r#"{'"#.to_string() + &expected_key + r#"': '"# + &expected_value + r#"'}"#;
+
+        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_escape_ctrlchars_double_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let key = supported_key_chars.to_string();
+        let value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let expected_key = supported_key_chars.to_string();
+        let expected_value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let json = r#"{""#.to_string() + &key + r#"": ""# + &value + r#""}"#;
+        let expected = r#"{""#.to_string() + &expected_key + r#"": ""# + &expected_value + r#""}"#;
+
+        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_escape_ctrlchars_unquoted_keys_supported_characters() {
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let key = SUPPORTED_KEY_CHARS
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+        let value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let expected_value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let json = r#"{"#.to_string() + &key + r#": ""# + &value + r#""}"#;
+        let expected = r#"{"#.to_string() + &key + r#": ""# + &expected_value + r#""}"#;
+
+        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_unescape_ctrlchars_single_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+
+        let key = supported_key_chars.to_string();
+        let value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let expected_key = supported_key_chars.to_string();
+        let expected_value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let json = r#"{"#.to_string() + &key + r#": '"# + &value + r#"'}"#;
+        let expected = r#"{"#.to_string() + &expected_key + r#": '"# + &expected_value + r#"'}"#;
+
+        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_unescape_ctrlchars_double_quoted_supported_characters() {
+        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let key = supported_key_chars.to_string();
+        let value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let expected_key = supported_key_chars.to_string();
+        let expected_value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let json = r#"{"#.to_string() + &key + r#": ""# + &value + r#""}"#;
+        let expected = r#"{"#.to_string() + &expected_key + r#": ""# + &expected_value + r#""}"#;
+
+        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_unescape_ctrlchars_double_quoted_keys_supported_characters() {
+        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+
+        let key = SUPPORTED_KEY_CHARS
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+        let value = supported_value_chars
+            .replacen("A", r#"A\n"#, 1)
+            .replacen("B", r#"B\t"#, 1);
+
+        let expected_value = supported_value_chars
+            .replacen(
+                "A", r#"A
+"#, 1,
+            )
+            .replacen("B", r#"B	"#, 1);
+
+        let json = r#"{""#.to_string() + &key + r#"": ""# + &value + r#""}"#;
+        let expected = r#"{""#.to_string() + &key + r#"": ""# + &expected_value + r#""}"#;
+
+        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
+        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_flatten_keys_round_trip_three_levels() {
+        let json = r#"{"a": {"b": {"c": 1, "d": [1, {"e": 2}]}, "f": 'g'}, h: null}"#;
+        let expected = r#"{"a.b.c": 1, "a.b.d": [1, {"e": 2}], "a.f": 'g', h: null}"#;
+
+        let actual = json_key_quote_utils::json_flatten_keys(json, ".");
+        let actual_unflattened = json_key_quote_utils::json_unflatten_keys(&actual, ".");
+
+        assert_eq!(expected, actual);
+        assert_eq!(json, actual_unflattened);
+    }
+
+    #[test]
+    fn test_json_flatten_keys_renders_on_a_single_line() {
+        let json = "{\n\t\"a\": {\n\t\tb: [1,\n 2],\n\t\t'c': {}\n\t},\n\td :null\n}";
+        let expected = "{\"a.b\": [1,\n 2], \"a.c\": {}, d: null}";
+
+        let actual = json_key_quote_utils::json_flatten_keys(json, ".");
+        let actual_unflattened = json_key_quote_utils::json_unflatten_keys(&actual, ".");
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            "{\"a\": {\"b\": [1,\n 2], \"c\": {}}, d: null}",
+            actual_unflattened
+        );
+    }
+
+    #[test]
+    fn test_json_flatten_keys_separator_in_string_value() {
+        let json = r#"{"a": {"b": "x/y", "c": {"d": "/"}}}"#;
+        let expected = r#"{"a/b": "x/y", "a/c/d": "/"}"#;
+
+        let actual = json_key_quote_utils::json_flatten_keys(json, "/");
+        let actual_unflattened = json_key_quote_utils::json_unflatten_keys(&actual, "/");
+
+        assert_eq!(expected, actual);
+        assert_eq!(json, actual_unflattened);
+    }
+
+    #[test]
+    fn test_json_minify_preserves_string_values() {
+        let json = "{ 'a b' : \"c  d\",\n\te f: [ 1 , 'g\th' ] }";
+        let expected = "{'a b':\"c  d\",e f:[1,'g\th']}";
+
+        let actual = json_key_quote_utils::json_minify(json);
+        let actual_second_pass = json_key_quote_utils::json_minify(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_strip_comments_keeps_strings() {
+        let json = "{\n\t// comment: 1\n\ta: 'b // c', /* d: 2 */ e: \"/* f */\"\r\n}";
+        let expected = "{\n\t\n\ta: 'b // c',  e: \"/* f */\"\r\n}";
+
+        let actual = json_key_quote_utils::json_strip_comments(json);
+        let actual_second_pass = json_key_quote_utils::json_strip_comments(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_minify_after_add_key_quotes_parses() {
+        let json_with_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        let json_without_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
+
+        let expected: serde_json::Value =
+            serde_json::from_str(json_with_keyquotes.trim_start_matches('\u{feff}')).unwrap();
+
+        let added =
+            json_key_quote_utils::json_add_key_quotes(&json_without_keyquotes, Quotes::DoubleQuote);
+        let minified =
+            json_key_quote_utils::json_minify(&json_key_quote_utils::json_escape_ctrlchars(&added));
+        let actual: serde_json::Value = serde_json::from_str(&minified).unwrap();
+
+        assert!(!minified.contains('\n'));
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_json_count_keys_traverses_arrays() {
+        let json = r#"{"a": [{b: 1, 'c': {"d": null}}, [{"e": "f: g"}]], h: "i, j: k"}"#;
+
+        assert_eq!(6, json_key_quote_utils::json_count_keys(json));
+        assert_eq!(2, json_key_quote_utils::json_count_keys_at_depth(json, 1));
+        assert_eq!(0, json_key_quote_utils::json_count_keys_at_depth(json, 2));
+        assert_eq!(2, json_key_quote_utils::json_count_keys_at_depth(json, 3));
+        assert_eq!(2, json_key_quote_utils::json_count_keys_at_depth(json, 4));
+    }
+
+    #[test]
+    fn test_json_count_nesting_depth() {
+        let cases = [
+            (r#"{"key": "val"}"#, 1),
+            ("{}", 1),
+            ("[1, 2]", 1),
+            (r#"{a: {b: {c: 1}}, d: 2}"#, 3),
+            (r#"[{"a": 1}, {'b': "c"}]"#, 2),
+            (r#"{"a": "{[{[", "{b}": [[]]}"#, 3),
+            ("42", 0),
+            ("", 0),
+        ];
+
+        for (json, expected) in cases {
+            let actual = json_key_quote_utils::json_count_nesting_depth(json);
+
+            assert_eq!(expected, actual, "json: {}", json);
+        }
+    }
+
+    #[test]
+    fn test_json_extract_values() {
+        let json = r#"{"a": "b: c", d: ['e', 1, "f\"g"], 'h': {a: 'i', "a": {j: "k"}}, l: "a"}"#;
+
+        let actual = json_key_quote_utils::json_extract_values(json);
+        let actual_for_key = json_key_quote_utils::json_extract_values_for_key(json, "a");
+        let actual_for_array_key = json_key_quote_utils::json_extract_values_for_key(json, "d");
+        let actual_for_missing_key =
+            json_key_quote_utils::json_extract_values_for_key(json, "missing");
+
+        assert_eq!(actual, vec!["b: c", "e", "f\\\"g", "i", "k", "a"]);
+        assert_eq!(actual_for_key, vec!["b: c", "i"]);
+        assert!(actual_for_array_key.is_empty());
+        assert!(actual_for_missing_key.is_empty());
+    }
+
+    #[test]
+    fn test_json_split_documents_convert_each() {
+        let json = "{a: 1}\n// {c: 3}\n[{b: 'x'}] /* d */ 2{e: \"f}\"}\r\n";
+        let expected = "{\"a\": 1}\n// {c: 3}\n[{\"b\": 'x'}] /* d */ 2{\"e\": \"f}\"}\r\n";
+
+        let actual_documents = json_key_quote_utils::json_split_documents(json);
+        let actual = json_key_quote_utils::json_convert_each(json, |document| {
+            json_key_quote_utils::json_add_key_quotes(document, Quotes::DoubleQuote)
+        });
+
+        assert_eq!(
+            actual_documents,
+            vec!["{a: 1}", "[{b: 'x'}]", "2", "{e: \"f}\"}"]
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_json_add_remove_key_quotes_json5_values() {
+        for value in ["+5", "0xFF", ".5", "5.", "Infinity", "-Infinity", "NaN"] {
+            let json = format!("{{a: {}, 'b': [{{c:{}}}]}}", value, value);
+            let expected = format!("{{\"a\": {}, 'b': [{{\"c\":{}}}]}}", value, value);
+
+            let actual = json_key_quote_utils::json_add_key_quotes_with_dialect(
+                &json,
+                Quotes::DoubleQuote,
+                Dialect::Json5,
+            );
+            let actual_removed = json_key_quote_utils::json_remove_key_quotes(&actual);
+
+            assert_eq!(expected, actual, "value: {}", value);
+            assert_eq!(json.replace("'b'", "b"), actual_removed, "value: {}", value);
+        }
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_call_values() {
+        let json =
+            r#"{_id: ObjectId("5f1d"), ts: ISODate ( '2020-01-01' ), $set: {$inc: Custom(1)}}"#;
+        let expected = r#"{"_id": ObjectId("5f1d"), "ts": ISODate ( '2020-01-01' ), "$set": {"$inc": Custom(1)}}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_removed = json_key_quote_utils::json_remove_key_quotes(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(json, actual_removed);
+    }
+
+    #[test]
+    fn test_json_convert_mongo_shell_values() {
+        let json = r#"{_id: ObjectId("5f\"1d"), ts: ISODate ( '2020-01-01' ), n: [NumberLong(5), NumberInt(-2), NumberDecimal("1.5")], note: "ObjectId('x')", other: MyObjectId("y"), bad: ObjectId(a b)}"#;
+        let expected = r#"{_id: {`$oid`: "5f\"1d"}, ts: {`$date`: "2020-01-01"}, n: [{`$numberLong`: "5"}, {`$numberInt`: "-2"}, {`$numberDecimal`: "1.5"}], note: "ObjectId('x')", other: MyObjectId("y"), bad: ObjectId(a b)}"#;
+        let expected_added =
+            r#"{"_id": {"$oid": "5f\"1d"}, "ts": {"$date": "2020-01-01"}, "$set": {"$inc": 1}}"#;
+
+        let actual = json_key_quote_utils::json_convert_mongo_shell_values(json, Quotes::Backtick);
+        let actual_added = json_key_quote_utils::json_add_key_quotes_with_dialect(
+            r#"{_id: ObjectId("5f\"1d"), ts: ISODate('2020-01-01'), $set: {$inc: 1}}"#,
+            Quotes::DoubleQuote,
+            Dialect::MongoShell,
+        );
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected_added, actual_added);
+    }
+
+    #[test]
+    fn test_json_collapse_whitespace_in_keys() {
+        let json = "{\"  key  name \": \"a  b\", 'tab\t\tkey': {multi \n  line: 1}, \"esc\\n\\t key\\\\n\": [{\" \": 2}], \"q\\\" x\": 3}";
+        let expected = "{\"key name\": \"a  b\", 'tab key': {multi line: 1}, \"esc key\\\\n\": [{\"\": 2}], \"q\\\" x\": 3}";
+
+        let actual = json_key_quote_utils::json_collapse_whitespace_in_keys(json);
+        let actual_second_pass = json_key_quote_utils::json_collapse_whitespace_in_keys(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+    }
+
+    #[test]
+    fn test_json_add_remove_key_quotes_reserved_keys() {
+        let json = r#"{null: 1, true: "yes", false: null, nulls: true, x: {null: [false]}}"#;
+        let expected =
+            r#"{"null": 1, "true": "yes", "false": null, "nulls": true, "x": {"null": [false]}}"#;
+        let expected_kept =
+            r#"{"null": 1, "true": "yes", "false": null, nulls: true, x: {"null": [false]}}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_removed = json_key_quote_utils::json_remove_key_quotes(&actual);
+        let actual_kept = json_key_quote_utils::json_remove_key_quotes_keep_reserved(&actual);
+        let actual_minified = json_key_quote_utils::json_remove_key_quotes_keep_reserved(
+            "{'null':1,`true`:'a',\"false\":{\"null\":true}}",
+        );
+
+        assert_eq!(expected, actual);
+        assert_eq!(json, actual_removed);
+        assert_eq!(expected_kept, actual_kept);
+        assert_eq!(
+            "{'null':1,`true`:'a',\"false\":{\"null\":true}}",
+            actual_minified
+        );
+    }
+
+    #[test]
+    fn test_json_add_remove_key_quotes_numeric_keys() {
+        let json = r#"{123: "x", 007: "y", -1: 5, 1.5: true, a: {0: [1, {2: null}]}, 99: {}, 3:4, 1a: -2}"#;
+        let expected = r#"{"123": "x", "007": "y", "-1": 5, "1.5": true, "a": {"0": [1, {"2": null}]}, "99": {}, "3":4, "1a": -2}"#;
+        let expected_kept = r#"{"123": "x", "007": "y", "-1": 5, "1.5": true, a: {"0": [1, {"2": null}]}, "99": {}, "3":4, 1a: -2}"#;
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_minified = json_key_quote_utils::json_add_key_quotes(
+            "[{1:2},{-3:'a'},{007:-0}]",
+            Quotes::SingleQuote,
+        );
+        let actual_removed = json_key_quote_utils::json_remove_key_quotes(&actual);
+        let actual_kept = json_key_quote_utils::json_remove_key_quotes_keep_numeric(&actual);
+
+        assert_eq!(expected, actual);
+        assert_eq!("[{'1':2},{'-3':'a'},{'007':-0}]", actual_minified);
+        assert_eq!(json, actual_removed);
+        assert_eq!(expected_kept, actual_kept);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_recursive_only() {
+        let json = "{a: {b: {c: 1, 'd': [{e: 2}]}}, f: 3, §g§: {§h§: 4}}";
+        let expected = "{a: {'b': {'c': 1, 'd': [{'e': 2}]}}, f: 3, §g§: {'§h§': 4}}";
+        let expected_custom = "{a: {§b§: {§c§: 1, 'd': [{§e§: 2}]}}, f: 3, §g§: {§h§: 4}}";
+        let expected_from_depth_3 = "{a: {b: {'c': 1, 'd': [{'e': 2}]}}, f: 3, §g§: {§h§: 4}}";
+
+        let actual =
+            json_key_quote_utils::json_add_key_quotes_recursive_only(json, Quotes::SingleQuote);
+        let actual_custom =
+            json_key_quote_utils::json_add_key_quotes_recursive_only(json, Quotes::Custom('§'));
+        let actual_from_depth_3 =
+            json_key_quote_utils::json_add_key_quotes_from_depth(json, Quotes::SingleQuote, 3);
+        let actual_from_depth_0 = json_key_quote_utils::json_add_key_quotes_from_depth(
+            "{a: {b: 1}}",
+            Quotes::DoubleQuote,
+            0,
+        );
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected_custom, actual_custom);
+        assert_eq!(expected_from_depth_3, actual_from_depth_3);
+        assert_eq!(r#"{"a": {"b": 1}}"#, actual_from_depth_0);
+    }
+
+    #[test]
+    fn test_json_add_remove_key_quotes_structural_chars_in_keys() {
+        for c in [',', '{', '}', '[', ']'] {
+            for quote in ['"', '\'', '`'] {
+                let json = format!(
+                    "{{{q}a{c}b{q}: {{{q}{c}{q}: [1]}}, key: 2}}",
+                    q = quote,
+                    c = c
+                );
+                let expected_added = format!(
+                    "{{{q}a{c}b{q}: {{{q}{c}{q}: [1]}}, \"key\": 2}}",
+                    q = quote,
+                    c = c
+                );
+                let expected_removed = format!(
+                    "{{{q}a{c}b{q}: {{{q}{c}{q}: [1]}}, key: 2}}",
+                    q = quote,
+                    c = c
+                );
+
+                let actual_added =
+                    json_key_quote_utils::json_add_key_quotes(&json, Quotes::DoubleQuote);
+                let actual_removed = json_key_quote_utils::json_remove_key_quotes(&actual_added);
+                let mut actual_stream_removed = Vec::new();
+                json_key_quote_utils::convert_stream(
+                    actual_added.as_bytes(),
+                    &mut actual_stream_removed,
+                    Direction::RemoveKeyQuotes,
+                    Quotes::DoubleQuote,
+                )
+                .unwrap();
+
+                assert_eq!(expected_added, actual_added, "key: {}{}{}", quote, c, quote);
+                assert_eq!(
+                    expected_removed, actual_removed,
+                    "key: {}{}{}",
+                    quote, c, quote
+                );
+                assert_eq!(
+                    expected_removed.as_bytes(),
+                    actual_stream_removed,
+                    "key: {}{}{}",
+                    quote,
+                    c,
+                    quote
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_empty_keys() {
+        let json = "{\"\": 1, \" \": 2, '': 3, \" \t\n\": {``: 4, \"a\": 5}}";
+        let expected = "{\"\": 1, \" \": 2, '': 3, \" \t\n\": {``: 4, a: 5}}";
+
+        let actual = json_key_quote_utils::json_remove_key_quotes(json);
+        let actual_custom = json_key_quote_utils::json_remove_custom_key_quotes(
+            "{§§: 1, § §: 2}",
+            Quotes::Custom('§'),
+        );
+        let mut actual_stream = Vec::new();
+        let stats = json_key_quote_utils::convert_stream(
+            json.as_bytes(),
+            &mut actual_stream,
+            Direction::RemoveKeyQuotes,
+            Quotes::DoubleQuote,
+        )
+        .unwrap();
+
+        assert_eq!(expected, actual);
+        assert_eq!("{§§: 1, § §: 2}", actual_custom);
+        assert_eq!(expected.as_bytes(), actual_stream);
+        assert_eq!(1, stats.keys_unquoted);
+    }
+
+    #[test]
+    fn test_json_add_key_quotes_empty_keys() {
+        let json = "{ : 1, a: {: 2}, b: [{:3}], c: \"{ : 4, : 5}\", d: 6,: 7}";
+        let expected = "{ \"\": 1, \"a\": {\"\": 2}, \"b\": [{\"\":3}], \"c\": \"{ : 4, : 5}\", \"d\": 6,\"\": 7}";
+
+        let actual = json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote);
+        let actual_single = json_key_quote_utils::json_add_key_quotes("{: 1}", Quotes::SingleQuote);
+        let actual_custom = json_key_quote_utils::json_add_key_quotes("{: 1}", Quotes::Custom('§'));
+        let mut actual_stream = Vec::new();
+        let stats = json_key_quote_utils::convert_stream(
+            json.as_bytes(),
+            &mut actual_stream,
+            Direction::AddKeyQuotes,
+            Quotes::DoubleQuote,
+        )
+        .unwrap();
+
+        assert_eq!(expected, actual);
+        assert_eq!("{'': 1}", actual_single);
+        assert_eq!("{§§: 1}", actual_custom);
+        assert_eq!(expected.as_bytes(), actual_stream);
+        assert_eq!(8, stats.keys_quoted);
+        assert_eq!(
+            json,
+            json_key_quote_utils::json_add_key_quotes(json, Quotes::NoQuotes)
+        );
+    }
+
+    #[test]
+    fn test_json_remove_null_values() {
+        let cases = [
+            ("{a: null, b: 1, c: 2}", "{b: 1, c: 2}"),
+            ("{a: 1, b: null, c: 2}", "{a: 1, c: 2}"),
+            ("{a: 1, b: 2, c: null}", "{a: 1, b: 2}"),
+            ("{a: null}", "{}"),
+            ("{a: null, b: null}", "{}"),
+            ("{a: null,}", "{}"),
+            ("{\n  a: null,\n  b: null,\n}", "{\n  \n}"),
+            ("{\n\t\"a\": 1,\n\t\"b\": null,\n}", "{\n\t\"a\": 1,\n}"),
+            (
+                r#"{"a": {"b": null, 'c': {d: null, e: "null"}}, f: [null, {g: null, h: 1}]}"#,
+                r#"{"a": {'c': {e: "null"}}, f: [null, {h: 1}]}"#,
+            ),
+            ("{a: null", "{a: null"),
+        ];
+
+        for (json, expected) in cases {
+            let actual = json_key_quote_utils::json_remove_null_values(json);
+
+            assert_eq!(expected, actual, "json: {}", json);
+        }
+    }
+
+    #[test]
+    fn test_json_replace_null_with_default() {
+        let json = r#"{"a": null, null: "null", 'b': {c: [null, 1, [null, "null"]], d: 'null'}, e: nullable}"#;
+        let expected_empty_string =
+            r#"{"a": "", null: "null", 'b': {c: ["", 1, ["", "null"]], d: 'null'}, e: nullable}"#;
+        let expected_zero =
+            r#"{"a": 0, null: "null", 'b': {c: [0, 1, [0, "null"]], d: 'null'}, e: nullable}"#;
+
+        let actual_empty_string =
+            json_key_quote_utils::json_replace_null_with_default(json, r#""""#);
+        let actual_zero = json_key_quote_utils::json_replace_null_with_default(json, "0");
+
+        assert_eq!(expected_empty_string, actual_empty_string);
+        assert_eq!(expected_zero, actual_zero);
+        assert_eq!(
+            "[1, 2]",
+            json_key_quote_utils::json_replace_null_with_default("[1, 2]", "0")
+        );
+    }
+
+    #[test]
+    fn test_json_empty_and_scalar_input() {
+        let conversions: [fn(&str) -> String; 12] = [
+            |json| json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote),
+            json_key_quote_utils::json_remove_key_quotes,
+            json_key_quote_utils::json_escape_ctrlchars,
+            json_key_quote_utils::json_unescape_ctrlchars,
+            json_key_quote_utils::json_escape_html,
+            json_key_quote_utils::json_keys_to_snake_case,
+            json_key_quote_utils::json_remove_null_values,
+            json_key_quote_utils::json_strip_comments,
+            json_key_quote_utils::json_minify,
+            |json| json_key_quote_utils::json_pretty(json, Indent::Spaces(2)),
+            |json| json_key_quote_utils::json_sort_keys(json, false),
+            |json| json_key_quote_utils::json_flatten_keys(json, "."),
+        ];
+
+        for json in ["", "   \n", "\t", "42", "true", "null", "\"hello\""] {
+            for (i, convert) in conversions.iter().enumerate() {
+                assert_eq!(json, convert(json), "conversion {}: {:?}", i, json);
+            }
+        }
+
+        let scalars = [
+            ("\"va\nl\"", r#""va\nl""#),
+            (" 'va\tl' ", r#" 'va\tl' "#),
+            ("\"va\u{1}l\"\n", "\"va\\u0001l\"\n"),
+        ];
+        for (unescaped, escaped) in scalars {
+            let mut actual_stream_escaped = Vec::new();
+            json_key_quote_utils::convert_stream(
+                unescaped.as_bytes(),
+                &mut actual_stream_escaped,
+                Direction::AddKeyQuotes,
+                Quotes::DoubleQuote,
+            )
+            .unwrap();
+
+            assert_eq!(
+                escaped,
+                json_key_quote_utils::json_escape_ctrlchars(unescaped)
+            );
+            assert_eq!(
+                unescaped,
+                json_key_quote_utils::json_unescape_ctrlchars(escaped)
+            );
+            assert_eq!(escaped.as_bytes(), actual_stream_escaped);
+        }
+    }
+
+    #[test]
+    fn test_json_array_to_object() {
+        let cases = [
+            ("[]", Quotes::DoubleQuote, "{}"),
+            (" [ ] ", Quotes::DoubleQuote, "{}"),
+            (
+                r#"[["key", "value"]]"#,
+                Quotes::DoubleQuote,
+                r#"{"key": "value"}"#,
+            ),
+            (
+                r#"[["key", "value"], ['key2', [1, 2]], [3, {a: null}], ["it's", 'a "b"']]"#,
+                Quotes::SingleQuote,
+                r#"{'key': "value", 'key2': [1, 2], '3': {a: null}, 'it\'s': 'a "b"'}"#,
+            ),
+            (
+                r#"[["a\"b", 1], ['c', 2]]"#,
+                Quotes::DoubleQuote,
+                r#"{"a\"b": 1, "c": 2}"#,
+            ),
+            (
+                r#"[["key", 1], [2, 3]]"#,
+                Quotes::NoQuotes,
+                "{key: 1, 2: 3}",
+            ),
+        ];
+
+        for (json, quote_type, expected) in cases {
+            let actual = json_key_quote_utils::json_array_to_object(json, quote_type);
+
+            assert_eq!(Ok(expected.to_string()), actual, "json: {}", json);
+        }
+
+        let invalid = [
+            (r#"{"a": 1}"#, "The JSON is not a structurally valid array"),
+            (r#"[["a", 1"#, "The JSON is not a structurally valid array"),
+            (
+                r#"[["a", 1], "b"]"#,
+                "Item 1 is not an array of two elements",
+            ),
+            (r#"[["a"]]"#, "Item 0 is not an array of two elements"),
+            (r#"[["a", 1, 2]]"#, "Item 0 is not an array of two elements"),
+            (r#"[[["a"], 1]]"#, "The key of item 0 is not a scalar"),
+        ];
+
+        for (json, expected) in invalid {
+            let actual = json_key_quote_utils::json_array_to_object(json, Quotes::DoubleQuote);
+
+            assert_eq!(Err(expected.to_string()), actual, "json: {}", json);
+        }
+    }
+
+    #[test]
+    fn test_json_object_to_array() {
+        let cases = [
+            ("{}", "[]"),
+            (" { } ", "[]"),
+            (r#"{"key": "value"}"#, r#"[["key", "value"]]"#),
+            (
+                r#"{"s": 'str', n: -1.5e3, 'b': true, `f`: false, "z": null, o: {a: [1, {}]}, "e": []}"#,
+                r#"[["s", 'str'], ["n", -1.5e3], ["b", true], ["f", false], ["z", null], ["o", {a: [1, {}]}], ["e", []]]"#,
+            ),
+            (
+                r#"{'it\'s': 1, "a\"b": 2, "c\nd": 3}"#,
+                r#"[["it's", 1], ["a\"b", 2], ["c\nd", 3]]"#,
+            ),
+            ("[1, 2]", "[1, 2]"),
+            ("{a: 1", "{a: 1"),
+        ];
+
+        for (json, expected) in cases {
+            let actual = json_key_quote_utils::json_object_to_array(json);
+
+            assert_eq!(expected, actual, "json: {}", json);
+        }
+
+        let json = r#"{"a": 1, "b": {"c": [true, null]}, "d": "e"}"#;
+        let actual_round_trip = json_key_quote_utils::json_array_to_object(
+            &json_key_quote_utils::json_object_to_array(json),
+            Quotes::DoubleQuote,
+        );
+        assert_eq!(Ok(json.to_string()), actual_round_trip);
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_safe() {
+        let json = r#"{"my key": 1, "my_key": {'$ref': "a b", "a.b-c": [{"": 2, "with\"quote": 3}]}, " padded ": 4, `tick`: 5}"#;
+        let expected = r#"{"my key": 1, my_key: {$ref: "a b", a.b-c: [{"": 2, "with\"quote": 3}]}, " padded ": 4, tick: 5}"#;
+
+        let actual = json_key_quote_utils::json_remove_key_quotes_safe(json);
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            expected,
+            json_key_quote_utils::json_remove_key_quotes_safe(expected)
+        );
+    }
+
+    #[test]
+    fn test_json_remove_key_quotes_safe_round_trip() {
+        // An escaped backslash right before the colon trips up json_add_key_quotes,
+        // even if no key-quotes were removed, like in `{"\\:": 1, k: "v"}`:
+        let chars: Vec<char> = (' '..='~').filter(|&c| c != '\\').collect();
+        let keys = chars.iter().map(|c| c.to_string()).chain(
+            chars
+                .iter()
+                .flat_map(|a| chars.iter().map(move |b| format!("{}{}", a, b))),
+        );
+
+        for key in keys {
+            let key = key.replace('"', "\\\"");
+            let json = format!("{{\"{}\": {{\"{}\": [1]}}, \"k\": \"v\"}}", key, key);
+
+            let removed = json_key_quote_utils::json_remove_key_quotes_safe(&json);
+            let added = json_key_quote_utils::json_add_key_quotes(&removed, Quotes::DoubleQuote);
+
+            assert_eq!(json, added, "removed: {}", removed);
+        }
+
+        for key in ["true", "false", "null"] {
+            let json = format!("{{\"{}\": {{\"{}\": [1]}}, \"k\": \"v\"}}", key, key);
+            let expected = format!("{{\"{}\": {{\"{}\": [1]}}, k: \"v\"}}", key, key);
+
+            let removed = json_key_quote_utils::json_remove_key_quotes_safe(&json);
+            let added = json_key_quote_utils::json_add_key_quotes(&removed, Quotes::DoubleQuote);
+
+            assert_eq!(expected, removed);
+            assert_eq!(json, added);
+        }
+    }
+
+    #[test]
+    fn test_json_to_query_string() {
+        let json = r#"{"q": "a b&c=d/é", 'quote': "say \"hi\"\n", yes: true, "no": false, none: null, n: -1.5e3, "k y": ''}"#;
+        let expected = "q=a%20b%26c%3Dd%2F%C3%A9&quote=say%20%22hi%22%0A&yes=true&no=false&none=&n=-1.5e3&k%20y=";
+
+        let actual = json_key_quote_utils::json_to_query_string(json);
+        let actual_empty = json_key_quote_utils::json_to_query_string("{}");
+        let actual_array = json_key_quote_utils::json_to_query_string(r#"{"a": 1, "b": [1]}"#);
+        let actual_not_object = json_key_quote_utils::json_to_query_string("[1]");
+
+        assert_eq!(Ok(expected.to_string()), actual);
+        assert_eq!(Ok(String::new()), actual_empty);
+        assert_eq!(Err("Nested value for key: b".to_string()), actual_array);
+        assert!(actual_not_object.is_err());
+    }
+
+    #[test]
+    fn test_json_from_query_string() {
+        let qs = "a=b+c%20d&e%5B0%5D=%22f%5C%22%0A&g&&h=&bad=%zz%E2%82%AC";
+        let expected = r#"{'a': "b c d", 'e[0]': "\"f\\\"\n", 'g': true, 'h': "", 'bad': "%zz€"}"#;
+
+        let actual = json_key_quote_utils::json_from_query_string(qs, Quotes::SingleQuote);
+        let actual_empty = json_key_quote_utils::json_from_query_string("?", Quotes::DoubleQuote);
+
+        assert_eq!(expected, actual);
+        assert_eq!("{}", actual_empty);
+    }
+
+    #[test]
+    fn test_json_from_query_string_round_trip() {
+        let json = r#"{"q": "a b&c=d/é", "quote": "say \"hi\"\n", "empty": "", "k y+": "1+1"}"#;
+
+        let qs = json_key_quote_utils::json_to_query_string(json).unwrap();
+        let actual = json_key_quote_utils::json_from_query_string(&qs, Quotes::DoubleQuote);
+        let actual_qs = json_key_quote_utils::json_to_query_string(&actual).unwrap();
+
+        assert_eq!(json, actual);
+        assert_eq!(qs, actual_qs);
+    }
+
+    #[test]
+    fn test_json_replace_nonfinite_with_add_key_quotes() {
+        let json =
+            load_write_utils::load_json(Path::new("./test_resources/Test_nonfinite.json")).unwrap();
+        let expected_null = "{\n\t\"sensor\": \"thermo-1\",\n\t\"readings\": [21.5, null, 22.25, null],\n\t\"stats\": {\n\t\t\"min\": null,\n\t\t\"max\": null,\n\t\t\"mean\": null,\n\t\t\"note\": \"NaN readings are dropped, Infinity means overflow\"\n\t},\n\t'NaN': null\n}\n";
+        let expected_string = expected_null
+            .replacen("null", "\"NaN\"", 1)
+            .replacen("null", "\"-Infinity\"", 2)
+            .replacen("null", "\"Infinity\"", 1)
+            .replacen("null", "\"NaN\"", 1)
+            .replacen("null", "\"Infinity\"", 1);
+
+        let actual_null = json_key_quote_utils::json_add_key_quotes(
+            &json_key_quote_utils::json_replace_nonfinite(&json, NonFinitePolicy::Null).unwrap(),
+            Quotes::DoubleQuote,
+        );
+        let actual_string = json_key_quote_utils::json_add_key_quotes(
+            &json_key_quote_utils::json_replace_nonfinite(&json, NonFinitePolicy::StringLiteral)
+                .unwrap(),
+            Quotes::DoubleQuote,
+        );
+        let actual_error =
+            json_key_quote_utils::json_replace_nonfinite(&json, NonFinitePolicy::Error);
+        let actual_error_finite =
+            json_key_quote_utils::json_replace_nonfinite(&actual_null, NonFinitePolicy::Error);
+
+        assert_eq!(expected_null, actual_null);
+        assert_eq!(expected_string, actual_string);
+        assert_eq!(
+            Err("Non-finite number `NaN` at byte 41".to_string()),
+            actual_error
+        );
+        assert_eq!(Ok(actual_null.clone()), actual_error_finite);
+    }
+
+    #[test]
+    fn test_json_add_type_annotations() {
+        let json = r#"{s: 'x', "n": -1.5, b: false, z: null, o: {a: [1, {c: "d"}]}, arr: []}"#;
+        let expected = r#"{s: 'x', 's_type': "string", "n": -1.5, 'n_type': "number", b: false, 'b_type': "boolean", z: null, 'z_type': "null", o: {a: [1, {c: "d", 'c_type': "string"}], 'a_type': "array"}, 'o_type': "object", arr: [], 'arr_type': "array"}"#;
+
+        let actual = json_key_quote_utils::json_add_type_annotations(json, Quotes::SingleQuote);
+        let actual_second_pass =
+            json_key_quote_utils::json_add_type_annotations(&actual, Quotes::SingleQuote);
+        let actual_no_quotes =
+            json_key_quote_utils::json_add_type_annotations(r#"{"a": true}"#, Quotes::NoQuotes);
+        let actual_invalid =
+            json_key_quote_utils::json_add_type_annotations("{a: 1", Quotes::DoubleQuote);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+        assert_eq!(r#"{"a": true, a_type: "boolean"}"#, actual_no_quotes);
+        assert_eq!("{a: 1", actual_invalid);
+    }
+
+    #[test]
+    fn test_json_pretty_round_trip() {
+        let json = r#"{a b: 'c, d', "e": [1, {f: []}], g: {}}"#;
+        let expected = "{\n    a b: 'c, d',\n    \"e\": [\n        1,\n        {\n            f: []\n        }\n    ],\n    g: {}\n}";
+
+        let actual = json_key_quote_utils::json_pretty(json, Indent::Spaces(4));
+        let actual_second_pass = json_key_quote_utils::json_pretty(&actual, Indent::Spaces(4));
+        let actual_minified_pass = json_key_quote_utils::json_pretty(
+            &json_key_quote_utils::json_minify(&actual),
+            Indent::Spaces(4),
+        );
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+        assert_eq!(expected, actual_minified_pass);
+    }
+
+    #[test]
+    fn test_json_convert_value_quotes_invalid_targets() {
+        let json = r#"{'a': 'x', "b": "y", c: [`z`]}"#;
+
+        for target in [Quotes::Backtick, Quotes::NoQuotes, Quotes::Custom('§')] {
+            let actual = json_key_quote_utils::json_convert_value_quotes(json, target);
+
+            assert_eq!(json, actual, "target: {:?}", target);
+        }
+    }
+
+    #[test]
+    fn test_json_convert_value_quotes_both_quote_styles() {
+        let json = r#"{'a': 'it\'s "x"', "b": "it's \"y\"", c: ['\\', "z"]}"#;
+        let expected_double = r#"{'a': "it's \"x\"", "b": "it's \"y\"", c: ["\\", "z"]}"#;
+        let expected_single = r#"{'a': 'it\'s "x"', "b": 'it\'s "y"', c: ['\\', 'z']}"#;
+
+        let actual_double =
+            json_key_quote_utils::json_convert_value_quotes(json, Quotes::DoubleQuote);
+        let actual_double_second_pass =
+            json_key_quote_utils::json_convert_value_quotes(&actual_double, Quotes::DoubleQuote);
+        let actual_single =
+            json_key_quote_utils::json_convert_value_quotes(&actual_double, Quotes::SingleQuote);
+
+        assert_eq!(expected_double, actual_double);
+        assert_eq!(expected_double, actual_double_second_pass);
+        assert_eq!(expected_single, actual_single);
+    }
+
+    #[test]
+    fn test_json_sort_keys_nested_and_in_arrays() {
+        let json = "{\n  z: [{'y': 1, \"x\": {b: 2, a: 3}}],\n  \"B\": 'b',\n  a: null\n}";
+        let expected = "{\n  \"B\": 'b',\n  a: null,\n  z: [{\"x\": {a: 3, b: 2}, 'y': 1}]\n}";
+        let expected_case_insensitive =
+            "{\n  a: null,\n  \"B\": 'b',\n  z: [{\"x\": {a: 3, b: 2}, 'y': 1}]\n}";
+
+        let actual = json_key_quote_utils::json_sort_keys(json, false);
+        let actual_second_pass = json_key_quote_utils::json_sort_keys(&actual, false);
+        let actual_case_insensitive = json_key_quote_utils::json_sort_keys(json, true);
+
+        assert_eq!(expected, actual);
+        assert_eq!(expected, actual_second_pass);
+        assert_eq!(expected_case_insensitive, actual_case_insensitive);
     }
 
     #[test]
-    fn test_json_add_key_quotes_single_quote_add_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
-
-        let json =
-            r#"{"#.to_string() + &supported_key_chars + r#": '"# + &supported_value_chars + r#"'}"#;
-        let expected = r#"{'"#.to_string()
-            + &supported_key_chars
-            + r#"': '"#
-            + &supported_value_chars
-            + r#"'}"#;
+    fn test_json_escape_ctrlchars_backspace_form_feed() {
+        let json = "{\"ke\u{8}y\": \"va\u{8}l\u{c}\", 'o\u{c}ther': {\"a\": 'b\u{c}'}}";
+        let expected = r#"{"key": "va\bl\f", 'other': {"a": 'b\f'}}"#;
 
-        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::SingleQuote);
-        let actual_second_pass =
-            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::SingleQuote);
+        let actual = json_key_quote_utils::json_escape_ctrlchars(json);
+        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
 
         assert_eq!(expected, actual);
         assert_eq!(expected, actual_second_pass);
     }
 
     #[test]
-    fn test_json_add_key_quotes_double_quote_add_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
-
-        let json =
-            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
-        let expected = r#"{""#.to_string()
-            + &supported_key_chars
-            + r#"": ""#
-            + &supported_value_chars
-            + r#""}"#;
+    fn test_json_unescape_ctrlchars_backspace_form_feed() {
+        let json = r#"{ke\by: "va\bl\f", o\fther: {a: 'b\f'}}"#;
+        let expected = "{key: \"va\u{8}l\u{c}\", other: {a: 'b\u{c}'}}";
 
-        let actual = json_key_quote_utils::json_add_key_quotes(&json, Quotes::DoubleQuote);
-        let actual_second_pass =
-            json_key_quote_utils::json_add_key_quotes(&actual, Quotes::DoubleQuote);
+        let actual = json_key_quote_utils::json_unescape_ctrlchars(json);
+        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
 
         assert_eq!(expected, actual);
         assert_eq!(expected, actual_second_pass);
     }
 
     #[test]
-    fn test_json_remove_key_quotes_single_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+    fn test_json_escape_ctrlchars_unescape_ctrlchars_null_character() {
+        let json = "{\"ke\0y\": \"va\0l\", other: 'bin\0\0ary'}";
+        let expected_escaped = r#"{"key": "va\u0000l", other: 'bin\u0000\u0000ary'}"#;
+        let expected_unescaped = "{\"key\": \"va\0l\", other: 'bin\0\0ary'}";
 
-        let json = r#"{'"#.to_string()
-            + &supported_key_chars
-            + r#"': ""#
-            + &supported_value_chars
-            + r#""}"#;
-        let expected =
-            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+        let actual_escaped = json_key_quote_utils::json_escape_ctrlchars(json);
+        let actual_escaped_second_pass =
+            json_key_quote_utils::json_escape_ctrlchars(&actual_escaped);
+        let actual_unescaped = json_key_quote_utils::json_unescape_ctrlchars(&actual_escaped);
 
-        let actual = json_key_quote_utils::json_remove_key_quotes(&json);
-        let actual_second_pass = json_key_quote_utils::json_remove_key_quotes(&actual);
+        assert_eq!(expected_escaped, actual_escaped);
+        assert_eq!(expected_escaped, actual_escaped_second_pass);
+        assert_eq!(expected_unescaped, actual_unescaped);
+        assert_eq!(
+            json_key_quote_utils::json_unescape_ctrlchars("{ke\\u0000y: 1}"),
+            "{key: 1}"
+        );
+    }
+
+    #[test]
+    fn test_json_conversions_with_stats() {
+        let json = "{a: \"b\nc\", 'd': {e: 'f\tg', \"h\": [\"i\"]}}";
+
+        let (json_added, stats_added) =
+            json_key_quote_utils::json_add_key_quotes_with_stats(json, Quotes::DoubleQuote);
+        assert_eq!(
+            ConversionStats {
+                keys_quoted: 2,
+                ..ConversionStats::default()
+            },
+            stats_added
+        );
+
+        let (json_escaped, stats_escaped) =
+            json_key_quote_utils::json_escape_ctrlchars_with_stats(&json_added);
+        assert_eq!(2, stats_escaped.values_escaped);
+        assert_eq!(
+            0,
+            json_key_quote_utils::json_escape_ctrlchars_with_stats(&json_escaped)
+                .1
+                .values_escaped
+        );
+
+        let (json_unescaped, stats_unescaped) =
+            json_key_quote_utils::json_unescape_ctrlchars_with_stats(&json_escaped);
+        assert_eq!(2, stats_unescaped.values_unescaped);
+
+        let (json_removed, stats_removed) = json_key_quote_utils::json_remove_key_quotes_with_stats(
+            &json_unescaped,
+            Quotes::DoubleQuote,
+        );
+        assert_eq!(4, stats_removed.keys_unquoted);
+        assert_eq!("{a: \"b\nc\", d: {e: 'f\tg', h: [\"i\"]}}", json_removed);
+
+        let mut stats = stats_added;
+        stats += stats_escaped;
+        stats += stats_removed;
+        assert_eq!(
+            ConversionStats {
+                keys_quoted: 2,
+                keys_unquoted: 4,
+                values_escaped: 2,
+                values_unescaped: 0,
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn test_json_rename_keys_only_in_key_position() {
+        let map = HashMap::from([
+            ("a.b".to_string(), "$c".to_string()),
+            ("d".to_string(), "e".to_string()),
+        ]);
+        let json = r#"{"a.b": "a.b", 'd': {d: 'd', axb: 1}, "e": [{'a.b': null}]}"#;
+        let expected = r#"{"$c": "a.b", 'e': {e: 'd', axb: 1}, "e": [{'$c': null}]}"#;
+
+        let (actual, collisions) = json_key_quote_utils::json_rename_keys(json, &map);
 
         assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+        assert_eq!(vec!["e"], collisions);
     }
 
     #[test]
-    fn test_json_remove_key_quotes_double_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+    fn test_json_merge_flat_objects() {
+        let base = r#"{"a": 1, b: 'x'}"#;
+        let overlay = r#"{'b': "y", "c": null}"#;
 
-        let json = r#"{""#.to_string()
-            + &supported_key_chars
-            + r#"": ""#
-            + &supported_value_chars
-            + r#""}"#;
-        let expected =
-            r#"{"#.to_string() + &supported_key_chars + r#": ""# + &supported_value_chars + r#""}"#;
+        let take_left = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::TakeLeft);
+        let take_right = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::TakeRight);
+        let error = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::Error);
 
-        let actual = json_key_quote_utils::json_remove_key_quotes(&json);
-        let actual_second_pass = json_key_quote_utils::json_remove_key_quotes(&actual);
+        assert_eq!(Ok(r#"{"a": 1, b: 'x', "c": null}"#.to_string()), take_left);
+        assert_eq!(Ok(r#"{"a": 1, b: "y", "c": null}"#.to_string()), take_right);
+        assert_eq!(Err("Conflicting key: b".to_string()), error);
+    }
 
-        assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+    #[test]
+    fn test_json_merge_nested_objects() {
+        let base = r#"{"a": {"b": {"c": 1, "d": [1]}}, "e": true}"#;
+        let overlay = r#"{"a": {"b": {"d": [2], "f": {}}, "g": 3}}"#;
+
+        let take_left = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::TakeLeft);
+        let take_right = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::TakeRight);
+        let error = json_key_quote_utils::json_merge(base, overlay, MergeStrategy::Error);
+        let invalid = json_key_quote_utils::json_merge(base, "[1]", MergeStrategy::TakeLeft);
+
+        assert_eq!(
+            Ok(r#"{"a": {"b": {"c": 1, "d": [1], "f": {}}, "g": 3}, "e": true}"#.to_string()),
+            take_left
+        );
+        assert_eq!(
+            Ok(r#"{"a": {"b": {"c": 1, "d": [2], "f": {}}, "g": 3}, "e": true}"#.to_string()),
+            take_right
+        );
+        assert_eq!(Err("Conflicting key: d".to_string()), error);
+        assert!(invalid.is_err());
     }
 
     #[test]
-    fn test_json_escape_ctrlchars_single_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+    fn test_json_transform_keys_edge_cases() {
+        let json = r#"{"a": {'b': [{c: "d: e"}]}, f: 1}"#;
 
-        let key = supported_key_chars.to_string();
-        let value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
+        let actual_empty =
+            json_key_quote_utils::json_transform_keys(json, |_| String::new(), Quotes::SingleQuote);
+        let actual_quotes = json_key_quote_utils::json_transform_keys(
+            json,
+            |key| format!("{}\"'\\\"", key),
+            Quotes::DoubleQuote,
+        );
+        let actual_nested = json_key_quote_utils::json_transform_keys(
+            json,
+            |key| key.to_uppercase(),
+            Quotes::DoubleQuote,
+        );
 
-        let expected_key = supported_key_chars.to_string();
-        let expected_value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+        assert_eq!(r#"{"": {'': [{'': "d: e"}]}, '': 1}"#, actual_empty);
+        assert_eq!(
+            r#"{"a\"'\"": {'b"\'\"': [{c"'\": "d: e"}]}, f"'\": 1}"#,
+            actual_quotes
+        );
+        assert_eq!(r#"{"A": {'B': [{C: "d: e"}]}, F: 1}"#, actual_nested);
+    }
 
-        let json = r#"{'"#.to_string() + &key + r#"': '"# + &value + r#"'}"#;
-        let expected = r#"{'"#.to_string() + &expected_key + r#"': '"# + &expected_value + r#"'}"#;
+    #[test]
+    fn test_json_deduplicate_keys_keeps_correct_occurrence() {
+        let json = "{\n  'a': 1,\n  b: {c: 2, \"c\": 3},\n  \"a\": [{a: 4, a: 5}],\n  d: 6\n}";
+        let expected_first = "{\n  'a': 1,\n  b: {c: 2},\n  d: 6\n}";
+        let expected_last = "{\n  b: {\"c\": 3},\n  \"a\": [{a: 5}],\n  d: 6\n}";
 
-        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+        let actual_first = json_key_quote_utils::json_deduplicate_keys(json, KeepDuplicate::First);
+        let actual_last = json_key_quote_utils::json_deduplicate_keys(json, KeepDuplicate::Last);
 
-        assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+        assert_eq!(expected_first, actual_first);
+        assert_eq!(expected_last, actual_last);
+        assert_eq!(4, json_key_quote_utils::json_count_keys(&actual_first));
+        assert_eq!(5, json_key_quote_utils::json_count_keys(&actual_last));
     }
 
     #[test]
-    fn test_json_escape_ctrlchars_double_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+    fn test_json_convert_key_case_acronyms_and_digits() {
+        let json = r#"{"HTTPServer": 1, 'v2Config': 2, userId: 3, "already_snake": "HTTPServer"}"#;
 
-        let key = supported_key_chars.to_string();
-        let value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
+        let expected_snake =
+            r#"{"http_server": 1, 'v2_config': 2, user_id: 3, "already_snake": "HTTPServer"}"#;
+        let expected_camel =
+            r#"{"httpServer": 1, 'v2Config': 2, userId: 3, "alreadySnake": "HTTPServer"}"#;
+        let expected_pascal =
+            r#"{"HttpServer": 1, 'V2Config': 2, UserId: 3, "AlreadySnake": "HTTPServer"}"#;
+        let expected_kebab =
+            r#"{"http-server": 1, 'v2-config': 2, user-id: 3, "already-snake": "HTTPServer"}"#;
+        let expected_screaming_snake =
+            r#"{"HTTP_SERVER": 1, 'V2_CONFIG': 2, USER_ID: 3, "ALREADY_SNAKE": "HTTPServer"}"#;
 
-        let expected_key = supported_key_chars.to_string();
-        let expected_value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+        assert_eq!(
+            expected_snake,
+            json_key_quote_utils::json_convert_key_case(json, KeyCase::Snake)
+        );
+        assert_eq!(
+            expected_camel,
+            json_key_quote_utils::json_convert_key_case(json, KeyCase::Camel)
+        );
+        assert_eq!(
+            expected_pascal,
+            json_key_quote_utils::json_convert_key_case(json, KeyCase::Pascal)
+        );
+        assert_eq!(
+            expected_kebab,
+            json_key_quote_utils::json_convert_key_case(json, KeyCase::Kebab)
+        );
+        assert_eq!(
+            expected_screaming_snake,
+            json_key_quote_utils::json_convert_key_case(json, KeyCase::ScreamingSnake)
+        );
+    }
 
-        let json = r#"{""#.to_string() + &key + r#"": ""# + &value + r#""}"#;
-        let expected = r#"{""#.to_string() + &expected_key + r#"": ""# + &expected_value + r#""}"#;
+    #[test]
+    fn test_json_keys_to_snake_case_camel_case() {
+        let json = r#"{"userId": 1, firstName: "firstName", 'HTTPServer': {already_snake: 'PascalCase'}, XMLHttpRequest: [true]}"#;
+        let expected_snake = r#"{"user_id": 1, first_name: "firstName", 'http_server': {already_snake: 'PascalCase'}, xml_http_request: [true]}"#;
+        let expected_camel = r#"{"userId": 1, firstName: "firstName", 'httpServer': {alreadySnake: 'PascalCase'}, xmlHttpRequest: [true]}"#;
 
-        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+        let actual_snake = json_key_quote_utils::json_keys_to_snake_case(json);
+        let actual_camel = json_key_quote_utils::json_keys_to_camel_case(&actual_snake);
 
-        assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+        assert_eq!(expected_snake, actual_snake);
+        assert_eq!(
+            expected_snake,
+            json_key_quote_utils::json_keys_to_snake_case(&actual_snake)
+        );
+        assert_eq!(expected_camel, actual_camel);
+        assert_eq!(
+            expected_camel,
+            json_key_quote_utils::json_keys_to_camel_case(&actual_camel)
+        );
     }
 
     #[test]
-    fn test_json_escape_ctrlchars_unquoted_keys_supported_characters() {
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+    fn test_json_convert_key_case_composes_with_key_quotes() {
+        let json = r#"{userName:"userName",HTTPServer:{v2Config:1}}"#;
+        let expected = r#"{"user_name":"userName","http_server":{"v2_config":1}}"#;
 
-        let key = SUPPORTED_KEY_CHARS
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
-        let value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
+        let case_first = json_key_quote_utils::json_add_key_quotes(
+            &json_key_quote_utils::json_convert_key_case(json, KeyCase::Snake),
+            Quotes::DoubleQuote,
+        );
+        let quotes_first = json_key_quote_utils::json_convert_key_case(
+            &json_key_quote_utils::json_add_key_quotes(json, Quotes::DoubleQuote),
+            KeyCase::Snake,
+        );
+
+        assert_eq!(expected, case_first);
+        assert_eq!(expected, quotes_first);
+        assert_eq!(
+            json_key_quote_utils::json_remove_key_quotes(expected),
+            json_key_quote_utils::json_convert_key_case(
+                &json_key_quote_utils::json_remove_key_quotes(expected),
+                KeyCase::Snake
             )
-            .replacen("B", r#"B	"#, 1);
+        );
+    }
 
-        let expected_value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+    #[test]
+    fn test_json_validate_structure() {
+        let valid = r#"{a: [{"b": "}]"}, 'c': '[{'], d: {}}"#;
+        let mismatched_brace = r#"{a: {b: 1]}"#;
+        let unexpected_brace = r#"{a: 1}}"#;
+        let unclosed_array = r#"{a: [1, "]"}"#;
 
-        let json = r#"{"#.to_string() + &key + r#": ""# + &value + r#""}"#;
-        let expected = r#"{"#.to_string() + &key + r#": ""# + &expected_value + r#""}"#;
+        assert_eq!(Ok(()), json_key_quote_utils::json_validate_structure(valid));
+        assert_eq!(
+            Err("Mismatched `]` at byte 9, expected `}`".to_string()),
+            json_key_quote_utils::json_validate_structure(mismatched_brace)
+        );
+        assert_eq!(
+            Err("Unexpected `}` at byte 6".to_string()),
+            json_key_quote_utils::json_validate_structure(unexpected_brace)
+        );
+        assert_eq!(
+            Err("Mismatched `}` at byte 11, expected `]`".to_string()),
+            json_key_quote_utils::json_validate_structure(unclosed_array)
+        );
+        assert_eq!(
+            Err("Unclosed `[` at byte 4".to_string()),
+            json_key_quote_utils::json_validate_structure(r#"{a: [1, 2"#)
+        );
+    }
 
-        let actual = json_key_quote_utils::json_escape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_escape_ctrlchars(&actual);
+    #[test]
+    fn test_json_find_duplicate_keys_per_object() {
+        let json = r#"{"a": {"b": 1, b: 2}, "c": [{"a": 1}, {"a": 2, "a": 3}], 'a': null}"#;
+        let expected = vec![
+            DuplicateKey {
+                key: "a".to_string(),
+                offsets: vec![1, 57],
+            },
+            DuplicateKey {
+                key: "b".to_string(),
+                offsets: vec![7, 15],
+            },
+            DuplicateKey {
+                key: "a".to_string(),
+                offsets: vec![39, 47],
+            },
+        ];
+
+        let actual = json_key_quote_utils::json_find_duplicate_keys(json);
+        let deduplicated = json_key_quote_utils::json_deduplicate_keys(json, KeepDuplicate::First);
+
+        assert_eq!(expected, actual);
+        assert_eq!(
+            r#"{"a": {"b": 1}, "c": [{"a": 1}, {"a": 2}]}"#,
+            deduplicated
+        );
+        assert!(json_key_quote_utils::json_find_duplicate_keys(&deduplicated).is_empty());
+    }
+
+    #[test]
+    fn test_json_escape_html_only_values() {
+        let json = r#"{<a>: "<b> & 'c'", 'd&': ['e"f', "\\<", "\u003C"]}"#;
+        let expected = r#"{<a>: "\u003Cb\u003E \u0026 \u0027c\u0027", 'd&': ['e\u0022f', "\\\u003C", "\u003C"]}"#;
+        let expected_unescaped = r#"{<a>: "<b> & 'c'", 'd&': ['e"f', "\\<", "<"]}"#;
+
+        let actual = json_key_quote_utils::json_escape_html(json);
+        let actual_second_pass = json_key_quote_utils::json_escape_html(&actual);
+        let actual_unescaped = json_key_quote_utils::json_unescape_html(&actual);
+        let actual_unescaped_second_pass =
+            json_key_quote_utils::json_unescape_html(&actual_unescaped);
 
         assert_eq!(expected, actual);
         assert_eq!(expected, actual_second_pass);
+        assert_eq!(expected_unescaped, actual_unescaped);
+        assert_eq!(expected_unescaped, actual_unescaped_second_pass);
     }
 
     #[test]
-    fn test_json_unescape_ctrlchars_single_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"'"#, r#"\'"#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"'"#, r#"\'"#, 1);
+    fn test_json_diff_keys_add_key_quotes() {
+        let json =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
 
-        let key = supported_key_chars.to_string();
-        let value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+        let actual = json_key_quote_utils::json_diff_keys(
+            &json,
+            &json_key_quote_utils::json_add_key_quotes(&json, Quotes::SingleQuote),
+        );
 
-        let expected_key = supported_key_chars.to_string();
-        let expected_value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
+        assert!(actual.is_empty());
+        assert!(!actual.unchanged.is_empty());
+    }
 
-        let json = r#"{"#.to_string() + &key + r#": '"# + &value + r#"'}"#;
-        let expected = r#"{"#.to_string() + &expected_key + r#": '"# + &expected_value + r#"'}"#;
+    #[test]
+    fn test_json_diff_keys_removed_key() {
+        let before = r#"{"a": 1, "b": [{"c": 2, "d": 3}]}"#;
+        let after = r#"{"a": 1, "b": [{"c": 2}]}"#;
+        let expected = JsonKeyDiff {
+            added: vec![],
+            removed: vec!["d".to_string()],
+            unchanged: vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        };
 
-        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+        let actual = json_key_quote_utils::json_diff_keys(before, after);
 
         assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+        assert!(!actual.is_empty());
     }
 
     #[test]
-    fn test_json_unescape_ctrlchars_double_quoted_supported_characters() {
-        let supported_key_chars = SUPPORTED_KEY_CHARS.replacen(r#"""#, r#"\""#, 1);
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+    fn test_json_replace_value_value_types() {
+        let json = r#"{'a': "it's, \"1\"", b: {a: 2.5e3}, "c": [{"a": true}, {a: null}], a: {"a": [1, {a: 2}]}, d: 'a'}"#;
+        let expected = r#"{'a': 0, b: {a: 0}, "c": [{"a": 0}, {a: 0}], a: 0, d: 'a'}"#;
 
-        let key = supported_key_chars.to_string();
-        let value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+        let actual = json_key_quote_utils::json_replace_value(json, "a", "0");
+        let actual_nested_array = json_key_quote_utils::json_replace_value(
+            r#"{a: [[1], [2, [3]]], b: 1}"#,
+            "a",
+            r#""x""#,
+        );
+        let actual_unclosed = json_key_quote_utils::json_replace_value(r#"{a: 1, b: [1"#, "b", "0");
 
-        let expected_key = supported_key_chars.to_string();
-        let expected_value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
+        assert_eq!(expected, actual);
+        assert_eq!(r#"{a: "x", b: 1}"#, actual_nested_array);
+        assert_eq!(r#"{a: 1, b: [1"#, actual_unclosed);
+    }
 
-        let json = r#"{"#.to_string() + &key + r#": ""# + &value + r#""}"#;
-        let expected = r#"{"#.to_string() + &expected_key + r#": ""# + &expected_value + r#""}"#;
+    #[test]
+    fn test_json_wrap_in_object_unwrap_object() {
+        let json = "\n  [1, {\"a\": 'b'}]\n";
+        let expected = r#"{'it\'s': [1, {"a": 'b'}]}"#;
 
-        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+        let actual = json_key_quote_utils::json_wrap_in_object(json, "it's", Quotes::SingleQuote);
+        let actual_unwrapped = json_key_quote_utils::json_unwrap_object(&actual, "it\\'s");
 
         assert_eq!(expected, actual);
-        assert_eq!(expected, actual_second_pass);
+        assert_eq!(Some(r#"[1, {"a": 'b'}]"#.to_string()), actual_unwrapped);
+        assert_eq!(
+            None,
+            json_key_quote_utils::json_unwrap_object(&actual, "other")
+        );
+        assert_eq!(
+            None,
+            json_key_quote_utils::json_unwrap_object("[1, 2]", "a")
+        );
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_json_unescape_ctrlchars_double_quoted_keys_supported_characters() {
-        let supported_value_chars = SUPPORTED_VALUE_CHARS.replacen(r#"""#, r#"\""#, 1);
+    fn test_json_parse_relaxed_fixtures() {
+        let json_with_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_with_keyquotes.json"))
+                .unwrap();
+        let json_without_keyquotes =
+            load_write_utils::load_json(Path::new("./test_resources/Test_without_keyquotes.json"))
+                .unwrap();
 
-        let key = SUPPORTED_KEY_CHARS
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
-        let value = supported_value_chars
-            .replacen("A", r#"A\n"#, 1)
-            .replacen("B", r#"B\t"#, 1);
+        let expected: serde_json::Value =
+            serde_json::from_str(json_with_keyquotes.trim_start_matches('\u{feff}')).unwrap();
 
-        let expected_value = supported_value_chars
-            .replacen(
-                "A", r#"A
-"#, 1,
-            )
-            .replacen("B", r#"B	"#, 1);
+        let actual = json_key_quote_utils::json_parse_relaxed(
+            json_without_keyquotes.trim_start_matches('\u{feff}'),
+        )
+        .unwrap();
+        let actual_single_quoted =
+            json_key_quote_utils::json_parse_relaxed(r#"{'a': 'it\'s', b: "\"c\""}"#).unwrap();
 
-        let json = r#"{""#.to_string() + &key + r#"": ""# + &value + r#""}"#;
-        let expected = r#"{""#.to_string() + &key + r#"": ""# + &expected_value + r#""}"#;
+        assert_eq!(expected, actual);
+        assert_eq!(
+            serde_json::json!({"a": "it's", "b": "\"c\""}),
+            actual_single_quoted
+        );
+    }
 
-        let actual = json_key_quote_utils::json_unescape_ctrlchars(&json);
-        let actual_second_pass = json_key_quote_utils::json_unescape_ctrlchars(&actual);
+    #[test]
+    fn test_json_escape_forward_slash_only_values() {
+        let json = r#"{a/b: "http://example.com", 'c/d': ['e/f', "\\/"]}"#;
+        let expected = r#"{a/b: "http:\/\/example.com", 'c/d': ['e\/f', "\\\/"]}"#;
+
+        let actual = json_key_quote_utils::json_escape_forward_slash(json);
+        let actual_second_pass = json_key_quote_utils::json_escape_forward_slash(&actual);
+        let actual_unescaped = json_key_quote_utils::json_unescape_forward_slash(&actual);
+        let actual_unescaped_second_pass =
+            json_key_quote_utils::json_unescape_forward_slash(&actual_unescaped);
 
         assert_eq!(expected, actual);
         assert_eq!(expected, actual_second_pass);
+        assert_eq!(json, actual_unescaped);
+        assert_eq!(json, actual_unescaped_second_pass);
     }
 }
@@ -7,39 +7,586 @@
 //! It is recommended to use the [JsonKeyQuoteConverter] builder,
 //! but using the core functions in [json_key_quote_utils] is possible too.
 
+#[cfg(not(any(feature = "regex", feature = "lite")))]
+compile_error!("Either the `regex` (default) or the `lite` feature must be enabled.");
+
+use std::collections::HashMap;
+use std::fmt;
+
 pub mod json_key_quote_utils;
 pub mod load_write_utils;
+mod scanner;
+mod stream;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// The quotes to use for the JSON keys.
 ///
 /// This does not affect existing single-quoted or double-quoted keys in JSON.
 ///
 /// The default value is [Quotes::DoubleQuote].
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum Quotes {
+    #[default]
     DoubleQuote,
     SingleQuote,
+    Backtick,
+    /// No key-quotes: [json_key_quote_utils::json_add_key_quotes] removes the key-quotes instead.
+    NoQuotes,
+    /// A custom key-quote character, preferably created with [Quotes::custom].
+    Custom(char),
 }
 
 impl Quotes {
-    fn as_str(&self) -> &'static str {
+    /// Returns a [Quotes::Custom] with the character as key-quotes.
+    ///
+    /// Returns an [InvalidQuoteChar] error if the character is a supported character in JSON keys,
+    /// or a character which is part of the JSON structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `c` - The key-quote character.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{InvalidQuoteChar, Quotes};
+    ///
+    /// assert_eq!(Quotes::custom('§'), Ok(Quotes::Custom('§')));
+    /// assert_eq!(Quotes::custom('|'), Err(InvalidQuoteChar('|')));
+    /// ```
+    pub fn custom(c: char) -> Result<Quotes, InvalidQuoteChar> {
+        if json_key_quote_utils::is_supported_key_char(c) || "{}[]:,".contains(c) {
+            return Err(InvalidQuoteChar(c));
+        }
+
+        Ok(Quotes::Custom(c))
+    }
+
+    fn as_char(&self) -> Option<char> {
+        match self {
+            Quotes::DoubleQuote => Some('"'),
+            Quotes::SingleQuote => Some('\''),
+            Quotes::Backtick => Some('`'),
+            Quotes::NoQuotes => None,
+            Quotes::Custom(c) => Some(*c),
+        }
+    }
+}
+
+impl fmt::Display for Quotes {
+    /// Formats the quotes as their name, which can be parsed back with [str::parse].
+    ///
+    /// A [Quotes::Custom] is formatted as its character.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quotes::DoubleQuote => write!(f, "double"),
+            Quotes::SingleQuote => write!(f, "single"),
+            Quotes::Backtick => write!(f, "backtick"),
+            Quotes::NoQuotes => write!(f, "none"),
+            Quotes::Custom(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+impl std::str::FromStr for Quotes {
+    type Err = ParseQuotesError;
+
+    /// Parses the quotes from their name or their character, case-insensitively.
+    ///
+    /// Accepts `double` or `"`, `single` or `'`, `backtick` or `` ` `` and `none`.
+    /// Any other single character is parsed with [Quotes::custom].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{Quotes};
+    ///
+    /// assert_eq!("Double".parse(), Ok(Quotes::DoubleQuote));
+    /// assert_eq!("'".parse(), Ok(Quotes::SingleQuote));
+    /// assert!("triple".parse::<Quotes>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "double" | "\"" => Ok(Quotes::DoubleQuote),
+            "single" | "'" => Ok(Quotes::SingleQuote),
+            "backtick" | "`" => Ok(Quotes::Backtick),
+            "none" => Ok(Quotes::NoQuotes),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => {
+                        Quotes::custom(c).map_err(|_| ParseQuotesError(s.to_string()))
+                    }
+                    _ => Err(ParseQuotesError(s.to_string())),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Quotes {
+    /// Serializes the quotes as their lowercase name, see [fmt::Display].
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Quotes {
+    /// Deserializes the quotes from their name or their character, see [std::str::FromStr].
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// The error returned when parsing [Quotes] from an unknown name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseQuotesError(pub String);
+
+impl fmt::Display for ParseQuotesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid quotes `{}`, expected `double`, `single`, `backtick`, `none` or a custom quote character",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseQuotesError {}
+
+/// The error returned by [Quotes::custom] for a character which can not be used as key-quotes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidQuoteChar(pub char);
+
+impl fmt::Display for InvalidQuoteChar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` can not be used as key-quotes", self.0)
+    }
+}
+
+impl std::error::Error for InvalidQuoteChar {}
+
+/// The indentation to use when pretty-printing the JSON.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Indent {
+    /// Indent with the given amount of spaces per level.
+    Spaces(usize),
+    /// Indent with one tab per level.
+    Tab,
+}
+
+impl Indent {
+    fn as_string(&self) -> String {
+        match self {
+            Indent::Spaces(amount) => " ".repeat(*amount),
+            Indent::Tab => "\t".to_string(),
+        }
+    }
+}
+
+/// The case to convert the JSON keys to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum KeyCase {
+    /// `snake_case`
+    Snake,
+    /// `camelCase`
+    Camel,
+    /// `PascalCase`
+    Pascal,
+    /// `kebab-case`
+    Kebab,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnake,
+}
+
+/// The dialect of the JSON string, which decides which values are recognized when adding key-quotes.
+///
+/// The default value is [Dialect::Json].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Dialect {
+    /// JSON, with the relaxations supported by this crate.
+    #[default]
+    Json,
+    /// JSON5, which adds the numbers with a leading `+`, `Infinity` and `NaN` to the recognized values.
+    ///
+    /// Hexadecimal numbers and numbers with a leading or trailing decimal point are recognized in all dialects.
+    Json5,
+    /// The output of the mongo shell, which converts the helpers like `ObjectId("x")` to MongoDB extended JSON
+    /// like `{"$oid": "x"}` before adding key-quotes, see [json_key_quote_utils::json_convert_mongo_shell_values].
+    MongoShell,
+}
+
+/// The error returned when the JSON string is not valid JSON according to `serde_json`.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct ValidationError {
+    /// The error message of `serde_json`.
+    pub message: String,
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    /// The column of the error, starting at 1.
+    pub column: usize,
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for ValidationError {}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for ValidationError {
+    fn from(err: serde_json::Error) -> Self {
+        ValidationError {
+            message: err.to_string(),
+            line: err.line(),
+            column: err.column(),
+        }
+    }
+}
+
+/// The error returned by [from_relaxed_str].
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum RelaxedError {
+    /// The relaxed JSON string could not be converted to valid JSON.
+    Conversion(String),
+    /// The converted JSON string could not be deserialized by `serde_json`.
+    Deserialize(ValidationError),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for RelaxedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Quotes::DoubleQuote => "\"",
-            Quotes::SingleQuote => "'",
+            RelaxedError::Conversion(message) => write!(f, "{}", message),
+            RelaxedError::Deserialize(err) => write!(f, "{}", err),
         }
     }
 }
 
-impl Default for Quotes {
-    fn default() -> Self {
-        Quotes::DoubleQuote
+#[cfg(feature = "serde")]
+impl std::error::Error for RelaxedError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RelaxedError::Conversion(_) => None,
+            RelaxedError::Deserialize(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<ValidationError> for RelaxedError {
+    fn from(err: ValidationError) -> Self {
+        RelaxedError::Deserialize(err)
+    }
+}
+
+/// Deserializes relaxed JSON into `T`.
+///
+/// The JSON string is made valid JSON first, like [json_key_quote_utils::json_parse_relaxed] does.
+///
+/// Only available with the `serde` feature.
+///
+/// # Arguments
+///
+/// * `json` - The relaxed JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{RelaxedError};
+/// use serde::Deserialize;
+///
+/// #[derive(Debug, Deserialize)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let config: Config = json_keyquotes_convert::from_relaxed_str("{name: 'first\nsecond', retries: 3}").unwrap();
+/// assert_eq!(config.name, "first\nsecond");
+/// assert_eq!(config.retries, 3);
+///
+/// let err = json_keyquotes_convert::from_relaxed_str::<Config>("{name: 'val'").unwrap_err();
+/// assert!(matches!(err, RelaxedError::Conversion(_)));
+///
+/// let err = json_keyquotes_convert::from_relaxed_str::<Config>("{name: 'val'}").unwrap_err();
+/// assert!(matches!(err, RelaxedError::Deserialize(_)));
+/// ```
+#[cfg(feature = "serde")]
+pub fn from_relaxed_str<T: serde::de::DeserializeOwned>(json: &str) -> Result<T, RelaxedError> {
+    json_key_quote_utils::json_validate_structure(json).map_err(RelaxedError::Conversion)?;
+    let json_normalized = json_key_quote_utils::json_normalize(json);
+
+    serde_json::from_str(&json_normalized)
+        .map_err(|err| RelaxedError::Deserialize(ValidationError::from(err)))
+}
+
+/// The style of the relaxed JSON string returned by [to_relaxed_string].
+#[cfg(feature = "serde")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RelaxedStyle {
+    /// Whether the JSON string values should be single- or double-quoted.
+    pub quotes: Quotes,
+    /// Whether the JSON string should be pretty-printed instead of compact.
+    pub pretty: bool,
+}
+
+/// Serializes `value` into a relaxed JSON string.
+///
+/// The key-quotes are removed and the ctrl-characters in the string values are unescaped.
+/// Passing the result to [from_relaxed_str] yields an equal value.
+///
+/// Only available with the `serde` feature.
+///
+/// # Arguments
+///
+/// * `value` - The value to serialize.
+/// * `style` - The style of the relaxed JSON string.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{Quotes, RelaxedStyle};
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Config {
+///     name: String,
+///     retries: u32,
+/// }
+///
+/// let config = Config { name: "first\nsecond".to_string(), retries: 3 };
+/// let style = RelaxedStyle { quotes: Quotes::SingleQuote, pretty: false };
+/// let json_relaxed = json_keyquotes_convert::to_relaxed_string(&config, style).unwrap();
+/// assert_eq!(json_relaxed, "{name:'first\nsecond',retries:3}");
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_relaxed_string<T: serde::Serialize + ?Sized>(
+    value: &T,
+    style: RelaxedStyle,
+) -> Result<String, serde_json::Error> {
+    let json = if style.pretty {
+        serde_json::to_string_pretty(value)?
+    } else {
+        serde_json::to_string(value)?
+    };
+
+    Ok(json_key_quote_utils::json_relax(&json, style.quotes))
+}
+
+/// The options for [JsonKeyQuoteConverter::convert].
+///
+/// With the `serde` feature, the options can be (de)serialized, missing fields get their default value.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{ConvertOptions, JsonKeyQuoteConverter, Quotes};
+///
+/// let options = ConvertOptions {
+///     quote_type: Quotes::SingleQuote,
+///     strip_comments: true,
+///     ..ConvertOptions::default()
+/// };
+/// let json_converted = JsonKeyQuoteConverter::with_options("{key: \"val\"} // comment", &options)
+///     .convert()
+///     .json();
+/// assert_eq!(json_converted, "{'key': \"val\"} ");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ConvertOptions {
+    /// The quotes to add around the JSON keys.
+    pub quote_type: Quotes,
+    /// Whether the ctrl-characters in the string values should be escaped.
+    pub escape_ctrlchars: bool,
+    /// Whether the string values should be converted to the `quote_type`,
+    /// or to double-quotes if the `quote_type` is not valid around string values.
+    pub normalize_value_quotes: bool,
+    /// Whether the comments should be removed, see [json_key_quote_utils::json_strip_comments].
+    pub strip_comments: bool,
+    /// The dialect of the JSON string, see [Dialect].
+    pub dialect: Dialect,
+}
+
+/// A conversion step for [JsonKeyQuoteConverter::apply].
+///
+/// With the `serde` feature, operations can be (de)serialized, e.g. `{"add_key_quotes": "double"}` or `"escape_ctrl_chars"`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Operation {
+    /// Adds key-quotes of the given type, like [JsonKeyQuoteConverter::add_key_quotes].
+    AddKeyQuotes(Quotes),
+    /// See [JsonKeyQuoteConverter::remove_key_quotes].
+    RemoveKeyQuotes,
+    /// See [JsonKeyQuoteConverter::escape_ctrlchars].
+    EscapeCtrlChars,
+    /// See [JsonKeyQuoteConverter::unescape_ctrlchars].
+    UnescapeCtrlChars,
+    /// See [JsonKeyQuoteConverter::convert_value_quotes].
+    NormalizeValueQuotes(Quotes),
+    /// See [json_key_quote_utils::json_strip_comments].
+    StripComments,
+    /// See [JsonKeyQuoteConverter::escape_forward_slash].
+    EscapeForwardSlash,
+    /// See [JsonKeyQuoteConverter::unescape_forward_slash].
+    UnescapeForwardSlash,
+    /// See [JsonKeyQuoteConverter::escape_html].
+    EscapeHtml,
+    /// See [JsonKeyQuoteConverter::unescape_html].
+    UnescapeHtml,
+    /// See [JsonKeyQuoteConverter::convert_key_case].
+    ConvertKeyCase(KeyCase),
+    /// See [JsonKeyQuoteConverter::sort_keys].
+    SortKeys,
+    /// See [JsonKeyQuoteConverter::minify].
+    Minify,
+    /// See [JsonKeyQuoteConverter::pretty].
+    Pretty(Indent),
+    /// See [JsonKeyQuoteConverter::wrap].
+    Wrap(String),
+    /// See [JsonKeyQuoteConverter::unwrap], fails if the JSON string can not be unwrapped.
+    Unwrap(String),
+    /// See [JsonKeyQuoteConverter::validate], fails if the JSON string is structurally invalid.
+    Validate,
+}
+
+/// The error returned by [JsonKeyQuoteConverter::apply] when an [Operation] fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OperationError {
+    /// The index of the failed operation.
+    pub index: usize,
+    /// The failed operation.
+    pub operation: Operation,
+    /// Why the operation failed.
+    pub message: String,
+}
+
+impl fmt::Display for OperationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Operation {} ({:?}) failed: {}",
+            self.index, self.operation, self.message
+        )
     }
 }
 
+impl std::error::Error for OperationError {}
+
 /// The builder for the JSON conversions.
+///
+/// Two builders are equal if their JSON strings and [ConvertOptions] are equal,
+/// their [JsonKeyQuoteConverter::stats] and [JsonKeyQuoteConverter::changed] are not compared.
+/// The default builder has an empty JSON string and the default [ConvertOptions].
+#[derive(Clone, Default)]
 pub struct JsonKeyQuoteConverter {
     json: String,
-    quote_type: Quotes,
+    options: ConvertOptions,
+    stats: json_key_quote_utils::ConversionStats,
+    changed: bool,
+}
+
+impl PartialEq for JsonKeyQuoteConverter {
+    fn eq(&self, other: &Self) -> bool {
+        self.json == other.json && self.options == other.options
+    }
+}
+
+impl Eq for JsonKeyQuoteConverter {}
+
+/// The amount of characters of the JSON string shown by the [fmt::Debug] implementation.
+const DEBUG_JSON_MAX_CHARS: usize = 100;
+
+impl fmt::Debug for JsonKeyQuoteConverter {
+    /// Formats the builder, with the JSON string truncated to its first 100 characters.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut json: String = self.json.chars().take(DEBUG_JSON_MAX_CHARS).collect();
+        if json.len() < self.json.len() {
+            json.push_str("...");
+        }
+
+        f.debug_struct("JsonKeyQuoteConverter")
+            .field("json", &json)
+            .field("quote_type", &self.options.quote_type)
+            .finish()
+    }
+}
+
+impl std::str::FromStr for JsonKeyQuoteConverter {
+    type Err = std::convert::Infallible;
+
+    /// Returns a new [JsonKeyQuoteConverter] with the default [Quotes].
+    ///
+    /// This never fails, the JSON string is not checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter};
+    ///
+    /// let converter: JsonKeyQuoteConverter = "{key: \"val\"}".parse().unwrap();
+    /// assert_eq!(converter.add_key_quotes().json(), "{\"key\": \"val\"}");
+    /// ```
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        Ok(JsonKeyQuoteConverter::new(json, Quotes::default()))
+    }
+}
+
+impl From<std::path::PathBuf> for JsonKeyQuoteConverter {
+    /// Returns a new [JsonKeyQuoteConverter] with the JSON string loaded from the file
+    /// and the default [Quotes], see [load_write_utils::load_json].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file can not be read. Use [JsonKeyQuoteConverter::from_file]
+    /// to handle the I/O error instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::path::PathBuf;
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter};
+    ///
+    /// let converter = JsonKeyQuoteConverter::from(PathBuf::from("./test_resources/Test_without_keyquotes.json"));
+    /// ```
+    fn from(path: std::path::PathBuf) -> Self {
+        JsonKeyQuoteConverter::from_file(&path, Quotes::default())
+            .unwrap_or_else(|err| panic!("could not load {}: {}", path.display(), err))
+    }
 }
 
 impl JsonKeyQuoteConverter {
@@ -58,108 +605,1968 @@ impl JsonKeyQuoteConverter {
     /// let converter = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default());
     /// ```
     pub fn new(json: &str, quote_type: Quotes) -> JsonKeyQuoteConverter {
+        JsonKeyQuoteConverter::with_options(
+            json,
+            &ConvertOptions {
+                quote_type,
+                ..ConvertOptions::default()
+            },
+        )
+    }
+
+    /// Returns a new [JsonKeyQuoteConverter] with the quote type used by most of the quoted keys,
+    /// or [Quotes::DoubleQuote] if no key is quoted.
+    ///
+    /// See [json_key_quote_utils::json_infer_quote_type].
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - A JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new_auto("{'key': {other: 1}}").add_key_quotes().json();
+    /// assert_eq!(json_added, "{'key': {'other': 1}}");
+    /// ```
+    pub fn new_auto(json: &str) -> JsonKeyQuoteConverter {
+        let quote_type =
+            json_key_quote_utils::json_infer_quote_type(json).unwrap_or(Quotes::DoubleQuote);
+
+        JsonKeyQuoteConverter::new(json, quote_type)
+    }
+
+    /// Returns a new [JsonKeyQuoteConverter] with the [ConvertOptions] for [JsonKeyQuoteConverter::convert].
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - A JSON string.
+    /// * `options` - The conversion options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{ConvertOptions, JsonKeyQuoteConverter};
+    ///
+    /// let converter = JsonKeyQuoteConverter::with_options("{key: \"val\"}", &ConvertOptions::default());
+    /// ```
+    pub fn with_options(json: &str, options: &ConvertOptions) -> JsonKeyQuoteConverter {
         JsonKeyQuoteConverter {
             json: String::from(json),
-            quote_type: quote_type,
+            options: *options,
+            stats: json_key_quote_utils::ConversionStats::default(),
+            changed: false,
         }
     }
 
-    /// Adds key-quotes to the JSON string.
+    /// Returns a new [JsonKeyQuoteConverter] with the serialized [serde_json::Value].
+    ///
+    /// The JSON string is strictly valid JSON, so it can be relaxed with
+    /// [JsonKeyQuoteConverter::remove_key_quotes] for example.
+    /// The string values stay escaped, so the relaxed JSON string can be parsed back into the same value.
+    ///
+    /// Only available with the `serde` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
     ///
     /// # Examples
     ///
     /// ```
     /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
     ///
-    /// let json_added = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
-    ///     .add_key_quotes().json();
-    /// assert_eq!(json_added, "{\"key\": \"val\"}");
-    ///
-    /// let json_already_existing = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default())
-    ///     .add_key_quotes().json();
-    /// assert_eq!(json_already_existing, "{\"key\": \"val\"}");
+    /// let value = serde_json::json!({"key": "va\nl"});
+    /// let json_relaxed = JsonKeyQuoteConverter::from_value(&value, Quotes::default())
+    ///     .remove_key_quotes()
+    ///     .json();
+    /// assert_eq!(json_relaxed, r#"{key:"va\nl"}"#);
     /// ```
-    pub fn add_key_quotes(mut self) -> JsonKeyQuoteConverter {
-        self.json = json_key_quote_utils::json_add_key_quotes(&self.json, self.quote_type);
-
-        self
+    #[cfg(feature = "serde")]
+    pub fn from_value(value: &serde_json::Value, quote_type: Quotes) -> JsonKeyQuoteConverter {
+        JsonKeyQuoteConverter::new(&value.to_string(), quote_type)
     }
 
-    /// Removes key-quotes from the JSON string.
+    /// Returns a new [JsonKeyQuoteConverter] with the JSON string loaded from the file,
+    /// see [load_write_utils::load_json].
+    ///
+    /// Unlike `From<PathBuf>`, this returns the I/O error instead of panicking.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::path::Path;
     /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
     ///
-    /// let json_removed = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default())
-    ///     .remove_key_quotes().json();
-    /// assert_eq!(json_removed, "{key: \"val\"}");
-    ///
-    /// let json_already_removed = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
-    ///     .remove_key_quotes().json();
-    /// assert_eq!(json_already_removed, "{key: \"val\"}");
+    /// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+    /// let converter = JsonKeyQuoteConverter::from_file(path, Quotes::default()).unwrap();
     /// ```
-    pub fn remove_key_quotes(mut self) -> JsonKeyQuoteConverter {
-        self.json = json_key_quote_utils::json_remove_key_quotes(&self.json);
+    pub fn from_file(
+        path: &std::path::Path,
+        quote_type: Quotes,
+    ) -> Result<JsonKeyQuoteConverter, std::io::Error> {
+        let json = load_write_utils::load_json(path)?;
 
-        self
+        Ok(JsonKeyQuoteConverter::new(&json, quote_type))
     }
 
-    /// Escape ctrl-characters from the JSON string values
-    /// and remove ctrl-characters from the JSON keys with keyquotes.
+    /// Returns a new [JsonKeyQuoteConverter] with the serialized [serde_json::Value],
+    /// see [JsonKeyQuoteConverter::from_value].
     ///
-    /// This method will escape `newlines`, `tabs` and `carriage returns` in the JSON string values
-    /// and remove `newlines`, `tabs` and `carriage returns` in the JSON keys with keyquotes.
+    /// Only available with the `serde` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
     ///
     /// # Examples
     ///
     /// ```
     /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
     ///
-    /// let json_escaped = JsonKeyQuoteConverter::new(r#"{"key": "va
-    /// l"}"#, Quotes::default())
-    ///     .escape_ctrlchars().json();
-    /// assert_eq!(json_escaped, r#"{"key": "va\nl"}"#);
-    ///
-    /// let json_already_escaped = JsonKeyQuoteConverter::new(r#"{"key": "va\nl"}"#, Quotes::default())
-    ///     .escape_ctrlchars().escape_ctrlchars().json();
-    /// assert_eq!(json_already_escaped, r#"{"key": "va\nl"}"#);
+    /// let value = serde_json::json!({"key": "val"});
+    /// let json = JsonKeyQuoteConverter::new_from_json_value(&value, Quotes::default()).json();
+    /// assert_eq!(json, r#"{"key":"val"}"#);
     /// ```
-    pub fn escape_ctrlchars(mut self) -> JsonKeyQuoteConverter {
-        self.json = json_key_quote_utils::json_escape_ctrlchars(&self.json);
-
-        self
+    #[cfg(feature = "serde")]
+    pub fn new_from_json_value(
+        value: &serde_json::Value,
+        quote_type: Quotes,
+    ) -> JsonKeyQuoteConverter {
+        JsonKeyQuoteConverter::from_value(value, quote_type)
     }
 
-    /// Unescape ctrl-characters from the JSON string values
-    /// and remove ctrl-characters from the JSON keys without keyquotes.
+    /// Returns a new [JsonKeyQuoteConverter] with the JSON string read from the reader,
+    /// see [load_write_utils::load_json_from_reader].
+    ///
+    /// # Arguments
     ///
-    /// This method will unescape `newlines`, `tabs` and `carriage returns` in the JSON string values
-    /// and remove `newlines`, `tabs` and `carriage returns` in the JSON keys without keyquotes.
+    /// * `reader` - The reader, like a file, stdin or a network socket.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
     ///
     /// # Examples
     ///
     /// ```
+    /// use std::io::Cursor;
     /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
     ///
-    /// let json_unescaped = JsonKeyQuoteConverter::new(r#"{key: "va\nl"}"#, Quotes::default())
-    ///     .unescape_ctrlchars().json();
-    /// assert_eq!(json_unescaped, r#"{key: "va
-    /// l"}"#);
-    ///
-    /// let json_already_unescaped = JsonKeyQuoteConverter::new(r#"{key: "va\nl"}"#, Quotes::default())
-    ///     .unescape_ctrlchars().unescape_ctrlchars().json();
-    /// assert_eq!(json_already_unescaped, r#"{key: "va
-    /// l"}"#);
+    /// let converter = JsonKeyQuoteConverter::from_reader(Cursor::new("{key: \"val\"}"), Quotes::default()).unwrap();
+    /// assert_eq!(converter.add_key_quotes().json(), "{\"key\": \"val\"}");
     /// ```
-    pub fn unescape_ctrlchars(mut self) -> JsonKeyQuoteConverter {
-        self.json = json_key_quote_utils::json_unescape_ctrlchars(&self.json);
+    pub fn from_reader<R: std::io::Read>(
+        reader: R,
+        quote_type: Quotes,
+    ) -> Result<JsonKeyQuoteConverter, std::io::Error> {
+        let json = load_write_utils::load_json_from_reader(reader)?;
 
-        self
+        Ok(JsonKeyQuoteConverter::new(&json, quote_type))
     }
 
-    /// Returns the JSON string.
+    /// Returns a new [JsonKeyQuoteConverter] with the JSON string decoded from the UTF-8 bytes.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The UTF-8 encoded JSON string, like a network buffer.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::from_bytes(b"{key: \"val\"}", Quotes::default()).unwrap();
+    /// assert_eq!(converter.add_key_quotes().json(), "{\"key\": \"val\"}");
+    ///
+    /// assert!(JsonKeyQuoteConverter::from_bytes(&[0xc3, 0x28], Quotes::default()).is_err());
+    /// ```
+    pub fn from_bytes(
+        bytes: &[u8],
+        quote_type: Quotes,
+    ) -> Result<JsonKeyQuoteConverter, std::str::Utf8Error> {
+        let json = std::str::from_utf8(bytes)?;
+
+        Ok(JsonKeyQuoteConverter::new(json, quote_type))
+    }
+
+    /// Returns a new [JsonKeyQuoteConverter] with the JSON string loaded from the file,
+    /// see [load_write_utils::async_load_json].
+    ///
+    /// Only available with the `tokio` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path.
+    /// * `quote_type` - Whether the JSON keys should be single- or double-quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use std::path::Path;
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+    /// let converter = JsonKeyQuoteConverter::from_file_async(path, Quotes::default()).await?;
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn from_file_async(
+        path: &std::path::Path,
+        quote_type: Quotes,
+    ) -> Result<JsonKeyQuoteConverter, std::io::Error> {
+        let json = load_write_utils::async_load_json(path).await?;
+
+        Ok(JsonKeyQuoteConverter::new(&json, quote_type))
+    }
+
+    /// Adds key-quotes to the JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .add_key_quotes().json();
+    /// assert_eq!(json_added, "{\"key\": \"val\"}");
+    ///
+    /// let json_already_existing = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default())
+    ///     .add_key_quotes().json();
+    /// assert_eq!(json_already_existing, "{\"key\": \"val\"}");
+    /// ```
+    pub fn add_key_quotes(mut self) -> JsonKeyQuoteConverter {
+        self.add_key_quotes_mut();
+
+        self
+    }
+
+    /// Adds key-quotes to the JSON string, calling `on_key` for every key encountered.
+    ///
+    /// The keys are reported in document order, with their byte spans in the JSON string
+    /// before the conversion. See [json_key_quote_utils::json_add_key_quotes_with].
+    ///
+    /// # Arguments
+    ///
+    /// * `on_key` - The closure which receives a [json_key_quote_utils::KeyEvent] for every key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut keys = Vec::new();
+    /// let json_added = JsonKeyQuoteConverter::new("{key: {\"other\": 1}}", Quotes::default())
+    ///     .add_key_quotes_with(|event| keys.push((event.key, event.quoted))).json();
+    /// assert_eq!(json_added, "{\"key\": {\"other\": 1}}");
+    /// assert_eq!(keys, vec![("key".to_string(), false), ("other".to_string(), true)]);
+    /// ```
+    pub fn add_key_quotes_with(
+        mut self,
+        on_key: impl FnMut(json_key_quote_utils::KeyEvent),
+    ) -> JsonKeyQuoteConverter {
+        self.add_key_quotes_of(self.options.quote_type, on_key);
+
+        self
+    }
+
+    /// Adds key-quotes to the JSON string, see [JsonKeyQuoteConverter::add_key_quotes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default());
+    /// converter.add_key_quotes_mut();
+    /// assert_eq!(converter.json_ref(), "{\"key\": \"val\"}");
+    /// ```
+    pub fn add_key_quotes_mut(&mut self) -> &mut JsonKeyQuoteConverter {
+        self.add_key_quotes_of(self.options.quote_type, |_| {});
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string.
+    ///
+    /// A [Quotes::Custom] quote type is removed too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default())
+    ///     .remove_key_quotes().json();
+    /// assert_eq!(json_removed, "{key: \"val\"}");
+    ///
+    /// let json_already_removed = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .remove_key_quotes().json();
+    /// assert_eq!(json_already_removed, "{key: \"val\"}");
+    /// ```
+    pub fn remove_key_quotes(mut self) -> JsonKeyQuoteConverter {
+        self.remove_key_quotes_mut();
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string, see [JsonKeyQuoteConverter::remove_key_quotes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default());
+    /// converter.remove_key_quotes_mut();
+    /// assert_eq!(converter.json_ref(), "{key: \"val\"}");
+    /// ```
+    pub fn remove_key_quotes_mut(&mut self) -> &mut JsonKeyQuoteConverter {
+        let (json, stats) = json_key_quote_utils::json_remove_key_quotes_with_stats(
+            &self.json,
+            self.options.quote_type,
+        );
+        self.update_json(json);
+        self.stats += stats;
+
+        self
+    }
+
+    /// Adds key-quotes only to the keys at nesting depth `min_depth` and deeper,
+    /// where the keys of the top-level object are at depth `1`.
+    ///
+    /// See [json_key_quote_utils::json_add_key_quotes_from_depth].
+    ///
+    /// # Arguments
+    ///
+    /// * `min_depth` - The nesting depth of the outermost keys to quote.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new("{a: {b: {c: 1}}}", Quotes::default())
+    ///     .add_key_quotes_from_depth(2).json();
+    /// assert_eq!(json_added, r#"{a: {"b": {"c": 1}}}"#);
+    /// ```
+    pub fn add_key_quotes_from_depth(mut self, min_depth: usize) -> JsonKeyQuoteConverter {
+        let json = json_key_quote_utils::json_add_key_quotes_from_depth(
+            &self.json,
+            self.options.quote_type,
+            min_depth,
+        );
+        self.stats += json_key_quote_utils::count_key_quote_changes(&self.json, &json);
+        self.update_json(json);
+
+        self
+    }
+
+    /// Adds key-quotes only to the unquoted keys for which the predicate returns `true`,
+    /// like [JsonKeyQuoteConverter::quote_keys_matching], counting them in the [JsonKeyQuoteConverter::stats].
+    ///
+    /// The keys of nested objects are passed to the predicate too.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Whether the key, without key-quotes, should be quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new("{x-id: 1, name: {x-first: 'a', last: 'b'}}", Quotes::default())
+    ///     .add_key_quotes_if(|key| key.starts_with("x-")).json();
+    /// assert_eq!(json_added, r#"{"x-id": 1, name: {"x-first": 'a', last: 'b'}}"#);
+    /// ```
+    pub fn add_key_quotes_if(mut self, predicate: impl Fn(&str) -> bool) -> JsonKeyQuoteConverter {
+        let json = json_key_quote_utils::json_quote_only_keys_matching(
+            &self.json,
+            predicate,
+            self.options.quote_type,
+        );
+        self.stats += json_key_quote_utils::count_key_quote_changes(&self.json, &json);
+        self.update_json(json);
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string like [JsonKeyQuoteConverter::remove_key_quotes],
+    /// but only from the keys for which the predicate returns `true`.
+    ///
+    /// The keys of nested objects are passed to the predicate too.
+    /// Keys which can't be unquoted, like `"a,b"`, keep their key-quotes regardless of the predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Whether the key, without key-quotes, should be unquoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new(r#"{"x-id": 1, "name": {"x-first": "a"}}"#, Quotes::default())
+    ///     .remove_key_quotes_if(|key| !key.starts_with("x-")).json();
+    /// assert_eq!(json_removed, r#"{"x-id": 1, name: {"x-first": "a"}}"#);
+    /// ```
+    pub fn remove_key_quotes_if(
+        mut self,
+        predicate: impl Fn(&str) -> bool,
+    ) -> JsonKeyQuoteConverter {
+        self.remove_key_quotes_except(|key| !predicate(key));
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string like [JsonKeyQuoteConverter::remove_key_quotes],
+    /// except from the numeric keys, like `123`, `007` or `-1`.
+    ///
+    /// See [json_key_quote_utils::json_remove_key_quotes_keep_numeric].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new(r#"{"123": 1, "key": true}"#, Quotes::default())
+    ///     .remove_key_quotes_keep_numeric().json();
+    /// assert_eq!(json_removed, r#"{"123": 1, key: true}"#);
+    /// ```
+    pub fn remove_key_quotes_keep_numeric(mut self) -> JsonKeyQuoteConverter {
+        self.remove_key_quotes_except(json_key_quote_utils::is_numeric_key);
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string like [JsonKeyQuoteConverter::remove_key_quotes],
+    /// but only from the keys which stay unambiguous without them, like `my_key` but not `my key`.
+    ///
+    /// See [json_key_quote_utils::json_remove_key_quotes_safe].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new(r#"{"my key": 1, "my_key": 2}"#, Quotes::default())
+    ///     .remove_key_quotes_safe().json();
+    /// assert_eq!(json_removed, r#"{"my key": 1, my_key: 2}"#);
+    /// ```
+    pub fn remove_key_quotes_safe(mut self) -> JsonKeyQuoteConverter {
+        let json = json_key_quote_utils::json_remove_key_quotes_safe(&self.json);
+        self.stats += json_key_quote_utils::count_key_quote_changes(&self.json, &json);
+        self.update_json(json);
+
+        self
+    }
+
+    /// Removes key-quotes from the JSON string like [JsonKeyQuoteConverter::remove_key_quotes],
+    /// except from the keys named `true`, `false` or `null`.
+    ///
+    /// See [json_key_quote_utils::json_remove_key_quotes_keep_reserved].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new(r#"{"null": 1, "key": true}"#, Quotes::default())
+    ///     .remove_key_quotes_keep_reserved().json();
+    /// assert_eq!(json_removed, r#"{"null": 1, key: true}"#);
+    /// ```
+    pub fn remove_key_quotes_keep_reserved(mut self) -> JsonKeyQuoteConverter {
+        self.remove_key_quotes_except(json_key_quote_utils::is_reserved_key);
+
+        self
+    }
+
+    /// Sets the dialect of the JSON string, which decides which values are recognized
+    /// when adding key-quotes. [Dialect::Json] by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `dialect` - The dialect of the JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{Dialect, JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new("{a: +5, b: Infinity, c: NaN}", Quotes::default())
+    ///     .dialect(Dialect::Json5)
+    ///     .add_key_quotes().json();
+    /// assert_eq!(json_added, r#"{"a": +5, "b": Infinity, "c": NaN}"#);
+    /// ```
+    pub fn dialect(mut self, dialect: Dialect) -> JsonKeyQuoteConverter {
+        self.options.dialect = dialect;
+
+        self
+    }
+
+    /// Escape ctrl-characters from the JSON string values
+    /// and remove ctrl-characters from the JSON keys with keyquotes.
+    ///
+    /// This method will escape `newlines`, `tabs`, `carriage returns`, `backspaces`, `form feeds`
+    /// and all other control characters in the range U+0000–U+001F as `\uXXXX`
+    /// in the JSON string values and remove them from the JSON keys with keyquotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_escaped = JsonKeyQuoteConverter::new(r#"{"key": "va
+    /// l"}"#, Quotes::default())
+    ///     .escape_ctrlchars().json();
+    /// assert_eq!(json_escaped, r#"{"key": "va\nl"}"#);
+    ///
+    /// let json_already_escaped = JsonKeyQuoteConverter::new(r#"{"key": "va\nl"}"#, Quotes::default())
+    ///     .escape_ctrlchars().escape_ctrlchars().json();
+    /// assert_eq!(json_already_escaped, r#"{"key": "va\nl"}"#);
+    /// ```
+    pub fn escape_ctrlchars(mut self) -> JsonKeyQuoteConverter {
+        self.escape_ctrlchars_mut();
+
+        self
+    }
+
+    /// Escapes ctrl-characters in the JSON string values, see [JsonKeyQuoteConverter::escape_ctrlchars].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::new("{key: \"va\nl\"}", Quotes::default());
+    /// converter.escape_ctrlchars_mut();
+    /// assert_eq!(converter.json_ref(), r#"{key: "va\nl"}"#);
+    /// ```
+    pub fn escape_ctrlchars_mut(&mut self) -> &mut JsonKeyQuoteConverter {
+        let (json, stats) = json_key_quote_utils::json_escape_ctrlchars_with_stats(&self.json);
+        self.update_json(json);
+        self.stats += stats;
+
+        self
+    }
+
+    /// Unescape ctrl-characters from the JSON string values
+    /// and remove ctrl-characters from the JSON keys without keyquotes.
+    ///
+    /// This method will unescape `newlines`, `tabs`, `carriage returns`, `backspaces`, `form feeds`
+    /// and all other control characters in the range U+0000–U+001F escaped as `\uXXXX`
+    /// in the JSON string values and remove their escaped variants from the JSON keys without keyquotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_unescaped = JsonKeyQuoteConverter::new(r#"{key: "va\nl"}"#, Quotes::default())
+    ///     .unescape_ctrlchars().json();
+    /// assert_eq!(json_unescaped, r#"{key: "va
+    /// l"}"#);
+    ///
+    /// let json_already_unescaped = JsonKeyQuoteConverter::new(r#"{key: "va\nl"}"#, Quotes::default())
+    ///     .unescape_ctrlchars().unescape_ctrlchars().json();
+    /// assert_eq!(json_already_unescaped, r#"{key: "va
+    /// l"}"#);
+    /// ```
+    pub fn unescape_ctrlchars(mut self) -> JsonKeyQuoteConverter {
+        self.unescape_ctrlchars_mut();
+
+        self
+    }
+
+    /// Unescapes ctrl-characters from the JSON string values, see [JsonKeyQuoteConverter::unescape_ctrlchars].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::new(r#"{key: "va\nl"}"#, Quotes::default());
+    /// converter.unescape_ctrlchars_mut();
+    /// assert_eq!(converter.json_ref(), "{key: \"va\nl\"}");
+    /// ```
+    pub fn unescape_ctrlchars_mut(&mut self) -> &mut JsonKeyQuoteConverter {
+        let (json, stats) = json_key_quote_utils::json_unescape_ctrlchars_with_stats(&self.json);
+        self.update_json(json);
+        self.stats += stats;
+
+        self
+    }
+
+    /// Applies the conversions enabled in the [ConvertOptions], see [JsonKeyQuoteConverter::with_options].
+    ///
+    /// The comments are stripped first, then the key-quotes are added,
+    /// then the string values are normalized and finally the ctrl-characters are escaped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{ConvertOptions, Dialect, JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let options = ConvertOptions {
+    ///     quote_type: Quotes::DoubleQuote,
+    ///     escape_ctrlchars: true,
+    ///     normalize_value_quotes: true,
+    ///     strip_comments: true,
+    ///     dialect: Dialect::Json,
+    /// };
+    /// let json_converted = JsonKeyQuoteConverter::with_options("{/* comment */key: 'va\nl'}", &options)
+    ///     .convert()
+    ///     .json();
+    /// assert_eq!(json_converted, r#"{"key": "va\nl"}"#);
+    /// ```
+    pub fn convert(mut self) -> JsonKeyQuoteConverter {
+        self.convert_mut();
+
+        self
+    }
+
+    /// Applies the conversions enabled in the [ConvertOptions], see [JsonKeyQuoteConverter::convert].
+    ///
+    /// Together with [JsonKeyQuoteConverter::set_json], the converter can be reused for multiple inputs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{ConvertOptions, JsonKeyQuoteConverter};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::with_options("", &ConvertOptions::default());
+    /// for json in ["{a: 1}", "{b: 2}"] {
+    ///     converter.set_json(json).convert_mut();
+    ///     assert!(converter.json_ref().contains('"'));
+    /// }
+    /// ```
+    pub fn convert_mut(&mut self) -> &mut JsonKeyQuoteConverter {
+        let options = self.options;
+        if options.strip_comments {
+            self.update_json(json_key_quote_utils::json_strip_comments(&self.json));
+        }
+        self.add_key_quotes_mut();
+        if options.normalize_value_quotes {
+            let value_quote_type = match options.quote_type {
+                Quotes::SingleQuote => Quotes::SingleQuote,
+                _ => Quotes::DoubleQuote,
+            };
+            self.update_json(json_key_quote_utils::json_convert_value_quotes(
+                &self.json,
+                value_quote_type,
+            ));
+        }
+        if options.escape_ctrlchars {
+            self.escape_ctrlchars_mut();
+        }
+
+        self
+    }
+
+    /// Escapes the forward slashes in the JSON string values as `\/`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_escaped = JsonKeyQuoteConverter::new(r#"{url: "http://example.com"}"#, Quotes::default())
+    ///     .escape_forward_slash().json();
+    /// assert_eq!(json_escaped, r#"{url: "http:\/\/example.com"}"#);
+    /// ```
+    pub fn escape_forward_slash(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_escape_forward_slash(&self.json));
+
+        self
+    }
+
+    /// Unescapes the escaped forward slashes (`\/`) in the JSON string values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_unescaped = JsonKeyQuoteConverter::new(r#"{url: "http:\/\/example.com"}"#, Quotes::default())
+    ///     .unescape_forward_slash().json();
+    /// assert_eq!(json_unescaped, r#"{url: "http://example.com"}"#);
+    /// ```
+    pub fn unescape_forward_slash(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_unescape_forward_slash(
+            &self.json,
+        ));
+
+        self
+    }
+
+    /// Escapes the HTML-sensitive characters in the JSON string values,
+    /// see [json_key_quote_utils::json_escape_html].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_escaped = JsonKeyQuoteConverter::new(r#"{key: "</script>"}"#, Quotes::default())
+    ///     .escape_html().json();
+    /// assert_eq!(json_escaped, r#"{key: "\u003C/script\u003E"}"#);
+    /// ```
+    pub fn escape_html(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_escape_html(&self.json));
+
+        self
+    }
+
+    /// Unescapes the HTML-sensitive characters in the JSON string values,
+    /// see [json_key_quote_utils::json_unescape_html].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_unescaped = JsonKeyQuoteConverter::new(r#"{key: "\u003C/script\u003E"}"#, Quotes::default())
+    ///     .unescape_html().json();
+    /// assert_eq!(json_unescaped, r#"{key: "</script>"}"#);
+    /// ```
+    pub fn unescape_html(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_unescape_html(&self.json));
+
+        self
+    }
+
+    /// Converts the quotes around all JSON string values to the `target` quote style.
+    ///
+    /// See [json_key_quote_utils::json_convert_value_quotes] for the exact rules.
+    /// Any other `target` than [Quotes::SingleQuote] or [Quotes::DoubleQuote] leaves the JSON string unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Whether the JSON string values should be single- or double-quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_converted = JsonKeyQuoteConverter::new(r#"{key: 'say "hi"'}"#, Quotes::default())
+    ///     .convert_value_quotes(Quotes::DoubleQuote).json();
+    /// assert_eq!(json_converted, r#"{key: "say \"hi\""}"#);
+    /// ```
+    pub fn convert_value_quotes(mut self, target: Quotes) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_convert_value_quotes(
+            &self.json, target,
+        ));
+
+        self
+    }
+
+    /// Renames the JSON keys according to the `map`.
+    ///
+    /// Only keys in key position are renamed, and their quotes are preserved.
+    /// Use [json_key_quote_utils::json_rename_keys] to detect keys which collide after renaming.
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - The old key names mapped to the new key names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let map = HashMap::from([("user_name".to_string(), "username".to_string())]);
+    ///
+    /// let json_renamed = JsonKeyQuoteConverter::new(r#"{user_name: "user_name"}"#, Quotes::default())
+    ///     .rename_keys(&map).json();
+    /// assert_eq!(json_renamed, r#"{username: "user_name"}"#);
+    /// ```
+    pub fn rename_keys(mut self, map: &HashMap<String, String>) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_rename_keys(&self.json, map).0);
+
+        self
+    }
+
+    /// Adds quotes to the unquoted JSON keys for which the predicate returns `true`, using the quote type.
+    ///
+    /// See [json_key_quote_utils::json_quote_only_keys_matching].
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - Whether the key should be quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_quoted = JsonKeyQuoteConverter::new("{first name: 1, age: 2}", Quotes::default())
+    ///     .quote_keys_matching(|key| key.contains(' ')).json();
+    /// assert_eq!(json_quoted, r#"{"first name": 1, age: 2}"#);
+    /// ```
+    pub fn quote_keys_matching(
+        mut self,
+        predicate: impl Fn(&str) -> bool,
+    ) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_quote_only_keys_matching(
+            &self.json,
+            predicate,
+            self.options.quote_type,
+        ));
+
+        self
+    }
+
+    /// Adds quotes to the unquoted JSON keys which contain characters outside of `[A-Za-z0-9_]`, using the quote type.
+    ///
+    /// See [json_key_quote_utils::json_conditional_key_quotes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_quoted = JsonKeyQuoteConverter::new("{simple_key: 1, key with spaces: 2}", Quotes::default())
+    ///     .conditional_key_quotes().json();
+    /// assert_eq!(json_quoted, r#"{simple_key: 1, "key with spaces": 2}"#);
+    /// ```
+    pub fn conditional_key_quotes(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_conditional_key_quotes(
+            &self.json,
+            self.options.quote_type,
+        ));
+
+        self
+    }
+
+    /// Transforms every JSON key in key position using the closure `f`.
+    ///
+    /// The closure receives the key text without its quotes. Quoted keys keep their quotes,
+    /// unquoted keys which would be ambiguous after the transformation are quoted using the quote type.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The closure which returns the new name of a key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_transformed = JsonKeyQuoteConverter::new(r#"{"vendor_key": {vendor_other: 1}}"#, Quotes::default())
+    ///     .transform_keys(|key| key.trim_start_matches("vendor_").to_string()).json();
+    /// assert_eq!(json_transformed, r#"{"key": {other: 1}}"#);
+    /// ```
+    pub fn transform_keys(mut self, f: impl Fn(&str) -> String) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_transform_keys(
+            &self.json,
+            f,
+            self.options.quote_type,
+        ));
+
+        self
+    }
+
+    /// Removes the duplicate keys from every JSON object, keeping only one occurrence of each key.
+    ///
+    /// # Arguments
+    ///
+    /// * `keep` - Whether to keep the first or the last occurrence of a duplicate key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    /// use json_keyquotes_convert::json_key_quote_utils::KeepDuplicate;
+    ///
+    /// let json_deduplicated = JsonKeyQuoteConverter::new(r#"{"a": 1, b: 2, a: 3}"#, Quotes::default())
+    ///     .deduplicate_keys(KeepDuplicate::Last).json();
+    /// assert_eq!(json_deduplicated, r#"{b: 2, a: 3}"#);
+    /// ```
+    pub fn deduplicate_keys(
+        mut self,
+        keep: json_key_quote_utils::KeepDuplicate,
+    ) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_deduplicate_keys(
+            &self.json, keep,
+        ));
+
+        self
+    }
+
+    /// Removes the members with a `null` value from every JSON object.
+    ///
+    /// See [json_key_quote_utils::json_remove_null_values].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_removed = JsonKeyQuoteConverter::new("{a: null, b: {c: null, d: 1}}", Quotes::default())
+    ///     .remove_null_values().json();
+    /// assert_eq!(json_removed, "{b: {d: 1}}");
+    /// ```
+    pub fn remove_null_values(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_remove_null_values(&self.json));
+
+        self
+    }
+
+    /// Replaces every `null` value with the `default` value, which is inserted verbatim.
+    ///
+    /// See [json_key_quote_utils::json_replace_null_with_default].
+    ///
+    /// # Arguments
+    ///
+    /// * `default` - The JSON value to insert, like `""` or `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_replaced = JsonKeyQuoteConverter::new("{a: null, b: [null]}", Quotes::default())
+    ///     .replace_null_with_default("0").json();
+    /// assert_eq!(json_replaced, "{a: 0, b: [0]}");
+    /// ```
+    pub fn replace_null_with_default(mut self, default: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_replace_null_with_default(
+            &self.json, default,
+        ));
+
+        self
+    }
+
+    /// Prepends the prefix to every JSON key in key position.
+    ///
+    /// See [json_key_quote_utils::json_add_key_prefix].
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix to prepend to the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_prefixed = JsonKeyQuoteConverter::new(r#"{key: "value"}"#, Quotes::default())
+    ///     .add_key_prefix("v2_").add_key_quotes().json();
+    /// assert_eq!(json_prefixed, r#"{"v2_key": "value"}"#);
+    /// ```
+    pub fn add_key_prefix(mut self, prefix: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_add_key_prefix(
+            &self.json, prefix,
+        ));
+
+        self
+    }
+
+    /// Strips the prefix from every JSON key in key position which starts with it.
+    ///
+    /// See [json_key_quote_utils::json_remove_key_prefix].
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix to strip from the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_stripped = JsonKeyQuoteConverter::new(r#"{"v2_key": "value"}"#, Quotes::default())
+    ///     .remove_key_prefix("v2_").json();
+    /// assert_eq!(json_stripped, r#"{"key": "value"}"#);
+    /// ```
+    pub fn remove_key_prefix(mut self, prefix: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_remove_key_prefix(
+            &self.json, prefix,
+        ));
+
+        self
+    }
+
+    /// Appends the suffix to every JSON key in key position.
+    ///
+    /// See [json_key_quote_utils::json_add_key_suffix].
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The suffix to append to the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_suffixed = JsonKeyQuoteConverter::new(r#"{key: "value"}"#, Quotes::default())
+    ///     .add_key_suffix("_v2").add_key_quotes().json();
+    /// assert_eq!(json_suffixed, r#"{"key_v2": "value"}"#);
+    /// ```
+    pub fn add_key_suffix(mut self, suffix: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_add_key_suffix(
+            &self.json, suffix,
+        ));
+
+        self
+    }
+
+    /// Strips the suffix from every JSON key in key position which ends with it.
+    ///
+    /// See [json_key_quote_utils::json_remove_key_suffix].
+    ///
+    /// # Arguments
+    ///
+    /// * `suffix` - The suffix to strip from the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_stripped = JsonKeyQuoteConverter::new(r#"{"key_v2": "value"}"#, Quotes::default())
+    ///     .remove_key_suffix("_v2").json();
+    /// assert_eq!(json_stripped, r#"{"key": "value"}"#);
+    /// ```
+    pub fn remove_key_suffix(mut self, suffix: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_remove_key_suffix(
+            &self.json, suffix,
+        ));
+
+        self
+    }
+
+    /// Converts every JSON key in key position to the requested case.
+    ///
+    /// See [json_key_quote_utils::json_convert_key_case] for how the keys are split into words.
+    ///
+    /// # Arguments
+    ///
+    /// * `case` - The case to convert the JSON keys to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, KeyCase, Quotes};
+    ///
+    /// let json_converted = JsonKeyQuoteConverter::new(r#"{userName: "userName"}"#, Quotes::default())
+    ///     .convert_key_case(KeyCase::Snake).add_key_quotes().json();
+    /// assert_eq!(json_converted, r#"{"user_name": "userName"}"#);
+    /// ```
+    pub fn convert_key_case(mut self, case: KeyCase) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_convert_key_case(
+            &self.json, case,
+        ));
+
+        self
+    }
+
+    /// Converts every JSON key in key position to `snake_case`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_converted = JsonKeyQuoteConverter::new(r#"{userName: "userName"}"#, Quotes::default())
+    ///     .keys_to_snake_case().json();
+    /// assert_eq!(json_converted, r#"{user_name: "userName"}"#);
+    /// ```
+    pub fn keys_to_snake_case(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_keys_to_snake_case(&self.json));
+
+        self
+    }
+
+    /// Converts every JSON key in key position to `camelCase`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_converted = JsonKeyQuoteConverter::new(r#"{user_name: "user_name"}"#, Quotes::default())
+    ///     .keys_to_camel_case().json();
+    /// assert_eq!(json_converted, r#"{userName: "user_name"}"#);
+    /// ```
+    pub fn keys_to_camel_case(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_keys_to_camel_case(&self.json));
+
+        self
+    }
+
+    /// Collapses every run of whitespace in the JSON keys to a single space, and trims the keys.
+    ///
+    /// See [json_key_quote_utils::json_collapse_whitespace_in_keys].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_collapsed = JsonKeyQuoteConverter::new("{\"key\n  name\": 1}", Quotes::default())
+    ///     .collapse_key_whitespace().json();
+    /// assert_eq!(json_collapsed, r#"{"key name": 1}"#);
+    /// ```
+    pub fn collapse_key_whitespace(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_collapse_whitespace_in_keys(
+            &self.json,
+        ));
+
+        self
+    }
+
+    /// Merges the overlay JSON object into the JSON object.
+    ///
+    /// See [json_key_quote_utils::json_merge] for how the objects are merged.
+    ///
+    /// # Arguments
+    ///
+    /// * `overlay` - The JSON string to merge into the JSON string of the builder.
+    /// * `strategy` - How to resolve keys which exist in both objects.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    /// use json_keyquotes_convert::json_key_quote_utils::MergeStrategy;
+    ///
+    /// let json_merged = JsonKeyQuoteConverter::new(r#"{a: {b: 1}, c: 2}"#, Quotes::default())
+    ///     .merge(r#"{a: {d: 3}, c: 4}"#, MergeStrategy::TakeLeft)
+    ///     .unwrap()
+    ///     .json();
+    /// assert_eq!(json_merged, r#"{a: {b: 1, d: 3}, c: 2}"#);
+    /// ```
+    pub fn merge(
+        mut self,
+        overlay: &str,
+        strategy: json_key_quote_utils::MergeStrategy,
+    ) -> Result<JsonKeyQuoteConverter, String> {
+        self.update_json(json_key_quote_utils::json_merge(
+            &self.json, overlay, strategy,
+        )?);
+
+        Ok(self)
+    }
+
+    /// Replaces the `NaN` and `Infinity` literals, which are not valid JSON.
+    ///
+    /// See [json_key_quote_utils::json_replace_nonfinite].
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - How to replace the literals.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    /// use json_keyquotes_convert::json_key_quote_utils::NonFinitePolicy;
+    ///
+    /// let json_replaced = JsonKeyQuoteConverter::new("{value: NaN, max: Infinity}", Quotes::default())
+    ///     .replace_nonfinite(NonFinitePolicy::Null)
+    ///     .unwrap()
+    ///     .add_key_quotes()
+    ///     .json();
+    /// assert_eq!(json_replaced, r#"{"value": null, "max": null}"#);
+    /// ```
+    pub fn replace_nonfinite(
+        mut self,
+        policy: json_key_quote_utils::NonFinitePolicy,
+    ) -> Result<JsonKeyQuoteConverter, String> {
+        self.update_json(json_key_quote_utils::json_replace_nonfinite(
+            &self.json, policy,
+        )?);
+
+        Ok(self)
+    }
+
+    /// Converts the array of key-value pairs into an object, quoting the keys with the `quote_type`.
+    ///
+    /// See [json_key_quote_utils::json_array_to_object].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_object = JsonKeyQuoteConverter::new(r#"[["a", 1], ["b", [2]]]"#, Quotes::SingleQuote)
+    ///     .array_to_object()
+    ///     .unwrap()
+    ///     .json();
+    /// assert_eq!(json_object, "{'a': 1, 'b': [2]}");
+    /// ```
+    pub fn array_to_object(mut self) -> Result<JsonKeyQuoteConverter, String> {
+        self.update_json(json_key_quote_utils::json_array_to_object(
+            &self.json,
+            self.options.quote_type,
+        )?);
+
+        Ok(self)
+    }
+
+    /// Converts the object into an array of key-value pairs, the inverse of [JsonKeyQuoteConverter::array_to_object].
+    ///
+    /// See [json_key_quote_utils::json_object_to_array].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_array = JsonKeyQuoteConverter::new("{a: 1, b: [2]}", Quotes::default())
+    ///     .object_to_array()
+    ///     .json();
+    /// assert_eq!(json_array, r#"[["a", 1], ["b", [2]]]"#);
+    /// ```
+    pub fn object_to_array(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_object_to_array(&self.json));
+
+        self
+    }
+
+    /// Sorts the members of every JSON object byte-wise by their keys.
+    ///
+    /// Each member keeps its full text, including its value and quoting style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_sorted = JsonKeyQuoteConverter::new(r#"{"b": 1, a: [{d: 2, 'c': 3}]}"#, Quotes::default())
+    ///     .sort_keys().json();
+    /// assert_eq!(json_sorted, r#"{a: [{'c': 3, d: 2}], "b": 1}"#);
+    /// ```
+    pub fn sort_keys(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_sort_keys(&self.json, false));
+
+        self
+    }
+
+    /// Sorts the members of every JSON object case-insensitively by their keys.
+    ///
+    /// Each member keeps its full text, including its value and quoting style.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_sorted = JsonKeyQuoteConverter::new(r#"{b: 1, C: 2, a: 3}"#, Quotes::default())
+    ///     .sort_keys_case_insensitive().json();
+    /// assert_eq!(json_sorted, r#"{a: 3, b: 1, C: 2}"#);
+    /// ```
+    pub fn sort_keys_case_insensitive(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_sort_keys(&self.json, true));
+
+        self
+    }
+
+    /// Removes all insignificant whitespace from the JSON string.
+    ///
+    /// Whitespace inside single- and double-quoted strings is preserved exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_minified = JsonKeyQuoteConverter::new("{key: \"a value\", other: [1, 2]}", Quotes::default())
+    ///     .minify().add_key_quotes().json();
+    /// assert_eq!(json_minified, r#"{"key":"a value","other":[1,2]}"#);
+    /// ```
+    pub fn minify(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_minify(&self.json));
+
+        self
+    }
+
+    /// Pretty-prints the JSON string with one member per line.
+    ///
+    /// # Arguments
+    ///
+    /// * `indent` - The indentation to use for every nesting level.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{Indent, JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_pretty = JsonKeyQuoteConverter::new(r#"{key:"val",other:[1,2]}"#, Quotes::default())
+    ///     .pretty(Indent::Spaces(2)).json();
+    /// assert_eq!(json_pretty, "{\n  key: \"val\",\n  other: [\n    1,\n    2\n  ]\n}");
+    /// ```
+    pub fn pretty(mut self, indent: Indent) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_pretty(&self.json, indent));
+
+        self
+    }
+
+    /// Flattens nested JSON objects to a single-level object with delimited keys.
+    ///
+    /// See [json_key_quote_utils::json_flatten_keys], the object is rendered on a single line.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The separator to put between the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_flattened = JsonKeyQuoteConverter::new(r#"{"a": {"b": {"c": 1}}}"#, Quotes::default())
+    ///     .flatten_keys(".").json();
+    /// assert_eq!(json_flattened, r#"{"a.b.c": 1}"#);
+    /// ```
+    pub fn flatten_keys(mut self, separator: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_flatten_keys(
+            &self.json, separator,
+        ));
+
+        self
+    }
+
+    /// Reconstructs the nested JSON objects from a single-level object with delimited keys.
+    ///
+    /// See [json_key_quote_utils::json_unflatten_keys], the objects are rendered on a single line.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` - The separator between the keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_unflattened = JsonKeyQuoteConverter::new(r#"{"a.b.c": 1}"#, Quotes::default())
+    ///     .unflatten_keys(".").json();
+    /// assert_eq!(json_unflattened, r#"{"a": {"b": {"c": 1}}}"#);
+    /// ```
+    pub fn unflatten_keys(mut self, separator: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_unflatten_keys(
+            &self.json, separator,
+        ));
+
+        self
+    }
+
+    /// Adds a sibling key `<key>_type` with the type of the value after every member,
+    /// quoted with the quote type of the builder.
+    ///
+    /// See [json_key_quote_utils::json_add_type_annotations].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_annotated = JsonKeyQuoteConverter::new("{a: 'b', c: {}}", Quotes::SingleQuote)
+    ///     .add_type_annotations()
+    ///     .json();
+    /// assert_eq!(json_annotated, r#"{a: 'b', 'a_type': "string", c: {}, 'c_type': "object"}"#);
+    /// ```
+    pub fn add_type_annotations(mut self) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_add_type_annotations(
+            &self.json,
+            self.options.quote_type,
+        ));
+
+        self
+    }
+
+    /// Replaces the values of all object entries with the key by `value`,
+    /// see [json_key_quote_utils::json_replace_value].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the values to replace.
+    /// * `value` - The new value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_replaced = JsonKeyQuoteConverter::new(r#"{host: "localhost", port: 80}"#, Quotes::default())
+    ///     .replace_value("port", "8080").json();
+    /// assert_eq!(json_replaced, r#"{host: "localhost", port: 8080}"#);
+    /// ```
+    pub fn replace_value(mut self, key: &str, value: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_replace_value(
+            &self.json, key, value,
+        ));
+
+        self
+    }
+
+    /// Wraps the JSON value in an object with a single key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the wrapping object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_wrapped = JsonKeyQuoteConverter::new("[1, 2]", Quotes::default())
+    ///     .wrap("items").json();
+    /// assert_eq!(json_wrapped, r#"{"items": [1, 2]}"#);
+    /// ```
+    pub fn wrap(mut self, key: &str) -> JsonKeyQuoteConverter {
+        self.update_json(json_key_quote_utils::json_wrap_in_object(
+            &self.json,
+            key,
+            self.options.quote_type,
+        ));
+
+        self
+    }
+
+    /// Extracts the value of the key from an object with only that key.
+    ///
+    /// Returns `None` if the JSON string is not a structurally valid object with `key` as its single key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the wrapping object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_unwrapped = JsonKeyQuoteConverter::new("{items: [1, 2]}", Quotes::default())
+    ///     .unwrap("items")
+    ///     .map(|converter| converter.json());
+    /// assert_eq!(json_unwrapped, Some("[1, 2]".to_string()));
+    /// ```
+    pub fn unwrap(mut self, key: &str) -> Option<JsonKeyQuoteConverter> {
+        self.update_json(json_key_quote_utils::json_unwrap_object(&self.json, key)?);
+
+        Some(self)
+    }
+
+    /// Applies a custom transformation to the JSON string.
+    ///
+    /// The closure borrows the JSON string, use [JsonKeyQuoteConverter::map_json]
+    /// to move the JSON string into the closure instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The transformation, returning the new JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_replaced = JsonKeyQuoteConverter::new(r#"{deprecated_key: "val"}"#, Quotes::default())
+    ///     .then(|json| json.replace("deprecated_key", "new_key"))
+    ///     .json();
+    /// assert_eq!(json_replaced, r#"{new_key: "val"}"#);
+    /// ```
+    pub fn then(mut self, f: impl FnOnce(&str) -> String) -> JsonKeyQuoteConverter {
+        self.update_json(f(&self.json));
+
+        self
+    }
+
+    /// Applies a custom transformation which takes ownership of the JSON string.
+    ///
+    /// Unlike [JsonKeyQuoteConverter::then], which borrows the JSON string, the closure receives the owned `String`,
+    /// so it can be moved into a transformation which consumes it without copying.
+    /// Because the original JSON string is moved, [JsonKeyQuoteConverter::changed] reports a change
+    /// regardless of the returned JSON string.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The transformation, receiving the JSON string and returning the new one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_mapped = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .map_json(|json| json + "\n")
+    ///     .add_key_quotes()
+    ///     .json();
+    /// assert_eq!(json_mapped, "{\"key\": \"val\"}\n");
+    /// ```
+    pub fn map_json(mut self, f: impl FnOnce(String) -> String) -> JsonKeyQuoteConverter {
+        self.json = f(std::mem::take(&mut self.json));
+        self.changed = true;
+
+        self
+    }
+
+    /// Applies the conversions to each of the concatenated JSON documents, like `{a: 1}\n{b: 2}`, separately.
+    ///
+    /// Every document is converted by its own builder with the same options,
+    /// the whitespace and comments in between the documents are kept.
+    /// See [json_key_quote_utils::json_split_documents] for how the documents are found.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The conversions of a single document.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_converted = JsonKeyQuoteConverter::new("{a: 1}\n// second\n{b: 2}", Quotes::default())
+    ///     .convert_each(|document| document.add_key_quotes())
+    ///     .json();
+    /// assert_eq!(json_converted, "{\"a\": 1}\n// second\n{\"b\": 2}");
+    /// ```
+    pub fn convert_each(
+        mut self,
+        f: impl Fn(JsonKeyQuoteConverter) -> JsonKeyQuoteConverter,
+    ) -> JsonKeyQuoteConverter {
+        let mut stats = self.stats;
+        let json = json_key_quote_utils::json_convert_each(&self.json, |document| {
+            let converted = f(JsonKeyQuoteConverter::with_options(document, &self.options));
+            stats += converted.stats();
+            converted.json()
+        });
+        self.stats = stats;
+        self.update_json(json);
+
+        self
+    }
+
+    /// Inspects the JSON string, e.g. for logging, without changing it.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The inspection, receiving the current JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_added = JsonKeyQuoteConverter::new(r#"{key: "val"}"#, Quotes::default())
+    ///     .peek(|json| println!("before: {}", json))
+    ///     .add_key_quotes()
+    ///     .json();
+    /// assert_eq!(json_added, r#"{"key": "val"}"#);
+    /// ```
+    pub fn peek(self, f: impl FnOnce(&str)) -> JsonKeyQuoteConverter {
+        f(&self.json);
+
+        self
+    }
+
+    /// Checks that no keys were added or removed since the `original` JSON string.
+    ///
+    /// Returns the [json_key_quote_utils::JsonKeyDiff] as the error if any keys were added or removed,
+    /// see [json_key_quote_utils::json_diff_keys].
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - The original JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let original = r#"{key: "val"}"#;
+    /// let json_checked = JsonKeyQuoteConverter::new(original, Quotes::default())
+    ///     .add_key_quotes()
+    ///     .assert_no_key_diff(original)
+    ///     .map(|converter| converter.json());
+    /// assert_eq!(json_checked, Ok(r#"{"key": "val"}"#.to_string()));
+    ///
+    /// let diff = JsonKeyQuoteConverter::new(original, Quotes::default())
+    ///     .then(|json| json.replace("key", "other"))
+    ///     .assert_no_key_diff(original)
+    ///     .unwrap_err();
+    /// assert_eq!(diff.removed, vec!["key".to_string()]);
+    /// ```
+    pub fn assert_no_key_diff(
+        self,
+        original: &str,
+    ) -> Result<JsonKeyQuoteConverter, json_key_quote_utils::JsonKeyDiff> {
+        let diff = json_key_quote_utils::json_diff_keys(original, &self.json);
+        if !diff.is_empty() {
+            return Err(diff);
+        }
+
+        Ok(self)
+    }
+
+    /// Applies the operations in order.
+    ///
+    /// Stops at the first failing operation, returning an [OperationError].
+    ///
+    /// # Arguments
+    ///
+    /// * `ops` - The operations to apply.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Operation, Quotes};
+    ///
+    /// let json_applied = JsonKeyQuoteConverter::new("{key: 'va\nl'}", Quotes::default())
+    ///     .apply(&[
+    ///         Operation::AddKeyQuotes(Quotes::DoubleQuote),
+    ///         Operation::NormalizeValueQuotes(Quotes::DoubleQuote),
+    ///         Operation::EscapeCtrlChars,
+    ///     ])
+    ///     .unwrap()
+    ///     .json();
+    /// assert_eq!(json_applied, r#"{"key": "va\nl"}"#);
+    ///
+    /// let err = JsonKeyQuoteConverter::new("{key: [1}", Quotes::default())
+    ///     .apply(&[Operation::Minify, Operation::Validate])
+    ///     .unwrap_err();
+    /// assert_eq!(err.index, 1);
+    /// ```
+    pub fn apply(mut self, ops: &[Operation]) -> Result<JsonKeyQuoteConverter, OperationError> {
+        for (index, operation) in ops.iter().enumerate() {
+            let fail = |message: String| OperationError {
+                index,
+                operation: operation.clone(),
+                message,
+            };
+
+            self = match operation {
+                Operation::AddKeyQuotes(quote_type) => {
+                    self.add_key_quotes_of(*quote_type, |_| {});
+                    self
+                }
+                Operation::RemoveKeyQuotes => self.remove_key_quotes(),
+                Operation::EscapeCtrlChars => self.escape_ctrlchars(),
+                Operation::UnescapeCtrlChars => self.unescape_ctrlchars(),
+                Operation::NormalizeValueQuotes(target) => self.convert_value_quotes(*target),
+                Operation::StripComments => {
+                    self.update_json(json_key_quote_utils::json_strip_comments(&self.json));
+                    self
+                }
+                Operation::EscapeForwardSlash => self.escape_forward_slash(),
+                Operation::UnescapeForwardSlash => self.unescape_forward_slash(),
+                Operation::EscapeHtml => self.escape_html(),
+                Operation::UnescapeHtml => self.unescape_html(),
+                Operation::ConvertKeyCase(case) => self.convert_key_case(*case),
+                Operation::SortKeys => self.sort_keys(),
+                Operation::Minify => self.minify(),
+                Operation::Pretty(indent) => self.pretty(*indent),
+                Operation::Wrap(key) => self.wrap(key),
+                Operation::Unwrap(key) => self.unwrap(key).ok_or_else(|| {
+                    fail(format!(
+                        "The JSON string is not an object with only the key `{}`",
+                        key
+                    ))
+                })?,
+                Operation::Validate => self.validate().map_err(fail)?,
+            };
+        }
+
+        Ok(self)
+    }
+
+    /// Checks whether the braces and brackets in the JSON string are balanced.
+    ///
+    /// This can be inserted into a chain to fail fast on structurally invalid input.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_validated = JsonKeyQuoteConverter::new(r#"{key: "val"}"#, Quotes::default())
+    ///     .validate()
+    ///     .map(|converter| converter.add_key_quotes().json());
+    /// assert_eq!(json_validated, Ok(r#"{"key": "val"}"#.to_string()));
+    ///
+    /// let json_invalid = JsonKeyQuoteConverter::new(r#"{key: ["val"}"#, Quotes::default())
+    ///     .validate();
+    /// assert!(json_invalid.is_err());
+    /// ```
+    pub fn validate(self) -> Result<JsonKeyQuoteConverter, String> {
+        json_key_quote_utils::json_validate_structure(&self.json)?;
+
+        Ok(self)
+    }
+
+    /// Checks whether the JSON string is valid JSON, using `serde_json`.
+    ///
+    /// Unlike [JsonKeyQuoteConverter::validate], this requires strictly valid JSON,
+    /// so it is typically called after [JsonKeyQuoteConverter::add_key_quotes]
+    /// and [JsonKeyQuoteConverter::escape_ctrlchars].
+    ///
+    /// Only available with the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let json_validated = JsonKeyQuoteConverter::new("{key: \"va\nl\"}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars()
+    ///     .validate_json()
+    ///     .map(|converter| converter.json());
+    /// assert_eq!(json_validated.unwrap(), r#"{"key": "va\nl"}"#);
+    ///
+    /// let err = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .validate_json()
+    ///     .unwrap_err();
+    /// assert_eq!((err.line, err.column), (1, 2));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn validate_json(self) -> Result<JsonKeyQuoteConverter, ValidationError> {
+        serde_json::from_str::<serde_json::Value>(&self.json)?;
+
+        Ok(self)
+    }
+
+    /// Parses the relaxed JSON string into a [serde_json::Value].
+    ///
+    /// See [json_key_quote_utils::json_parse_relaxed] for how the JSON string is made valid first.
+    ///
+    /// Only available with the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let value = JsonKeyQuoteConverter::new("{key: 'va\nl'}", Quotes::default())
+    ///     .to_value()
+    ///     .unwrap();
+    /// assert_eq!(value, serde_json::json!({"key": "va\nl"}));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_value(self) -> Result<serde_json::Value, ValidationError> {
+        json_key_quote_utils::json_parse_relaxed(&self.json)
+    }
+
+    /// Parses the JSON string into a [serde_json::Value], as strictly valid JSON.
+    ///
+    /// Unlike [JsonKeyQuoteConverter::to_value], the JSON string is not made valid first,
+    /// so the keys should be double-quoted and the ctrl-characters escaped.
+    ///
+    /// Only available with the `serde` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let value = JsonKeyQuoteConverter::new("{key: \"va\nl\"}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars()
+    ///     .into_json_value()
+    ///     .unwrap();
+    /// assert_eq!(value, serde_json::json!({"key": "va\nl"}));
+    ///
+    /// let result = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default()).into_json_value();
+    /// assert!(result.is_err());
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn into_json_value(self) -> Result<serde_json::Value, serde_json::Error> {
+        serde_json::from_str(&self.json)
+    }
+
+    /// Serializes the flat JSON object to a URL query string.
+    ///
+    /// See [json_key_quote_utils::json_to_query_string].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let query = JsonKeyQuoteConverter::new("{foo: 'bar baz', baz: 42}", Quotes::default())
+    ///     .to_query_string();
+    /// assert_eq!(query, Ok("foo=bar%20baz&baz=42".to_string()));
+    /// ```
+    pub fn to_query_string(self) -> Result<String, String> {
+        json_key_quote_utils::json_to_query_string(&self.json)
+    }
+
+    /// Writes the JSON string to the writer, see [load_write_utils::write_json_to_writer].
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - The writer, like a file, stdout or a network socket.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut buffer = Vec::new();
+    /// JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .to_writer(&mut buffer)
+    ///     .unwrap();
+    /// assert_eq!(buffer, b"{\"key\": \"val\"}");
+    /// ```
+    pub fn to_writer<W: std::io::Write>(self, writer: W) -> Result<(), std::io::Error> {
+        load_write_utils::write_json_to_writer(writer, &self.json)
+    }
+
+    /// Writes the JSON string to the file, see [load_write_utils::write_json].
+    ///
+    /// The builder is not consumed, so the same JSON string can be written to multiple files.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{load_write_utils, JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let path = std::env::temp_dir().join("json_keyquotes_convert_write_to_file.json");
+    /// std::fs::write(&path, "{key: \"va\nl\"}").unwrap();
+    ///
+    /// let converter = JsonKeyQuoteConverter::from_file(&path, Quotes::default())?
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars();
+    /// converter.write_to_file(&path)?;
+    /// assert_eq!(load_write_utils::load_json(&path)?, "{\"key\": \"va\\nl\"}");
+    /// # std::fs::remove_file(&path)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_to_file(&self, path: &std::path::Path) -> Result<(), std::io::Error> {
+        load_write_utils::write_json(path, &self.json)
+    }
+
+    /// Writes the JSON string to the file, see [load_write_utils::async_write_json].
+    ///
+    /// Only available with the `tokio` feature.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file path.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use std::path::Path;
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let path = Path::new("./test_resources/Test_without_keyquotes.json");
+    /// JsonKeyQuoteConverter::from_file_async(path, Quotes::default()).await?
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars()
+    ///     .to_file_async(path)
+    ///     .await?;
+    /// ```
+    #[cfg(feature = "tokio")]
+    pub async fn to_file_async(self, path: &std::path::Path) -> Result<(), std::io::Error> {
+        load_write_utils::async_write_json(path, &self.json).await
+    }
+
+    /// Returns the amount of object keys in the JSON string, at all nesting depths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new(r#"{"a": {"b": 1}, c: [{"d": 2}]}"#, Quotes::default());
+    /// assert_eq!(converter.key_count(), 4);
+    /// ```
+    pub fn key_count(&self) -> usize {
+        json_key_quote_utils::json_count_keys(&self.json)
+    }
+
+    /// Returns the maximum nesting depth of the objects and arrays in the JSON string.
+    ///
+    /// See [json_key_quote_utils::json_count_nesting_depth].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new("{a: [{b: 1}], c: 2}", Quotes::default());
+    /// assert_eq!(converter.max_depth(), 3);
+    /// ```
+    pub fn max_depth(&self) -> usize {
+        json_key_quote_utils::json_count_nesting_depth(&self.json)
+    }
+
+    /// Returns all string values in the JSON string, without their quotes.
+    ///
+    /// See [json_key_quote_utils::json_extract_values].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new(r#"{"a": "b", c: ['d', 1]}"#, Quotes::default());
+    /// assert_eq!(converter.values(), vec!["b", "d"]);
+    /// ```
+    pub fn values(&self) -> Vec<String> {
+        json_key_quote_utils::json_extract_values(&self.json)
+    }
+
+    /// Returns the string values of the members with the key, without their quotes.
+    ///
+    /// See [json_key_quote_utils::json_extract_values_for_key].
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the string values, without quotes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new(r#"{"a": "b", c: {a: 'd'}}"#, Quotes::default());
+    /// assert_eq!(converter.values_for_key("a"), vec!["b", "d"]);
+    /// ```
+    pub fn values_for_key(&self, key: &str) -> Vec<String> {
+        json_key_quote_utils::json_extract_values_for_key(&self.json, key)
+    }
+
+    /// Removes the key-quotes, except from the keys for which `keep` returns `true`.
+    fn remove_key_quotes_except(&mut self, keep: impl Fn(&str) -> bool) {
+        let json_removed = json_key_quote_utils::json_remove_custom_key_quotes(
+            &self.json,
+            self.options.quote_type,
+        );
+        let json = json_key_quote_utils::restore_key_quotes(&self.json, &json_removed, keep);
+        self.stats += json_key_quote_utils::count_key_quote_changes(&self.json, &json);
+        self.update_json(json);
+    }
+
+    /// Replaces the JSON string with the converted one, keeping track of whether it changed.
+    fn update_json(&mut self, json: String) {
+        if json != self.json {
+            self.changed = true;
+            self.json = json;
+        }
+    }
+
+    /// Returns the JSON string.
     ///
     /// # Examples
     ///
@@ -173,4 +2580,960 @@ impl JsonKeyQuoteConverter {
     pub fn json(self) -> String {
         self.json
     }
+
+    /// Returns the JSON string as UTF-8 bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let bytes = JsonKeyQuoteConverter::new("{key: \"val\"}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .into_bytes();
+    /// assert_eq!(bytes, b"{\"key\": \"val\"}");
+    /// ```
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.json.into_bytes()
+    }
+
+    /// Returns the amount of keys and values changed by the key-quote and ctrl-character conversions so far.
+    ///
+    /// The stats are accumulated across the chained conversions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new("{key: \"va\nl\", \"other\": 1}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars();
+    /// assert_eq!(converter.stats().keys_quoted, 1);
+    /// assert_eq!(converter.stats().values_escaped, 1);
+    /// ```
+    pub fn stats(&self) -> json_key_quote_utils::ConversionStats {
+        self.stats
+    }
+
+    /// Whether any conversion modified the JSON string since the builder was created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default())
+    ///     .add_key_quotes()
+    ///     .escape_ctrlchars();
+    /// assert!(!converter.changed());
+    ///
+    /// let converter = converter.remove_key_quotes();
+    /// assert!(converter.changed());
+    /// ```
+    pub fn changed(&self) -> bool {
+        self.changed
+    }
+
+    /// Returns the lines which differ between the `original` and the current JSON string,
+    /// as a unified diff without context lines.
+    ///
+    /// The line numbers of the removed lines refer to the `original`.
+    /// See [json_key_quote_utils::diff_lines].
+    ///
+    /// # Arguments
+    ///
+    /// * `original` - The original JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let original = "{\n  key: \"value\"\n}";
+    /// let converter = JsonKeyQuoteConverter::new(original, Quotes::default()).add_key_quotes();
+    /// assert_eq!(converter.diff(original), "@@ -2,1 +2,1 @@\n-  key: \"value\"\n+  \"key\": \"value\"\n");
+    /// ```
+    pub fn diff(&self, original: &str) -> String {
+        json_key_quote_utils::diff_lines(original, &self.json)
+    }
+
+    /// Returns a reference to the JSON string, without consuming the builder.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let converter = JsonKeyQuoteConverter::new(r#"{key: "value"}"#, Quotes::default());
+    /// assert_eq!(converter.json_ref(), r#"{key: "value"}"#);
+    /// ```
+    pub fn json_ref(&self) -> &str {
+        &self.json
+    }
+
+    /// Returns the JSON string and the [Quotes].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let (json, quote_type) = JsonKeyQuoteConverter::new(r#"{key: "value"}"#, Quotes::SingleQuote)
+    ///     .into_inner();
+    /// assert_eq!(json, r#"{key: "value"}"#);
+    /// assert_eq!(quote_type, Quotes::SingleQuote);
+    /// ```
+    pub fn into_inner(self) -> (String, Quotes) {
+        (self.json, self.options.quote_type)
+    }
+
+    /// Replaces the JSON string, keeping the [Quotes] and [ConvertOptions].
+    ///
+    /// This resets [JsonKeyQuoteConverter::changed].
+    ///
+    /// # Arguments
+    ///
+    /// * `json` - The new JSON string.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use json_keyquotes_convert::{JsonKeyQuoteConverter, Quotes};
+    ///
+    /// let mut converter = JsonKeyQuoteConverter::new(r#"{key: "value"}"#, Quotes::default());
+    /// converter.set_json(r#"{other: "value"}"#).add_key_quotes_mut();
+    /// assert_eq!(converter.json_ref(), r#"{"other": "value"}"#);
+    /// ```
+    pub fn set_json(&mut self, json: &str) -> &mut JsonKeyQuoteConverter {
+        self.json = String::from(json);
+        self.changed = false;
+
+        self
+    }
+
+    /// Adds key-quotes of the quote type in the dialect of the options, counting the quoted keys
+    /// and calling `on_key` for every key.
+    fn add_key_quotes_of(
+        &mut self,
+        quote_type: Quotes,
+        on_key: impl FnMut(json_key_quote_utils::KeyEvent),
+    ) {
+        let json = json_key_quote_utils::add_key_quotes_reporting(
+            &self.json,
+            quote_type,
+            self.options.dialect,
+            on_key,
+        );
+        self.stats += json_key_quote_utils::count_key_quote_changes(&self.json, &json);
+        self.update_json(json);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ConvertOptions, Dialect, Indent, InvalidQuoteChar, JsonKeyQuoteConverter, Operation,
+        ParseQuotesError, Quotes,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_debug_quotes() {
+        assert_eq!("DoubleQuote", format!("{:?}", Quotes::DoubleQuote));
+        assert_eq!("SingleQuote", format!("{:?}", Quotes::SingleQuote));
+    }
+
+    #[test]
+    fn test_quotes_from_str_display() {
+        let names = [
+            ("double", Quotes::DoubleQuote),
+            ("DOUBLE", Quotes::DoubleQuote),
+            ("\"", Quotes::DoubleQuote),
+            ("Single", Quotes::SingleQuote),
+            ("'", Quotes::SingleQuote),
+            ("backtick", Quotes::Backtick),
+            ("none", Quotes::NoQuotes),
+            ("§", Quotes::Custom('§')),
+        ];
+        for (name, expected) in names {
+            assert_eq!(Ok(expected), name.parse::<Quotes>());
+            assert_eq!(Ok(expected), expected.to_string().parse::<Quotes>());
+        }
+
+        assert_eq!(
+            Err(ParseQuotesError("triple".to_string())),
+            "triple".parse::<Quotes>()
+        );
+        assert_eq!(
+            Err(ParseQuotesError("|".to_string())),
+            "|".parse::<Quotes>()
+        );
+        assert_eq!("double", Quotes::DoubleQuote.to_string());
+    }
+
+    #[test]
+    fn test_convert_with_options() {
+        let json = "{\n\t// comment\n\tkey: 'va\nl',\n\tother: [\"b\"]\n}";
+
+        let options = ConvertOptions {
+            quote_type: Quotes::SingleQuote,
+            escape_ctrlchars: true,
+            normalize_value_quotes: true,
+            strip_comments: true,
+            dialect: Dialect::Json,
+        };
+        let expected = "{\n\t\n\t'key': 'va\\nl',\n\t'other': ['b']\n}";
+        let actual = JsonKeyQuoteConverter::with_options(json, &options)
+            .convert()
+            .json();
+        assert_eq!(expected, actual);
+
+        let expected_default = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .add_key_quotes()
+            .json();
+        let actual_default = JsonKeyQuoteConverter::with_options(json, &ConvertOptions::default())
+            .convert()
+            .json();
+        assert_eq!(expected_default, actual_default);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_convert_options_serde() {
+        let options = ConvertOptions {
+            quote_type: Quotes::Backtick,
+            escape_ctrlchars: true,
+            ..ConvertOptions::default()
+        };
+
+        let serialized = serde_json::to_string(&options).unwrap();
+        assert_eq!(
+            r#"{"quote_type":"backtick","escape_ctrlchars":true,"normalize_value_quotes":false,"strip_comments":false,"dialect":"json"}"#,
+            serialized
+        );
+        assert_eq!(options, serde_json::from_str(&serialized).unwrap());
+
+        let partial: ConvertOptions = serde_json::from_str(r#"{"quote_type": "Single"}"#).unwrap();
+        assert_eq!(Quotes::SingleQuote, partial.quote_type);
+        assert!(!partial.strip_comments);
+
+        assert!(serde_json::from_str::<Quotes>(r#""triple""#).is_err());
+    }
+
+    #[test]
+    fn test_quotes_eq_hash() {
+        let quote_names = HashMap::from([
+            (Quotes::DoubleQuote, "double"),
+            (Quotes::SingleQuote, "single"),
+        ]);
+
+        assert_eq!(Quotes::DoubleQuote, Quotes::default());
+        assert_ne!(Quotes::SingleQuote, Quotes::default());
+        assert_eq!(Some(&"double"), quote_names.get(&Quotes::default()));
+    }
+
+    #[test]
+    fn test_debug_json_key_quote_converter() {
+        let converter = JsonKeyQuoteConverter::new(r#"{key: "val"}"#, Quotes::SingleQuote);
+
+        let actual = format!("{:?}", converter);
+
+        assert_eq!(
+            r#"JsonKeyQuoteConverter { json: "{key: \"val\"}", quote_type: SingleQuote }"#,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_clone_json_key_quote_converter_independent() {
+        let converter = JsonKeyQuoteConverter::new(r#"{key: "val"}"#, Quotes::default());
+        let cloned = converter.clone();
+        assert_eq!(converter, cloned);
+
+        let added = converter.add_key_quotes();
+        let removed = cloned.remove_key_quotes();
+
+        assert_eq!(r#"{"key": "val"}"#, added.clone().json());
+        assert_eq!(r#"{key: "val"}"#, removed.clone().json());
+        assert_ne!(added, removed);
+        assert_ne!(
+            JsonKeyQuoteConverter::new("{}", Quotes::DoubleQuote),
+            JsonKeyQuoteConverter::new("{}", Quotes::SingleQuote)
+        );
+    }
+
+    #[test]
+    fn test_clone_mid_chain_json_key_quote_converter() {
+        let mid_chain = JsonKeyQuoteConverter::new("{b: 'va\nl', a: [1, 2]}", Quotes::default())
+            .add_key_quotes()
+            .escape_ctrlchars();
+
+        let pretty = mid_chain.clone().sort_keys().pretty(Indent::Spaces(2));
+        let minified = mid_chain.clone().minify();
+
+        assert!(pretty.changed());
+        assert!(minified.changed());
+        assert_eq!(mid_chain.stats(), minified.stats());
+        assert_eq!(
+            "{\n  \"a\": [\n    1,\n    2\n  ],\n  \"b\": 'va\\nl'\n}",
+            pretty.json()
+        );
+        assert_eq!(r#"{"b":'va\nl',"a":[1,2]}"#, minified.json());
+        assert_eq!(r#"{"b": 'va\nl', "a": [1, 2]}"#, mid_chain.json());
+    }
+
+    #[test]
+    fn test_mut_methods_reuse_converter() {
+        let mut converter = JsonKeyQuoteConverter::new("", Quotes::SingleQuote);
+
+        for (json, expected) in [
+            ("{a: \"b\nc\"}", "{'a': \"b\\nc\"}"),
+            ("{d: [1]}", "{'d': [1]}"),
+        ] {
+            converter
+                .set_json(json)
+                .add_key_quotes_mut()
+                .escape_ctrlchars_mut();
+            assert_eq!(expected, converter.json_ref());
+            assert_eq!(
+                JsonKeyQuoteConverter::new(json, Quotes::SingleQuote)
+                    .add_key_quotes()
+                    .escape_ctrlchars()
+                    .json(),
+                converter.json_ref()
+            );
+
+            converter.unescape_ctrlchars_mut().remove_key_quotes_mut();
+            assert_eq!(json, converter.json_ref());
+        }
+
+        assert_eq!(
+            ("{d: [1]}".to_string(), Quotes::SingleQuote),
+            converter.into_inner()
+        );
+    }
+
+    #[test]
+    fn test_apply_equals_chained_methods() {
+        let json = "{key: \"va\nl\", other: {a: 'b'}}";
+
+        let expected = JsonKeyQuoteConverter::new(json, Quotes::SingleQuote)
+            .add_key_quotes()
+            .escape_ctrlchars();
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::SingleQuote)
+            .apply(&[
+                Operation::AddKeyQuotes(Quotes::SingleQuote),
+                Operation::EscapeCtrlChars,
+            ])
+            .unwrap();
+        assert_eq!(expected.stats(), actual.stats());
+        assert_eq!(expected, actual);
+
+        let json5 = "{a: +5, b: {c: NaN, d: Infinity}}";
+        let expected_json5 = JsonKeyQuoteConverter::new(json5, Quotes::default())
+            .dialect(Dialect::Json5)
+            .add_key_quotes();
+        let actual_json5 = JsonKeyQuoteConverter::new(json5, Quotes::default())
+            .dialect(Dialect::Json5)
+            .apply(&[Operation::AddKeyQuotes(Quotes::DoubleQuote)])
+            .unwrap();
+        assert_eq!(expected_json5.stats(), actual_json5.stats());
+        assert_eq!(4, actual_json5.stats().keys_quoted);
+        assert_eq!(expected_json5, actual_json5);
+        assert_eq!(
+            r#"{"a": +5, "b": {"c": NaN, "d": Infinity}}"#,
+            actual_json5.json()
+        );
+
+        let expected_pretty = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .wrap("data")
+            .pretty(Indent::Tab)
+            .json();
+        let actual_pretty = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .apply(&[
+                Operation::Wrap("data".to_string()),
+                Operation::Validate,
+                Operation::Pretty(Indent::Tab),
+            ])
+            .unwrap()
+            .json();
+        assert_eq!(expected_pretty, actual_pretty);
+
+        let err = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .apply(&[
+                Operation::RemoveKeyQuotes,
+                Operation::Unwrap("data".to_string()),
+                Operation::Minify,
+            ])
+            .unwrap_err();
+        assert_eq!(1, err.index);
+        assert_eq!(Operation::Unwrap("data".to_string()), err.operation);
+    }
+
+    #[test]
+    fn test_apply_accumulates_stats() {
+        let json = "{a: 1, b: {c: 'd'}}";
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .apply(&[Operation::AddKeyQuotes(Quotes::SingleQuote)])
+            .unwrap()
+            .apply(&[
+                Operation::RemoveKeyQuotes,
+                Operation::AddKeyQuotes(Quotes::DoubleQuote),
+            ])
+            .unwrap();
+
+        assert_eq!(6, actual.stats().keys_quoted);
+        assert_eq!(3, actual.stats().keys_unquoted);
+        assert_eq!(r#"{"a": 1, "b": {"c": 'd'}}"#, actual.json());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_operation_serde() {
+        let ops = vec![
+            Operation::AddKeyQuotes(Quotes::Backtick),
+            Operation::EscapeCtrlChars,
+            Operation::Pretty(Indent::Spaces(2)),
+            Operation::ConvertKeyCase(crate::KeyCase::Snake),
+        ];
+
+        let serialized = serde_json::to_string(&ops).unwrap();
+        assert_eq!(
+            r#"[{"add_key_quotes":"backtick"},"escape_ctrl_chars",{"pretty":{"spaces":2}},{"convert_key_case":"snake"}]"#,
+            serialized
+        );
+        assert_eq!(
+            ops,
+            serde_json::from_str::<Vec<Operation>>(&serialized).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_stats_accumulate_across_chain() {
+        let converter =
+            JsonKeyQuoteConverter::new("{a: \"b\nc\", 'd': [{e: 'f\tg'}]}", Quotes::default())
+                .add_key_quotes()
+                .escape_ctrlchars()
+                .unescape_ctrlchars()
+                .remove_key_quotes()
+                .add_key_quotes();
+
+        let stats = converter.stats();
+        assert_eq!(5, stats.keys_quoted);
+        assert_eq!(3, stats.keys_unquoted);
+        assert_eq!(2, stats.values_escaped);
+        assert_eq!(2, stats.values_unescaped);
+        assert_eq!(
+            JsonKeyQuoteConverter::new("", Quotes::default()).stats(),
+            Default::default()
+        );
+    }
+
+    #[test]
+    fn test_changed() {
+        let mut converter =
+            JsonKeyQuoteConverter::new("{\"key\": \"val\"}", Quotes::default()).add_key_quotes();
+        assert!(!converter.changed());
+
+        converter.remove_key_quotes_mut();
+        assert!(converter.changed());
+
+        converter.add_key_quotes_mut();
+        assert!(converter.changed());
+
+        converter.set_json("{key: 1}");
+        assert!(!converter.changed());
+    }
+
+    #[test]
+    fn test_from_path_buf() {
+        let path = std::path::PathBuf::from("./test_resources/Test_without_keyquotes.json");
+        let expected = JsonKeyQuoteConverter::from_file(&path, Quotes::default()).unwrap();
+
+        assert_eq!(JsonKeyQuoteConverter::from(path), expected);
+        assert!(JsonKeyQuoteConverter::from_file(
+            std::path::Path::new("./test_resources/missing.json"),
+            Quotes::default()
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "could not load ./test_resources/missing.json")]
+    fn test_from_path_buf_panics() {
+        let _ =
+            JsonKeyQuoteConverter::from(std::path::PathBuf::from("./test_resources/missing.json"));
+    }
+
+    #[test]
+    fn test_from_file_write_to_file() {
+        let path = std::path::Path::new("./tmp_builder_from_file");
+        let other_path = std::path::Path::new("./tmp_builder_write_to_file");
+        std::fs::copy("./test_resources/Test_without_keyquotes.json", path).unwrap();
+
+        let converter = JsonKeyQuoteConverter::from_file(path, Quotes::DoubleQuote)
+            .unwrap()
+            .add_key_quotes()
+            .escape_ctrlchars();
+        converter.write_to_file(path).unwrap();
+        converter.write_to_file(other_path).unwrap();
+
+        let actual = crate::load_write_utils::load_json(path).unwrap();
+        let other_actual = crate::load_write_utils::load_json(other_path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        std::fs::remove_file(other_path).unwrap();
+        let expected = crate::load_write_utils::load_json(std::path::Path::new(
+            "./test_resources/Test_with_keyquotes.json",
+        ))
+        .unwrap();
+        assert_eq!(actual, expected);
+        assert_eq!(other_actual, expected);
+    }
+
+    #[test]
+    fn test_escape_ctrlchars_all_ctrlchars() {
+        let ctrlchars: String = ('\u{1}'..='\u{1f}').collect();
+        let json = format!("{{key: \"{}\"}}", ctrlchars);
+        let escaped_ctrlchars = concat!(
+            r#"\u0001\u0002\u0003\u0004\u0005\u0006\u0007\b\t\n\u000B\f\r\u000E\u000F"#,
+            r#"\u0010\u0011\u0012\u0013\u0014\u0015\u0016\u0017\u0018\u0019\u001A\u001B\u001C\u001D\u001E\u001F"#,
+        );
+
+        let converter = JsonKeyQuoteConverter::new(&json, Quotes::default()).escape_ctrlchars();
+        assert_eq!(
+            converter.json_ref(),
+            format!("{{key: \"{}\"}}", escaped_ctrlchars)
+        );
+        assert_eq!(converter.stats().values_escaped, 1);
+        assert_eq!(converter.unescape_ctrlchars().json(), json);
+
+        let options = ConvertOptions {
+            escape_ctrlchars: true,
+            ..ConvertOptions::default()
+        };
+        let actual = JsonKeyQuoteConverter::with_options(&json, &options)
+            .convert()
+            .json();
+        assert_eq!(actual, format!("{{\"key\": \"{}\"}}", escaped_ctrlchars));
+    }
+
+    #[test]
+    fn test_from_bytes_into_bytes() {
+        let bytes = "{k\u{e9}y: \"va\nl\"}".as_bytes();
+
+        let actual = JsonKeyQuoteConverter::from_bytes(bytes, Quotes::default())
+            .unwrap()
+            .into_bytes();
+        assert_eq!(actual, bytes);
+
+        let actual = JsonKeyQuoteConverter::from_bytes(b"{key: \"va\nl\"}", Quotes::default())
+            .unwrap()
+            .add_key_quotes()
+            .into_bytes();
+        assert_eq!(actual, b"{\"key\": \"va\nl\"}");
+
+        assert!(JsonKeyQuoteConverter::from_bytes(&[b'{', 0xff, b'}'], Quotes::default()).is_err());
+    }
+
+    #[test]
+    fn test_from_reader_to_writer() {
+        let reader = std::io::Cursor::new("{key: \"va\nl\"}".as_bytes());
+        let mut writer = std::io::Cursor::new(Vec::new());
+
+        JsonKeyQuoteConverter::from_reader(reader, Quotes::SingleQuote)
+            .unwrap()
+            .add_key_quotes()
+            .escape_ctrlchars()
+            .to_writer(&mut writer)
+            .unwrap();
+
+        let written = writer.into_inner();
+        assert_eq!(b"{'key': \"va\\nl\"}".to_vec(), written);
+        assert_eq!(
+            "{'key': \"va\\nl\"}",
+            crate::load_write_utils::load_json_from_reader(written.as_slice()).unwrap()
+        );
+        assert!(JsonKeyQuoteConverter::from_reader(&[0xc3, 0x28][..], Quotes::default()).is_err());
+    }
+
+    #[test]
+    fn test_default_json_key_quote_converter() {
+        let converter = JsonKeyQuoteConverter::default();
+
+        assert_eq!(JsonKeyQuoteConverter::new("", Quotes::default()), converter);
+        assert_eq!("", converter.json());
+    }
+
+    #[test]
+    fn test_debug_json_key_quote_converter_truncates_json() {
+        let json = "{key: \"".to_string() + &"€".repeat(200) + "\"}";
+        let converter = JsonKeyQuoteConverter::new(&json, Quotes::default());
+
+        let actual = format!("{:?}", converter);
+
+        assert!(actual.contains(&("{key: \\\"".to_string() + &"€".repeat(93) + "...\"")));
+        assert!(!actual.contains(&"€".repeat(94)));
+        assert!(actual.contains("quote_type: DoubleQuote"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_value_round_trip() {
+        let value = serde_json::json!({
+            "key": "va\nl\t\u{1}\"",
+            "nested": {"list": [1, "two", null, {"three": true}]}
+        });
+
+        let json_relaxed = JsonKeyQuoteConverter::from_value(&value, Quotes::default())
+            .remove_key_quotes()
+            .json();
+        let actual = JsonKeyQuoteConverter::new(&json_relaxed, Quotes::default())
+            .add_key_quotes()
+            .json();
+        let actual_value: serde_json::Value = serde_json::from_str(&actual).unwrap();
+
+        assert_eq!(
+            r#"{key:"va\nl\t\u0001\"",nested:{list:[1,"two",null,{three:true}]}}"#,
+            json_relaxed
+        );
+        assert_eq!(value, actual_value);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_value_round_trip() {
+        let value = serde_json::json!({
+            "key": "va\nl\t\"",
+            "nested": {"list": [1, "two", null, {"three": true}]}
+        });
+
+        let converter = JsonKeyQuoteConverter::new_from_json_value(&value, Quotes::default())
+            .remove_key_quotes()
+            .unescape_ctrlchars();
+        assert!(converter.clone().into_json_value().is_err());
+
+        let actual = converter
+            .add_key_quotes()
+            .escape_ctrlchars()
+            .into_json_value()
+            .unwrap();
+        assert_eq!(value, actual);
+    }
+
+    #[test]
+    fn test_then_between_builder_methods() {
+        let json = "{deprecated_key: \"va\nl\"}";
+        let expected = r#"{"new_key": "va\nl"}"#;
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .add_key_quotes()
+            .then(|json| json.replace("deprecated_key", "new_key"))
+            .escape_ctrlchars()
+            .json();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_map_json_between_builder_methods() {
+        let json = "{key: \"va\nl\"}";
+        let expected = r#"{"key": "va\nl"}"#;
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .map_json(|json| String::from_utf8(json.into_bytes()).unwrap())
+            .add_key_quotes()
+            .escape_ctrlchars();
+
+        assert!(actual.changed());
+        assert_eq!(expected, actual.json());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_replace_nonfinite_into_json_value() {
+        use crate::json_key_quote_utils::NonFinitePolicy;
+
+        let json = r#"{value: NaN, "text": "NaN", max: -Infinity}"#;
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .replace_nonfinite(NonFinitePolicy::StringLiteral)
+            .unwrap()
+            .add_key_quotes()
+            .into_json_value()
+            .unwrap();
+        let actual_error = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .replace_nonfinite(NonFinitePolicy::Error);
+
+        assert_eq!(
+            serde_json::json!({"value": "NaN", "text": "NaN", "max": "-Infinity"}),
+            actual
+        );
+        assert!(actual_error.is_err());
+    }
+
+    #[test]
+    fn test_add_type_annotations_uses_quote_type() {
+        let json = r#"{"a": [1], b: null}"#;
+        let expected = r#"{"a": [1], `a_type`: "array", b: null, `b_type`: "null"}"#;
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::Backtick).add_type_annotations();
+        let actual_removed = JsonKeyQuoteConverter::new(json, Quotes::Backtick)
+            .add_type_annotations()
+            .remove_key_quotes()
+            .json();
+
+        assert!(actual.changed());
+        assert_eq!(expected, actual.json());
+        assert_eq!(
+            r#"{a: [1], a_type: "array", b: null, b_type: "null"}"#,
+            actual_removed
+        );
+    }
+
+    #[test]
+    fn test_dialect() {
+        let json = "{a: +5, b: NaN, c: .5}";
+        let expected_json = "{a: +5, b: NaN, \"c\": .5}";
+        let expected_json5 = r#"{"a": +5, "b": NaN, "c": .5}"#;
+
+        let actual_json = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .add_key_quotes()
+            .json();
+        let actual_json5 = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .dialect(Dialect::Json5)
+            .add_key_quotes();
+
+        assert_eq!(expected_json, actual_json);
+        assert_eq!(actual_json5.stats().keys_quoted, 3);
+        assert_eq!(expected_json5, actual_json5.json_ref());
+        assert_eq!(
+            json,
+            JsonKeyQuoteConverter::new(expected_json5, Quotes::default())
+                .dialect(Dialect::Json5)
+                .remove_key_quotes()
+                .json()
+        );
+    }
+
+    #[test]
+    fn test_dialect_mongo_shell() {
+        let json = "{_id: ObjectId('5f1d'), count: NumberInt(3)}";
+        let expected = r#"{'_id': {'$oid': "5f1d"}, 'count': {'$numberInt': "3"}}"#;
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::SingleQuote)
+            .dialect(Dialect::MongoShell)
+            .add_key_quotes()
+            .json();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_remove_key_quotes_keep_reserved_custom_quotes() {
+        let json = "{§null§: 1, §key§: {§false§: 2}}";
+        let expected = "{§null§: 1, key: {§false§: 2}}";
+
+        let actual =
+            JsonKeyQuoteConverter::new(json, Quotes::Custom('§')).remove_key_quotes_keep_reserved();
+
+        assert!(actual.changed());
+        assert_eq!(expected, actual.json());
+    }
+
+    #[test]
+    fn test_add_key_quotes_from_depth() {
+        let json = "{level1: {level2: {level3: 'x'}}, other: [1]}";
+        let expected = r#"{level1: {"level2": {"level3": 'x'}}, other: [1]}"#;
+
+        let actual =
+            JsonKeyQuoteConverter::new(json, Quotes::default()).add_key_quotes_from_depth(2);
+
+        assert_eq!(actual.stats().keys_quoted, 2);
+        assert_eq!(expected, actual.json());
+    }
+
+    #[test]
+    fn test_add_remove_key_quotes_if() {
+        let json = r#"{"lower": 1, "Upper": {"nested": [{"x-id": 2, "deep": 3}], "MiXed": 4}, 'snake_case': 5, "camelCase": 6}"#;
+        let expected_removed = r#"{lower: 1, "Upper": {nested: [{"x-id": 2, deep: 3}], "MiXed": 4}, snake_case: 5, "camelCase": 6}"#;
+        let is_lowercase = |key: &str| key.chars().all(|c| c.is_ascii_lowercase() || c == '_');
+
+        let actual_removed =
+            JsonKeyQuoteConverter::new(json, Quotes::default()).remove_key_quotes_if(is_lowercase);
+        let actual_added = JsonKeyQuoteConverter::new(actual_removed.json_ref(), Quotes::default())
+            .add_key_quotes_if(|key| key != "deep");
+
+        assert_eq!(actual_removed.stats().keys_unquoted, 4);
+        assert_eq!(expected_removed, actual_removed.json());
+        assert_eq!(actual_added.stats().keys_quoted, 3);
+        assert_eq!(
+            r#"{"lower": 1, "Upper": {"nested": [{"x-id": 2, deep: 3}], "MiXed": 4}, "snake_case": 5, "camelCase": 6}"#,
+            actual_added.json()
+        );
+    }
+
+    #[test]
+    fn test_empty_and_scalar_json_key_quote_converter() {
+        for json in ["", "   \n", "42"] {
+            let converted = JsonKeyQuoteConverter::new(json, Quotes::default())
+                .add_key_quotes()
+                .escape_ctrlchars()
+                .remove_key_quotes()
+                .unescape_ctrlchars();
+
+            assert!(!converted.changed(), "json: {:?}", json);
+            assert_eq!(json, converted.json());
+        }
+
+        let escaped = JsonKeyQuoteConverter::new("\"va\nl\"", Quotes::default())
+            .add_key_quotes()
+            .escape_ctrlchars();
+        assert_eq!(1, escaped.stats().values_escaped);
+        assert_eq!(r#""va\nl""#, escaped.json());
+    }
+
+    #[test]
+    fn test_convert_each() {
+        let json = "{a: \"b\nc\"} // first\n{d: 1}";
+        let expected = "{\"a\": \"b\\nc\"} // first\n{\"d\": 1}";
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .convert_each(|document| document.add_key_quotes().escape_ctrlchars());
+
+        assert!(actual.changed());
+        assert_eq!(actual.stats().keys_quoted, 2);
+        assert_eq!(expected, actual.json());
+    }
+
+    #[test]
+    fn test_peek_between_builder_methods() {
+        let json = "{key: \"va\nl\"}";
+        let mut peeked = Vec::new();
+
+        let expected = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .add_key_quotes()
+            .escape_ctrlchars()
+            .json();
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::default())
+            .peek(|json| peeked.push(json.to_string()))
+            .add_key_quotes()
+            .peek(|json| peeked.push(json.to_string()))
+            .escape_ctrlchars()
+            .json();
+
+        assert_eq!(expected, actual);
+        assert_eq!(vec![json, "{\"key\": \"va\nl\"}"], peeked);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_relaxed_string_from_relaxed_str_round_trip() {
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Inner {
+            text: String,
+            tags: Vec<String>,
+            ratio: Option<f64>,
+        }
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Outer {
+            name: String,
+            inner: Inner,
+            items: Vec<Inner>,
+        }
+
+        let texts = [
+            "",
+            "plain",
+            "va\nl",
+            "\t\r\u{8}\u{c}",
+            "\\n",
+            "it's \"quoted\"",
+            "a/b: {c}, [d]",
+        ];
+        for (i, text) in texts.iter().enumerate() {
+            let inner = |ratio| Inner {
+                text: text.to_string(),
+                tags: texts.iter().skip(i).map(|tag| tag.to_string()).collect(),
+                ratio,
+            };
+            let value = Outer {
+                name: text.repeat(2),
+                inner: inner(None),
+                items: vec![inner(Some(0.5)), inner(None)],
+            };
+
+            for quotes in [Quotes::DoubleQuote, Quotes::SingleQuote] {
+                for pretty in [false, true] {
+                    let style = crate::RelaxedStyle { quotes, pretty };
+                    let json_relaxed = crate::to_relaxed_string(&value, style).unwrap();
+                    let actual: Outer = crate::from_relaxed_str(&json_relaxed).unwrap();
+
+                    assert!(!json_relaxed.contains("\"name\""));
+                    assert_eq!(value, actual, "{}", json_relaxed);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_str() {
+        let converter = "{key: \"val\"}".parse::<JsonKeyQuoteConverter>().unwrap();
+
+        assert_eq!(
+            "JsonKeyQuoteConverter { json: \"{key: \\\"val\\\"}\", quote_type: DoubleQuote }",
+            format!("{:?}", converter)
+        );
+        assert_eq!("{\"key\": \"val\"}", converter.add_key_quotes().json());
+    }
+
+    #[test]
+    fn test_quotes_custom_rejects_key_chars() {
+        assert_eq!(Err(InvalidQuoteChar('|')), Quotes::custom('|'));
+        assert_eq!(Err(InvalidQuoteChar('#')), Quotes::custom('#'));
+        assert_eq!(Err(InvalidQuoteChar(':')), Quotes::custom(':'));
+        assert_eq!(Err(InvalidQuoteChar(' ')), Quotes::custom(' '));
+        assert_eq!(Ok(Quotes::Custom('§')), Quotes::custom('§'));
+    }
+
+    #[test]
+    fn test_custom_quotes_builder_round_trip() {
+        let json = "{key: \"val\"}";
+
+        let actual = JsonKeyQuoteConverter::new(json, Quotes::Custom('|'))
+            .add_key_quotes()
+            .json();
+        let actual_removed = JsonKeyQuoteConverter::new(&actual, Quotes::Custom('|'))
+            .remove_key_quotes()
+            .json();
+
+        assert_eq!("{|key|: \"val\"}", actual);
+        assert_eq!(json, actual_removed);
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_from_file_async_to_file_async() {
+        let path = std::path::Path::new("./tmp_async_without_keyquotes");
+        std::fs::copy("./test_resources/Test_without_keyquotes.json", path).unwrap();
+
+        JsonKeyQuoteConverter::from_file_async(path, Quotes::DoubleQuote)
+            .await
+            .unwrap()
+            .add_key_quotes()
+            .escape_ctrlchars()
+            .to_file_async(path)
+            .await
+            .unwrap();
+        let converted_file_contents = crate::load_write_utils::load_json(path).unwrap();
+        let expected_file_contents = crate::load_write_utils::load_json(std::path::Path::new(
+            "./test_resources/Test_with_keyquotes.json",
+        ))
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(expected_file_contents, converted_file_contents);
+    }
 }
@@ -1,8 +1,44 @@
 //! Functions used to load and write JSON to a file.
 
-use std::{fs, io, path::Path};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
 
-/// Loads JSON from a file to a string.
+use crate::scanner;
+
+/// Options for writing JSON to a file, see [write_json_with_options].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteOptions {
+    /// The suffix of the backup file, like `.bak`.
+    ///
+    /// If set, an existing file is copied to `<file name><suffix>` before it is overwritten,
+    /// replacing any previous backup.
+    pub backup: Option<String>,
+    /// The line endings to write, outside of the string values.
+    pub newline: Newline,
+    /// Whether a newline should be appended if the JSON string does not end with one.
+    pub ensure_trailing_newline: bool,
+    /// Whether the missing parent directories of the file should be created.
+    pub create_dirs: bool,
+}
+
+/// The line endings to write, see [WriteOptions].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Newline {
+    /// Keeps the dominant line ending of the file which is overwritten.
+    #[default]
+    Preserve,
+    /// Writes `\n` line endings.
+    Lf,
+    /// Writes `\r\n` line endings.
+    CrLf,
+    /// Writes `\r\n` line endings on Windows, and `\n` line endings elsewhere.
+    Platform,
+}
+
+/// Loads JSON from a file to a string, see [load_json_from_reader].
 ///
 /// # Arguments
 ///
@@ -18,13 +54,10 @@ use std::{fs, io, path::Path};
 /// let json: String = load_write_utils::load_json(&path).expect("Couldn't load from file!");
 /// ```
 pub fn load_json(path: &Path) -> Result<String, io::Error> {
-    match fs::read_to_string(path) {
-        Ok(val) => return Ok(val),
-        Err(err) => return Err(err),
-    };
+    load_json_from_reader(fs::File::open(path)?)
 }
 
-/// Writes JSON from a string to a file.
+/// Writes JSON from a string to a file as UTF-8, see [write_json_to_writer].
 ///
 /// # Arguments
 ///
@@ -41,8 +74,436 @@ pub fn load_json(path: &Path) -> Result<String, io::Error> {
 /// load_write_utils::write_json(&path, &json).expect("Couldn't write to file!");
 /// ```
 pub fn write_json(path: &Path, json: &str) -> Result<(), io::Error> {
-    match fs::write(path, json) {
-        Ok(_) => return Ok(()),
-        Err(err) => return Err(err),
+    write_json_to_writer(fs::File::create(path)?, json)
+}
+
+/// Writes JSON from a string to a file, honoring the [WriteOptions].
+///
+/// With [Newline::Preserve], the line endings are detected from the existing file.
+/// If the backup can not be created, the file is not written to.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `json` - The JSON string to write.
+/// * `options` - The options, like the suffix of the backup file.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::load_write_utils::{self, WriteOptions};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// let options = WriteOptions { backup: Some(".bak".to_string()), ..WriteOptions::default() };
+/// load_write_utils::write_json_with_options(&path, &json, &options).expect("Couldn't write to file!");
+/// ```
+pub fn write_json_with_options(
+    path: &Path,
+    json: &str,
+    options: &WriteOptions,
+) -> Result<(), io::Error> {
+    let original = match options.newline {
+        Newline::Preserve if path.exists() => load_json(path).ok(),
+        _ => None,
+    };
+    let json = apply_newlines(json, original.as_deref(), options);
+
+    backup_json(path, options)?;
+    create_parent_dirs(path, options)?;
+
+    write_json(path, &json)
+}
+
+/// Creates the missing parent directories of the file if the [WriteOptions] ask for it.
+pub(crate) fn create_parent_dirs(path: &Path, options: &WriteOptions) -> Result<(), io::Error> {
+    match path.parent() {
+        Some(parent) if options.create_dirs && !parent.as_os_str().is_empty() => {
+            fs::create_dir_all(parent)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Applies the line endings and the trailing newline of the [WriteOptions] to the JSON string.
+///
+/// The line endings are only converted in between the tokens, so the string values are kept as-is.
+/// `original` is the JSON string which is overwritten, used by [Newline::Preserve].
+pub(crate) fn apply_newlines(json: &str, original: Option<&str>, options: &WriteOptions) -> String {
+    let newline = match options.newline {
+        Newline::Preserve => original.and_then(dominant_newline),
+        Newline::Lf => Some("\n"),
+        Newline::CrLf => Some("\r\n"),
+        Newline::Platform => Some(if cfg!(windows) { "\r\n" } else { "\n" }),
+    };
+
+    let mut json = match newline {
+        Some(newline) => convert_newlines(json, newline),
+        None => json.to_string(),
+    };
+    if options.ensure_trailing_newline && !json.ends_with('\n') {
+        json.push_str(newline.unwrap_or("\n"));
+    }
+
+    json
+}
+
+/// Returns the line ending used by most lines of the JSON string, if it has multiple lines.
+fn dominant_newline(json: &str) -> Option<&'static str> {
+    let crlf_count = json.matches("\r\n").count();
+    let lf_count = json.matches('\n').count() - crlf_count;
+
+    match (crlf_count, lf_count) {
+        (0, 0) => None,
+        _ if crlf_count > lf_count => Some("\r\n"),
+        _ => Some("\n"),
+    }
+}
+
+/// Converts the line endings in between the tokens of the JSON string to `newline`.
+fn convert_newlines(json: &str, newline: &str) -> String {
+    let convert = |whitespace: &str| whitespace.replace("\r\n", "\n").replace('\n', newline);
+
+    let mut converted = String::with_capacity(json.len());
+    let mut pos = 0;
+    for token in scanner::tokenize(json) {
+        converted.push_str(&convert(&json[pos..token.start]));
+        converted.push_str(token.text(json));
+        pos = token.end;
+    }
+    converted.push_str(&convert(&json[pos..]));
+
+    converted
+}
+
+/// Copies the existing file to its backup file, if the [WriteOptions] have a backup suffix.
+pub(crate) fn backup_json(path: &Path, options: &WriteOptions) -> Result<(), io::Error> {
+    let Some(suffix) = &options.backup else {
+        return Ok(());
+    };
+    if suffix.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the backup suffix can not be empty",
+        ));
+    }
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let mut backup_file_name = path.file_name().unwrap_or_default().to_os_string();
+    backup_file_name.push(suffix);
+    fs::copy(path, path.with_file_name(backup_file_name))?;
+
+    Ok(())
+}
+
+/// Writes JSON from a string to a file atomically.
+///
+/// The JSON string is written to a temporary file next to the file, named `.<file name>.tmp`,
+/// which is synced to disk and then renamed over the file.
+/// If writing fails, the original file is left intact and the temporary file is removed.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// load_write_utils::write_json_atomic(&path, &json).expect("Couldn't write to file!");
+/// ```
+pub fn write_json_atomic(path: &Path, json: &str) -> Result<(), io::Error> {
+    let tmp_path = atomic_tmp_path(path)?;
+
+    let mut file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(&tmp_path)?;
+    let result = file
+        .write_all(json.as_bytes())
+        .and_then(|()| file.sync_all())
+        .and_then(|()| fs::rename(&tmp_path, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+
+    result
+}
+
+/// Returns the path of the temporary file used by [write_json_atomic], `.<file name>.tmp`.
+fn atomic_tmp_path(path: &Path) -> Result<PathBuf, io::Error> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", path.display()),
+        )
+    })?;
+    let mut tmp_file_name = std::ffi::OsString::from(".");
+    tmp_file_name.push(file_name);
+    tmp_file_name.push(".tmp");
+
+    Ok(path.with_file_name(tmp_file_name))
+}
+
+/// Loads JSON from a reader to a string, reading until EOF.
+///
+/// A UTF-8 byte order mark is stripped, and UTF-16LE and UTF-16BE are decoded
+/// if a byte order mark indicates them. Without a byte order mark the JSON is read as UTF-8.
+/// Returns an [io::ErrorKind::InvalidData] error if the JSON can not be decoded.
+///
+/// # Arguments
+///
+/// * `reader` - The reader, like a file, stdin or a network socket.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Cursor;
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let json = load_write_utils::load_json_from_reader(Cursor::new("{key: \"val\"}")).unwrap();
+/// assert_eq!(json, "{key: \"val\"}");
+/// ```
+pub fn load_json_from_reader<R: Read>(mut reader: R) -> Result<String, io::Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    decode_json(bytes)
+}
+
+/// Decodes the JSON bytes to a string, see [load_json_from_reader].
+fn decode_json(bytes: Vec<u8>) -> Result<String, io::Error> {
+    let invalid_data = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+
+    let from_utf16 = |bytes: &[u8], from_bytes: fn([u8; 2]) -> u16| {
+        if !bytes.chunks_exact(2).remainder().is_empty() {
+            return Err(invalid_data(
+                "stream did not contain valid UTF-16".to_string(),
+            ));
+        }
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|unit| from_bytes([unit[0], unit[1]]))
+            .collect();
+        String::from_utf16(&units).map_err(|err| invalid_data(err.to_string()))
+    };
+
+    match bytes.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => {
+            String::from_utf8(rest.to_vec()).map_err(|err| invalid_data(err.to_string()))
+        }
+        [0xFF, 0xFE, rest @ ..] => from_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => from_utf16(rest, u16::from_be_bytes),
+        _ => String::from_utf8(bytes).map_err(|err| invalid_data(err.to_string())),
+    }
+}
+
+/// Writes JSON from a string to a writer, and flushes it.
+///
+/// # Arguments
+///
+/// * `writer` - The writer, like a file, stdout or a network socket.
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let mut buffer = Vec::new();
+/// load_write_utils::write_json_to_writer(&mut buffer, "{key: \"val\"}").unwrap();
+/// assert_eq!(buffer, b"{key: \"val\"}");
+/// ```
+pub fn write_json_to_writer<W: Write>(mut writer: W, json: &str) -> Result<(), io::Error> {
+    writer.write_all(json.as_bytes())?;
+    writer.flush()
+}
+
+/// Loads JSON from stdin to a string, reading until EOF, see [load_json_from_reader].
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let json: String = load_write_utils::load_json_stdin().expect("Couldn't load from stdin!");
+/// ```
+pub fn load_json_stdin() -> Result<String, io::Error> {
+    load_json_from_reader(io::stdin().lock())
+}
+
+/// Writes JSON from a string to stdout, without a trailing newline.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// load_write_utils::write_json_stdout(&json).expect("Couldn't write to stdout!");
+/// ```
+pub fn write_json_stdout(json: &str) -> Result<(), io::Error> {
+    write_json_to_writer(io::stdout().lock(), json)
+}
+
+/// Writes JSON from a string to stdout, followed by a newline.
+///
+/// # Arguments
+///
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// load_write_utils::write_json_stdout_ln(&json).expect("Couldn't write to stdout!");
+/// ```
+pub fn write_json_stdout_ln(json: &str) -> Result<(), io::Error> {
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(json.as_bytes())?;
+    writeln!(stdout)?;
+    stdout.flush()
+}
+
+/// Loads JSON from a file to a string, without blocking the async executor.
+///
+/// Only available with the `tokio` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// let json: String = load_write_utils::async_load_json(&path).await.expect("Couldn't load from file!");
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn async_load_json(path: &Path) -> Result<String, io::Error> {
+    decode_json(tokio::fs::read(path).await?)
+}
+
+/// Writes JSON from a string to a file, without blocking the async executor.
+///
+/// Only available with the `tokio` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// load_write_utils::async_write_json(&path, &json).await.expect("Couldn't write to file!");
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn async_write_json(path: &Path, json: &str) -> Result<(), io::Error> {
+    tokio::fs::write(path, json).await
+}
+
+/// Writes JSON from a string to a file atomically, without blocking the async executor.
+///
+/// See [write_json_atomic]. Only available with the `tokio` feature.
+///
+/// # Arguments
+///
+/// * `path` - The file path.
+/// * `json` - The JSON string to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use std::path::Path;
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let path = Path::new("./test_resources/Test_with_keyquotes.json");
+/// load_write_utils::async_write_json_atomic(&path, &json).await.expect("Couldn't write to file!");
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn async_write_json_atomic(path: &Path, json: &str) -> Result<(), io::Error> {
+    let tmp_path = atomic_tmp_path(path)?;
+
+    let result = async {
+        tokio::fs::write(&tmp_path, json).await?;
+        tokio::fs::File::open(&tmp_path).await?.sync_all().await?;
+        tokio::fs::rename(&tmp_path, path).await
+    }
+    .await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&tmp_path).await;
     }
+
+    result
+}
+
+/// Loads JSON from all files matching the glob pattern.
+///
+/// Returns the `(path, json)` pairs in sorted path order.
+///
+/// # Arguments
+///
+/// * `pattern` - The glob pattern, like `./config/*.json`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let files = load_write_utils::load_json_glob("./test_resources/*.json").expect("Couldn't load from files!");
+/// ```
+pub fn load_json_glob(pattern: &str) -> Result<Vec<(PathBuf, String)>, io::Error> {
+    let paths =
+        glob::glob(pattern).map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+
+    let mut paths = paths
+        .collect::<Result<Vec<PathBuf>, glob::GlobError>>()
+        .map_err(io::Error::from)?;
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| load_json(&path).map(|json| (path, json)))
+        .collect()
+}
+
+/// Writes all `(path, json)` pairs to their files.
+///
+/// Stops at and returns the first error encountered.
+///
+/// # Arguments
+///
+/// * `pairs` - The file paths with the JSON strings to write.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use json_keyquotes_convert::{load_write_utils};
+///
+/// let files = load_write_utils::load_json_glob("./test_resources/*.json").expect("Couldn't load from files!");
+/// load_write_utils::write_json_bulk(&files).expect("Couldn't write to files!");
+/// ```
+pub fn write_json_bulk(pairs: &[(PathBuf, String)]) -> Result<(), io::Error> {
+    pairs
+        .iter()
+        .try_for_each(|(path, json)| write_json(path, json))
 }
@@ -0,0 +1,309 @@
+//! A string-aware scanner for (relaxed) JSON.
+//!
+//! Used by the structural transformations which need to know where the keys,
+//! values and string boundaries are, instead of matching them with regular expressions.
+
+/// The kind of a scanned [Token].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    ObjectStart,
+    ObjectEnd,
+    ArrayStart,
+    ArrayEnd,
+    Colon,
+    Comma,
+    /// An object key, with the quote character surrounding it (if any).
+    ///
+    /// Besides single and double quotes, keys can be surrounded by backticks.
+    Key(Option<char>),
+    /// A quoted string value, with the quote character surrounding it.
+    String(char),
+    /// A bare value, like a number, `true`, `false` or `null`.
+    Literal,
+}
+
+/// A token in the JSON string.
+///
+/// `start` and `end` are byte offsets into the scanned string,
+/// the quotes around keys and strings are included in the range.
+/// `depth` is the amount of objects and arrays the token is nested in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+    pub depth: usize,
+}
+
+impl Token {
+    /// Returns the text of the token.
+    pub fn text<'a>(&self, json: &'a str) -> &'a str {
+        &json[self.start..self.end]
+    }
+
+    /// Returns the text of the token without its surrounding quotes.
+    pub fn inner_text<'a>(&self, json: &'a str) -> &'a str {
+        match self.kind {
+            TokenKind::Key(Some(quote)) | TokenKind::String(quote) => {
+                let text = self.text(json);
+                let text = text.strip_prefix(quote).unwrap_or(text);
+                text.strip_suffix(quote).unwrap_or(text)
+            }
+            _ => self.text(json),
+        }
+    }
+}
+
+/// Whether the character is insignificant whitespace.
+///
+/// The byte order mark is treated as whitespace too.
+pub(crate) fn is_whitespace(c: char) -> bool {
+    c.is_whitespace() || c == '\u{feff}'
+}
+
+/// Returns the byte offset right after the string starting at `start`.
+///
+/// `start` must point at the opening quote. Escaped quotes are skipped.
+/// An unterminated string runs until the end of the input.
+pub(crate) fn string_end(json: &str, start: usize, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in json[start + quote.len_utf8()..].char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if c == quote {
+            return start + quote.len_utf8() + i + c.len_utf8();
+        }
+    }
+    json.len()
+}
+
+/// Splits the JSON string into [Token]s.
+///
+/// The scanner never fails: malformed input results in a best-effort token stream.
+/// Whitespace is skipped, every other byte of the input is part of exactly one token.
+pub(crate) fn tokenize(json: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut stack: Vec<char> = Vec::new();
+    let mut expect_key = false;
+    let mut pos = 0;
+
+    while let Some(c) = json[pos..].chars().next() {
+        if is_whitespace(c) {
+            pos += c.len_utf8();
+            continue;
+        }
+
+        let depth = stack.len();
+        let (kind, end) = match c {
+            '{' => {
+                stack.push('{');
+                expect_key = true;
+                (TokenKind::ObjectStart, pos + 1)
+            }
+            '[' => {
+                stack.push('[');
+                expect_key = false;
+                (TokenKind::ArrayStart, pos + 1)
+            }
+            '}' | ']' => {
+                stack.pop();
+                expect_key = false;
+                let kind = if c == '}' {
+                    TokenKind::ObjectEnd
+                } else {
+                    TokenKind::ArrayEnd
+                };
+                (kind, pos + 1)
+            }
+            ':' => {
+                expect_key = false;
+                (TokenKind::Colon, pos + 1)
+            }
+            ',' => {
+                expect_key = stack.last() == Some(&'{');
+                (TokenKind::Comma, pos + 1)
+            }
+            '"' | '\'' => {
+                let end = string_end(json, pos, c);
+                let kind = if expect_key {
+                    TokenKind::Key(Some(c))
+                } else {
+                    TokenKind::String(c)
+                };
+                expect_key = false;
+                (kind, end)
+            }
+            '`' if expect_key => {
+                expect_key = false;
+                (TokenKind::Key(Some(c)), string_end(json, pos, c))
+            }
+            _ if expect_key => {
+                expect_key = false;
+                bare_key(json, pos)
+            }
+            _ => (TokenKind::Literal, literal_end(json, pos)),
+        };
+
+        tokens.push(Token {
+            kind,
+            start: pos,
+            end,
+            depth: match kind {
+                TokenKind::ObjectEnd | TokenKind::ArrayEnd => stack.len(),
+                _ => depth,
+            },
+        });
+        pos = end;
+    }
+
+    tokens
+}
+
+/// Scans an unquoted key starting at `start`.
+///
+/// The key runs until the colon, its trailing whitespace is not included.
+/// If a structural character is found before the colon, the text is scanned as a literal instead.
+fn bare_key(json: &str, start: usize) -> (TokenKind, usize) {
+    for (i, c) in json[start..].char_indices() {
+        match c {
+            ':' => {
+                let key = json[start..start + i].trim_end_matches(is_whitespace);
+                return (TokenKind::Key(None), start + key.len());
+            }
+            ',' | '{' | '}' | '[' | ']' => break,
+            _ => (),
+        }
+    }
+    (TokenKind::Literal, literal_end(json, start))
+}
+
+/// Returns the byte offset right after the bare value starting at `start`.
+fn literal_end(json: &str, start: usize) -> usize {
+    json[start..]
+        .char_indices()
+        .find(|&(_, c)| is_whitespace(c) || matches!(c, ',' | ':' | '{' | '}' | '[' | ']'))
+        .map_or(json.len(), |(i, _)| start + i)
+}
+
+/// A `(key, value)` member of an object.
+pub(crate) type Member = (Token, Node);
+
+/// A parsed JSON value, referring back to the scanned string.
+#[derive(Debug)]
+pub(crate) enum Node {
+    /// An object with its members.
+    Object {
+        start: usize,
+        end: usize,
+        members: Vec<Member>,
+    },
+    /// An array with its items.
+    Array {
+        start: usize,
+        end: usize,
+        items: Vec<Node>,
+    },
+    /// A string or a literal.
+    Scalar(Token),
+}
+
+impl Node {
+    /// Returns the byte offset of the start of the value.
+    pub fn start(&self) -> usize {
+        match self {
+            Node::Object { start, .. } | Node::Array { start, .. } => *start,
+            Node::Scalar(token) => token.start,
+        }
+    }
+
+    /// Returns the byte offset right after the end of the value.
+    pub fn end(&self) -> usize {
+        match self {
+            Node::Object { end, .. } | Node::Array { end, .. } => *end,
+            Node::Scalar(token) => token.end,
+        }
+    }
+
+    /// Returns the text of the value.
+    pub fn text<'a>(&self, json: &'a str) -> &'a str {
+        &json[self.start()..self.end()]
+    }
+}
+
+/// Parses the JSON string into a single [Node].
+///
+/// Returns `None` if the input is not exactly one structurally valid value.
+/// Trailing commas are accepted.
+pub(crate) fn parse(json: &str) -> Option<Node> {
+    let tokens = tokenize(json);
+    let mut pos = 0;
+    let node = parse_value(&tokens, &mut pos)?;
+    if pos == tokens.len() {
+        Some(node)
+    } else {
+        None
+    }
+}
+
+fn parse_value(tokens: &[Token], pos: &mut usize) -> Option<Node> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    match token.kind {
+        TokenKind::ObjectStart => {
+            let mut members = Vec::new();
+            loop {
+                let next = tokens.get(*pos)?;
+                match next.kind {
+                    TokenKind::ObjectEnd => break,
+                    TokenKind::Key(_) => {
+                        let key = *next;
+                        *pos += 1;
+                        if tokens.get(*pos)?.kind != TokenKind::Colon {
+                            return None;
+                        }
+                        *pos += 1;
+                        members.push((key, parse_value(tokens, pos)?));
+                    }
+                    _ => return None,
+                }
+                match tokens.get(*pos)?.kind {
+                    TokenKind::Comma => *pos += 1,
+                    TokenKind::ObjectEnd => (),
+                    _ => return None,
+                }
+            }
+            let end = tokens[*pos].end;
+            *pos += 1;
+            Some(Node::Object {
+                start: token.start,
+                end,
+                members,
+            })
+        }
+        TokenKind::ArrayStart => {
+            let mut items = Vec::new();
+            loop {
+                if tokens.get(*pos)?.kind == TokenKind::ArrayEnd {
+                    break;
+                }
+                items.push(parse_value(tokens, pos)?);
+                match tokens.get(*pos)?.kind {
+                    TokenKind::Comma => *pos += 1,
+                    TokenKind::ArrayEnd => (),
+                    _ => return None,
+                }
+            }
+            let end = tokens[*pos].end;
+            *pos += 1;
+            Some(Node::Array {
+                start: token.start,
+                end,
+                items,
+            })
+        }
+        TokenKind::String(_) | TokenKind::Literal => Some(Node::Scalar(token)),
+        _ => None,
+    }
+}
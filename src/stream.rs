@@ -0,0 +1,380 @@
+//! A streaming converter for (relaxed) JSON.
+//!
+//! Used by [crate::json_key_quote_utils::convert_stream] to convert inputs which are too large
+//! to be loaded into a string. Only the scanner state is carried across the chunk boundaries:
+//! the stack of objects and arrays, the current token and the pending key or escape sequence.
+
+use std::io::{self, Write};
+
+use crate::json_key_quote_utils::{
+    self, ConversionStats, Direction, EscapedCtrlchar, DEFAULT_MAX_KEY_LEN,
+};
+use crate::{Dialect, Quotes};
+
+/// The token the [StreamConverter] is in.
+enum State {
+    /// In between the tokens.
+    Structure,
+    /// In a bare value, like a number, `true`, `false` or `null`.
+    Literal,
+    /// In an unquoted key which is buffered until the colon, to be quoted.
+    BareKey(Vec<u8>),
+    /// After the colon of an unquoted key, with the start of its value buffered
+    /// until it decides whether the key is quoted, see [json_key_quote_utils::is_quoted_value].
+    BareKeyValue { key: Vec<u8>, value: Vec<u8> },
+    /// In an unquoted key which is written as-is.
+    BareKeyPassThrough,
+    /// In a quoted key, buffered until the closing quote if its quotes may be removed.
+    QuotedKey {
+        quote: u8,
+        escaped: bool,
+        buffer: Option<Vec<u8>>,
+    },
+    /// In a quoted string value, with the escape sequence which is not complete yet.
+    Value {
+        quote: u8,
+        escape: Vec<u8>,
+        changed: bool,
+    },
+}
+
+/// Converts the JSON string byte by byte, writing the converted bytes to the writer.
+pub(crate) struct StreamConverter<W: Write> {
+    writer: W,
+    /// The quote to add around the unquoted keys, `None` if the key-quotes are removed instead.
+    quote: Option<char>,
+    /// Whether the ctrl-characters are escaped, or unescaped.
+    escape: bool,
+    stack: Vec<u8>,
+    expect_key: bool,
+    state: State,
+    stats: ConversionStats,
+}
+
+impl<W: Write> StreamConverter<W> {
+    pub fn new(writer: W, direction: Direction, quote_type: Quotes) -> Self {
+        let quote = match direction {
+            Direction::AddKeyQuotes => quote_type.as_char(),
+            Direction::RemoveKeyQuotes => None,
+        };
+
+        StreamConverter {
+            writer,
+            quote,
+            escape: direction == Direction::AddKeyQuotes,
+            stack: Vec::new(),
+            expect_key: false,
+            state: State::Structure,
+            stats: ConversionStats::default(),
+        }
+    }
+
+    /// Converts the chunk of the JSON string.
+    pub fn write_chunk(&mut self, chunk: &[u8]) -> Result<(), io::Error> {
+        for &b in chunk {
+            while !self.step(b)? {}
+        }
+
+        Ok(())
+    }
+
+    /// Writes the pending bytes of an unterminated token and returns the stats.
+    pub fn finish(mut self) -> Result<ConversionStats, io::Error> {
+        match std::mem::replace(&mut self.state, State::Structure) {
+            State::BareKey(buffer) => self.writer.write_all(&buffer)?,
+            State::BareKeyValue { key, value } => {
+                self.write_bare_key_and_value(&key, &value)?;
+                return self.finish();
+            }
+            State::QuotedKey {
+                quote,
+                buffer: Some(buffer),
+                ..
+            } => {
+                self.writer.write_all(&[quote])?;
+                self.writer.write_all(&buffer)?;
+            }
+            State::Value { escape, .. } => self.writer.write_all(&escape)?,
+            _ => (),
+        }
+        self.writer.flush()?;
+
+        Ok(self.stats)
+    }
+
+    /// Handles the byte, returns `false` if the byte ended the token and should be handled again.
+    fn step(&mut self, b: u8) -> Result<bool, io::Error> {
+        match &mut self.state {
+            State::Structure => self.structure(b)?,
+            State::Literal => {
+                if is_token_end(b) {
+                    self.state = State::Structure;
+                    return Ok(false);
+                }
+                self.writer.write_all(&[b])?;
+            }
+            State::BareKey(buffer) => match b {
+                b':' => {
+                    self.state = State::BareKeyValue {
+                        key: std::mem::take(buffer),
+                        value: Vec::new(),
+                    };
+                    return Ok(false);
+                }
+                b',' | b'{' | b'}' | b'[' | b']' => {
+                    self.writer.write_all(buffer)?;
+                    self.state = State::Structure;
+                    return Ok(false);
+                }
+                _ => {
+                    buffer.push(b);
+                    if buffer.len() > DEFAULT_MAX_KEY_LEN {
+                        self.writer.write_all(buffer)?;
+                        self.state = State::BareKeyPassThrough;
+                    }
+                }
+            },
+            State::BareKeyValue { key, value } => {
+                value.push(b);
+                if is_value_start_complete(value) || value.len() > DEFAULT_MAX_KEY_LEN {
+                    let (key, value) = (std::mem::take(key), std::mem::take(value));
+                    self.state = State::Structure;
+                    self.write_bare_key_and_value(&key, &value)?;
+                }
+            }
+            State::BareKeyPassThrough => {
+                if matches!(b, b':' | b',' | b'{' | b'}' | b'[' | b']') {
+                    self.state = State::Structure;
+                    return Ok(false);
+                }
+                self.writer.write_all(&[b])?;
+            }
+            State::QuotedKey {
+                quote,
+                escaped,
+                buffer,
+            } => {
+                let quote = *quote;
+                let is_end = !*escaped && b == quote;
+                *escaped = !*escaped && b == b'\\';
+                match buffer {
+                    Some(key) if is_end => {
+                        let key = std::mem::take(key);
+                        self.write_quoted_key(quote, &key)?;
+                        self.state = State::Structure;
+                    }
+                    Some(key) => {
+                        key.push(b);
+                        if key.len() > DEFAULT_MAX_KEY_LEN {
+                            self.writer.write_all(&[quote])?;
+                            self.writer.write_all(key)?;
+                            *buffer = None;
+                        }
+                    }
+                    None => {
+                        self.writer.write_all(&[b])?;
+                        if is_end {
+                            self.state = State::Structure;
+                        }
+                    }
+                }
+            }
+            State::Value {
+                quote,
+                escape,
+                changed,
+            } => {
+                if !escape.is_empty() {
+                    escape.push(b);
+                    if self.escape {
+                        self.writer.write_all(escape)?;
+                        escape.clear();
+                        return Ok(true);
+                    }
+                    match json_key_quote_utils::match_escaped_ctrlchar(escape) {
+                        EscapedCtrlchar::Complete(ctrlchar) => {
+                            write!(self.writer, "{}", ctrlchar)?;
+                            escape.clear();
+                            *changed = true;
+                        }
+                        EscapedCtrlchar::Incomplete => (),
+                        // The byte right after the backslash is always escaped,
+                        // any later byte may end the string.
+                        EscapedCtrlchar::Mismatch if escape.len() == 2 => {
+                            self.writer.write_all(escape)?;
+                            escape.clear();
+                        }
+                        EscapedCtrlchar::Mismatch => {
+                            self.writer.write_all(&escape[..escape.len() - 1])?;
+                            escape.clear();
+                            return Ok(false);
+                        }
+                    }
+                } else if b == b'\\' {
+                    escape.push(b);
+                } else if b == *quote {
+                    self.writer.write_all(&[b])?;
+                    if *changed && self.escape {
+                        self.stats.values_escaped += 1;
+                    } else if *changed {
+                        self.stats.values_unescaped += 1;
+                    }
+                    self.state = State::Structure;
+                } else if let Some(escaped) =
+                    json_key_quote_utils::escape_ctrlchar(b as char).filter(|_| self.escape)
+                {
+                    self.writer.write_all(escaped.as_bytes())?;
+                    *changed = true;
+                } else {
+                    self.writer.write_all(&[b])?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Handles the byte in between the tokens, like the [crate::scanner].
+    fn structure(&mut self, b: u8) -> Result<(), io::Error> {
+        match b {
+            b'{' => {
+                self.stack.push(b);
+                self.expect_key = true;
+            }
+            b'[' => {
+                self.stack.push(b);
+                self.expect_key = false;
+            }
+            b'}' | b']' => {
+                self.stack.pop();
+                self.expect_key = false;
+            }
+            b':' if self.expect_key => {
+                self.expect_key = false;
+                // An empty key, like in `{: 1}`:
+                if let Some(quote) = self.quote {
+                    write!(self.writer, "{}{}", quote, quote)?;
+                    self.stats.keys_quoted += 1;
+                }
+            }
+            b':' => self.expect_key = false,
+            b',' => self.expect_key = self.stack.last() == Some(&b'{'),
+            _ if b.is_ascii_whitespace() => (),
+            b'"' | b'\'' | b'`' if self.expect_key => {
+                self.expect_key = false;
+                // Only keys without added quotes can lose their quotes.
+                let buffer = match self.quote {
+                    None => Some(Vec::new()),
+                    Some(_) => None,
+                };
+                if buffer.is_none() {
+                    self.writer.write_all(&[b])?;
+                }
+                self.state = State::QuotedKey {
+                    quote: b,
+                    escaped: false,
+                    buffer,
+                };
+                return Ok(());
+            }
+            b'"' | b'\'' => {
+                self.state = State::Value {
+                    quote: b,
+                    escape: Vec::new(),
+                    changed: false,
+                };
+            }
+            _ if self.expect_key => {
+                self.expect_key = false;
+                if self.quote.is_some() {
+                    self.state = State::BareKey(vec![b]);
+                    return Ok(());
+                }
+                self.state = State::BareKeyPassThrough;
+            }
+            _ => self.state = State::Literal,
+        }
+
+        self.writer.write_all(&[b])
+    }
+
+    /// Writes the unquoted key, quoted if its value gets key-quotes,
+    /// and converts the buffered start of its value.
+    fn write_bare_key_and_value(&mut self, key: &[u8], value: &[u8]) -> Result<(), io::Error> {
+        let value_start = String::from_utf8_lossy(&value[1..]);
+        let quoted = json_key_quote_utils::is_quoted_value(
+            value_start.trim_start_matches(|c: char| c.is_ascii_whitespace()),
+            Dialect::Json,
+        );
+        self.write_bare_key(key, quoted)?;
+
+        for &b in value {
+            while !self.step(b)? {}
+        }
+
+        Ok(())
+    }
+
+    /// Writes the unquoted key, with the quotes added if `quoted`, followed by its trailing whitespace.
+    fn write_bare_key(&mut self, buffer: &[u8], quoted: bool) -> Result<(), io::Error> {
+        let key_len = buffer.len()
+            - buffer
+                .iter()
+                .rev()
+                .take_while(|b| b.is_ascii_whitespace())
+                .count();
+        let (key, whitespace) = buffer.split_at(key_len);
+
+        match (self.quote, std::str::from_utf8(key)) {
+            // Keys with custom key-quotes are scanned as unquoted keys.
+            (Some(quote), Ok(key))
+                if quoted
+                    && !(key.len() > quote.len_utf8()
+                        && key.starts_with(quote)
+                        && key.ends_with(quote)) =>
+            {
+                let key = json_key_quote_utils::escape_quote(key, quote);
+                write!(self.writer, "{}{}{}", quote, key, quote)?;
+                self.stats.keys_quoted += 1;
+            }
+            _ => self.writer.write_all(key)?,
+        }
+
+        self.writer.write_all(whitespace)
+    }
+
+    /// Writes the quoted key, without its quotes if all of its characters are supported in unquoted keys.
+    fn write_quoted_key(&mut self, quote: u8, key: &[u8]) -> Result<(), io::Error> {
+        let unquotable = !key.contains(&quote)
+            && std::str::from_utf8(key).is_ok_and(|key| {
+                !key.trim().is_empty()
+                    && key.chars().all(json_key_quote_utils::is_supported_key_char)
+            });
+
+        if unquotable {
+            self.stats.keys_unquoted += 1;
+            self.writer.write_all(key)
+        } else {
+            self.writer.write_all(&[quote])?;
+            self.writer.write_all(key)?;
+            self.writer.write_all(&[quote])
+        }
+    }
+}
+
+/// Whether the byte ends a bare value.
+fn is_token_end(b: u8) -> bool {
+    b.is_ascii_whitespace() || matches!(b, b',' | b':' | b'{' | b'}' | b'[' | b']')
+}
+
+/// Whether the buffered colon and value start decide whether the key is quoted:
+/// a value only decides once the bytes after its leading name, like `null` or `ObjectId (`, are read.
+fn is_value_start_complete(value: &[u8]) -> bool {
+    let value = value[1..].trim_ascii_start();
+    let name_len = value
+        .iter()
+        .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$'))
+        .count();
+
+    !value[name_len..].trim_ascii_start().is_empty()
+}
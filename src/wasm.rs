@@ -0,0 +1,58 @@
+//! The `wasm-bindgen` bindings, only available with the `wasm` feature.
+//!
+//! The quote type is passed as a string from JavaScript, like `"double"`, `"single"` or `"backtick"`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{json_key_quote_utils, Quotes};
+
+/// Parses the quote type passed from JavaScript, see [Quotes]'s [std::str::FromStr] implementation.
+fn parse_quotes(quote: &str) -> Result<Quotes, JsValue> {
+    quote
+        .parse()
+        .map_err(|err: crate::ParseQuotesError| JsError::new(&err.to_string()).into())
+}
+
+/// Adds key-quotes to the JSON string, see [json_key_quote_utils::json_add_key_quotes].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+/// * `quote` - Whether the JSON keys should be `"double"`-quoted, `"single"`-quoted or surrounded by `"backtick"`s.
+#[wasm_bindgen(js_name = addKeyQuotes)]
+pub fn add_key_quotes(json: &str, quote: &str) -> Result<String, JsValue> {
+    Ok(json_key_quote_utils::json_add_key_quotes(
+        json,
+        parse_quotes(quote)?,
+    ))
+}
+
+/// Removes key-quotes from the JSON string, see [json_key_quote_utils::json_remove_key_quotes].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+#[wasm_bindgen(js_name = removeKeyQuotes)]
+pub fn remove_key_quotes(json: &str) -> String {
+    json_key_quote_utils::json_remove_key_quotes(json)
+}
+
+/// Escapes the ctrl-characters in the JSON string, see [json_key_quote_utils::json_escape_ctrlchars].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+#[wasm_bindgen(js_name = escapeCtrlchars)]
+pub fn escape_ctrlchars(json: &str) -> String {
+    json_key_quote_utils::json_escape_ctrlchars(json)
+}
+
+/// Unescapes the ctrl-characters in the JSON string, see [json_key_quote_utils::json_unescape_ctrlchars].
+///
+/// # Arguments
+///
+/// * `json` - The JSON string.
+#[wasm_bindgen(js_name = unescapeCtrlchars)]
+pub fn unescape_ctrlchars(json: &str) -> String {
+    json_key_quote_utils::json_unescape_ctrlchars(json)
+}
@@ -0,0 +1,29 @@
+//! The tests of the `wasm` feature, run with `wasm-pack test --node --features wasm`.
+
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use json_keyquotes_convert::wasm;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_add_key_quotes() {
+    let actual = wasm::add_key_quotes("{key: \"va\nl\"}", "single").unwrap();
+    let actual_escaped = wasm::escape_ctrlchars(&actual);
+
+    assert_eq!("{'key': \"va\nl\"}", actual);
+    assert_eq!("{'key': \"va\\nl\"}", actual_escaped);
+}
+
+#[wasm_bindgen_test]
+fn test_add_key_quotes_invalid_quote() {
+    assert!(wasm::add_key_quotes("{key: \"val\"}", "custom").is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_remove_key_quotes() {
+    let actual = wasm::remove_key_quotes("{\"key\": \"va\\nl\"}");
+    let actual_unescaped = wasm::unescape_ctrlchars(&actual);
+
+    assert_eq!("{key: \"va\\nl\"}", actual);
+    assert_eq!("{key: \"va\nl\"}", actual_unescaped);
+}